@@ -2,8 +2,10 @@ use lazy_static::lazy_static;
 use openvm_stark_backend::p3_field::{Field, PrimeField32};
 use serde_json::json;
 use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
 
 use rand::rngs::StdRng;
 use rand::seq::IndexedRandom;
@@ -40,6 +42,92 @@ use openvm_instructions::{
 pub const NUM_LIMBS: usize = 4;
 pub const LIMB_BITS: usize = 8;
 
+// -----------------------------------------------------------------------------
+// Path-based witness injection
+// -----------------------------------------------------------------------------
+
+/// How a single chip-row payload field is perturbed before emission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessMutation {
+    Zero,
+    FlipBit(u32),
+    Random,
+    Add(i64),
+}
+
+/// Surgical injection target: one field (`field_path`, e.g. `"a.2"`) of the chip row of kind
+/// `chip_kind` emitted at instruction step `step`.
+///
+/// When `op_idx` is set, only the chip row at that index within the step
+/// (`chip_row_op_idx_in_step`) is targeted, so steps emitting several rows of the same kind can
+/// be disambiguated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessPathInjection {
+    pub step: u64,
+    pub op_idx: Option<u64>,
+    pub chip_kind: String,
+    pub field_path: String,
+    pub mutation: WitnessMutation,
+}
+
+fn mutate_json_scalar(value: &Value, mutation: WitnessMutation, rng: &mut StdRng) -> Option<Value> {
+    if let Some(b) = value.as_bool() {
+        return Some(json!(match mutation {
+            WitnessMutation::Zero => false,
+            WitnessMutation::Random => rng.random::<bool>(),
+            WitnessMutation::FlipBit(_) | WitnessMutation::Add(_) => !b,
+        }));
+    }
+    if let Some(u) = value.as_u64() {
+        return Some(json!(match mutation {
+            WitnessMutation::Zero => 0,
+            WitnessMutation::FlipBit(bit) => u ^ (1u64 << (bit % 64)),
+            WitnessMutation::Random => u64::from(rng.random::<u32>()),
+            WitnessMutation::Add(delta) => u.wrapping_add_signed(delta),
+        }));
+    }
+    if let Some(i) = value.as_i64() {
+        return Some(json!(match mutation {
+            WitnessMutation::Zero => 0,
+            WitnessMutation::FlipBit(bit) => i ^ (1i64 << (bit % 64)),
+            WitnessMutation::Random => i64::from(rng.random::<i32>()),
+            WitnessMutation::Add(delta) => i.wrapping_add(delta),
+        }));
+    }
+    None
+}
+
+/// Serialize `value` with object keys sorted, independent of serde_json's map ordering.
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
 ////////////////
 // GLOBAL STATE
 /////////
@@ -80,20 +168,59 @@ pub struct GlobalState {
     /// Stored emitted micro-operations.
     pub emitted_micro_ops: Vec<serde_json::Value>,
 
+    /// Cap on `emitted_micro_ops.len()` (`None` = unbounded), so a runaway program degrades to a
+    /// truncated trace instead of exhausting memory before the timeout fires.
+    pub max_micro_ops: Option<usize>,
+    /// Set once a micro-op was dropped at `max_micro_ops` during the current run.
+    pub trace_truncated: bool,
+
+    /// Whether to attribute wall time between micro-op emissions to the emitting step
+    /// (`BEAK_WORKER_OPCODE_TIMING=1`).
+    pub step_timing_enabled: bool,
+    /// Time of the previous micro-op emission while step timing is enabled.
+    pub last_emit_at: Option<Instant>,
+    /// Accumulated nanoseconds per `step_idx` while step timing is enabled.
+    pub step_nanos: BTreeMap<u64, u128>,
+
     //////////////////////////////////////////////////////////////////////////////
     /// TODO: Implement the state for the fault injection (loop2).
     pub injection_enabled: bool,
     pub injection_kind: String,
     pub injection_step: u64,
+    /// When set, `should_inject_witness` only fires for the fill-site row with this index among
+    /// the rows sharing `(kind, injection_step)`, so steps filling several rows can be
+    /// disambiguated.
+    pub injection_op_idx: Option<u64>,
     pub witness_step_idx: u64,
+    /// Rows each fill site has asked `should_inject_witness` about so far, per `(kind, step)`.
+    /// Witness fill runs after execution, so the execution-time `chip_row_op_idx_in_step` does
+    /// not describe the row being filled.
+    pub witness_fill_rows: BTreeMap<(String, u64), u64>,
     pub observed_witness_sites: BTreeMap<String, Vec<u64>>,
     pub assertions_enabled: bool,
+    /// Optional field-level injection applied inside `emit_chip_row_envelope`.
+    pub path_injection: Option<WitnessPathInjection>,
+    /// Set once `path_injection` mutated a row since the last `take_path_injection_applied`.
+    pub path_injection_applied: bool,
+    /// Operand indices `random_mutate_instruction` drew at or above the field order, keyed by the
+    /// canonical `(opcode, operands)` the field reduced the mutated instruction to.
+    pub out_of_field_mutations: BTreeMap<(u32, [u32; 7]), Vec<usize>>,
+    /// `(rs1_val, effective_ptr)` the load/store adapter resolved for the current step, for
+    /// cores whose execution never sees the register value.
+    pub pending_load_store_address: Option<(u32, u32)>,
+    /// Opcode weights `random_mutate_instruction` draws replacement opcodes with.
+    pub opcode_weights: OpcodeWeights,
 
     pub rng: StdRng,
     pub seed: u64,
     //////////////////////////////////////////////////////////////////////////////
 }
 
+/// Fill-site row pinned by `BEAK_OPENVM_WITNESS_INJECT_OP_IDX`, if any.
+fn env_injection_op_idx() -> Option<u64> {
+    std::env::var("BEAK_OPENVM_WITNESS_INJECT_OP_IDX").ok().and_then(|s| s.parse::<u64>().ok())
+}
+
 impl GlobalState {
     fn new() -> Self {
         let injection_kind = std::env::var("BEAK_OPENVM_WITNESS_INJECT_KIND").unwrap_or_default();
@@ -113,22 +240,84 @@ impl GlobalState {
             row_count: 0,
             last_row_id: None,
             emitted_micro_ops: Vec::new(),
+            max_micro_ops: None,
+            trace_truncated: false,
+            step_timing_enabled: std::env::var("BEAK_WORKER_OPCODE_TIMING")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+            last_emit_at: None,
+            step_nanos: BTreeMap::new(),
             injection_enabled: !injection_kind.is_empty(),
             injection_kind,
             injection_step,
+            injection_op_idx: env_injection_op_idx(),
             witness_step_idx: 0,
+            witness_fill_rows: BTreeMap::new(),
             observed_witness_sites: BTreeMap::new(),
             assertions_enabled: false,
+            path_injection: None,
+            path_injection_applied: false,
+            out_of_field_mutations: BTreeMap::new(),
+            pending_load_store_address: None,
+            opcode_weights: OpcodeWeights::default(),
             rng: StdRng::seed_from_u64(0),
             seed: 0,
         }
     }
 
     fn emit_micro_op(&mut self, micro_op: serde_json::Value) {
+        if self.step_timing_enabled {
+            let now = Instant::now();
+            if let Some(prev) = self.last_emit_at {
+                *self.step_nanos.entry(self.step_idx).or_insert(0) +=
+                    now.duration_since(prev).as_nanos();
+            }
+            self.last_emit_at = Some(now);
+        }
+        if self.max_micro_ops.is_some_and(|max| self.emitted_micro_ops.len() >= max) {
+            self.trace_truncated = true;
+            return;
+        }
         self.emitted_micro_ops.push(micro_op);
         self.seq += 1;
     }
 
+    /// Cap the micro-ops recorded per run (0 disables the cap). Called once at worker init.
+    pub fn set_max_micro_ops(&mut self, max: usize) {
+        self.max_micro_ops = (max > 0).then_some(max);
+    }
+
+    /// Replace the opcode weights mutated instructions are drawn with. Called once at worker init.
+    pub fn set_opcode_weights(&mut self, weights: OpcodeWeights) {
+        self.opcode_weights = weights;
+    }
+
+    /// Whether micro-ops were dropped at `max_micro_ops` since the last `take_json_logs`.
+    ///
+    /// Must be read before `take_json_logs`, which resets it for the next run.
+    pub fn trace_truncated(&self) -> bool {
+        self.trace_truncated
+    }
+
+    /// Drain the per-step wall time (nanoseconds) accumulated since the last call.
+    pub fn take_step_nanos(&mut self) -> BTreeMap<u64, u128> {
+        self.last_emit_at = None;
+        std::mem::take(&mut self.step_nanos)
+    }
+
+    /// SHA-256 (hex) over the key-sorted JSON of the micro-ops emitted so far.
+    ///
+    /// Must be read before `take_json_logs` drains the buffer. Golden tests compare this against
+    /// a recorded value to catch unintended changes to the emission format.
+    pub fn micro_ops_digest(&self) -> String {
+        let mut canonical = String::new();
+        for micro_op in &self.emitted_micro_ops {
+            write_canonical_json(micro_op, &mut canonical);
+            canonical.push('\n');
+        }
+        Sha256::digest(canonical.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
     pub fn take_json_logs(&mut self) -> Vec<serde_json::Value> {
         let out = std::mem::take(&mut self.emitted_micro_ops);
         // Reset per-run counters so each backend run starts at step/seq 0.
@@ -139,7 +328,9 @@ impl GlobalState {
         self.chip_row_op_idx_in_step = 0;
         self.row_count = 0;
         self.last_row_id = None;
+        self.trace_truncated = false;
         self.witness_step_idx = 0;
+        self.witness_fill_rows.clear();
         self.observed_witness_sites.clear();
         // Canonicalize Value trees before handing them out.
         //
@@ -169,29 +360,106 @@ impl GlobalState {
         }
     }
 
-    pub fn should_inject_witness(&self, kind: &str, step: u64) -> bool {
-        self.injection_enabled && self.injection_kind == kind && self.injection_step == step
+    pub fn should_inject_witness(&self, kind: &str, step: u64, row_idx: u64) -> bool {
+        self.injection_enabled
+            && self.injection_kind == kind
+            && self.injection_step == step
+            && self.injection_op_idx.is_none_or(|op_idx| op_idx == row_idx)
+    }
+
+    /// Index of the next row a fill site asks about for `(kind, step)`.
+    fn next_witness_fill_row(&mut self, kind: &str, step: u64) -> u64 {
+        let rows = self.witness_fill_rows.entry((kind.to_string(), step)).or_insert(0);
+        let cur = *rows;
+        *rows += 1;
+        cur
+    }
+
+    /// `should_inject_witness` as seen from a witness fill site: records the site and matches
+    /// `injection_op_idx` against the row's index among the rows filled for `(kind, step)`.
+    pub fn should_inject_witness_at_fill_site(&mut self, kind: &str, step: u64) -> bool {
+        self.note_witness_site(kind, step);
+        let row_idx = self.next_witness_fill_row(kind, step);
+        self.should_inject_witness(kind, step, row_idx)
     }
 
     pub fn take_observed_witness_sites(&mut self) -> BTreeMap<String, Vec<u64>> {
         std::mem::take(&mut self.observed_witness_sites)
     }
 
-    pub fn configure_witness_injection(&mut self, kind: Option<&str>, step: u64) {
+    pub fn configure_witness_injection(
+        &mut self,
+        kind: Option<&str>,
+        step: u64,
+        op_idx: Option<u64>,
+    ) {
         match kind {
             Some(k) if !k.is_empty() => {
                 self.injection_enabled = true;
                 self.injection_kind = k.to_string();
                 self.injection_step = step;
+                // Without an explicit row, keep the one pinned by the environment.
+                self.injection_op_idx = op_idx.or_else(env_injection_op_idx);
             }
             _ => {
                 self.injection_enabled = false;
                 self.injection_kind.clear();
                 self.injection_step = 0;
+                self.injection_op_idx = None;
             }
         }
         // Reset witness-local step so each run uses deterministic step numbering.
         self.witness_step_idx = 0;
+        self.witness_fill_rows.clear();
+    }
+
+    pub fn configure_witness_injection_path(
+        &mut self,
+        step: u64,
+        op_idx: Option<u64>,
+        chip_kind: &str,
+        field_path: &str,
+        mutation: WitnessMutation,
+    ) {
+        self.path_injection = Some(WitnessPathInjection {
+            step,
+            op_idx,
+            chip_kind: chip_kind.to_string(),
+            field_path: field_path.to_string(),
+            mutation,
+        });
+    }
+
+    pub fn clear_witness_injection_path(&mut self) {
+        self.path_injection = None;
+    }
+
+    pub fn take_path_injection_applied(&mut self) -> bool {
+        std::mem::take(&mut self.path_injection_applied)
+    }
+
+    /// Apply the configured path injection to a chip-row payload if it targets this row.
+    ///
+    /// `field_path` is dot-separated; numeric segments index into arrays (`"a.2"` is the third
+    /// limb of `a`). Unknown paths and non-scalar targets leave the payload untouched.
+    fn apply_path_injection(&mut self, chip_kind: &str, payload_data: &mut Value) {
+        let Some(spec) = self.path_injection.as_ref() else {
+            return;
+        };
+        if spec.step != self.step_idx || spec.chip_kind != chip_kind {
+            return;
+        }
+        if spec.op_idx.is_some_and(|op_idx| op_idx != self.chip_row_op_idx_in_step) {
+            return;
+        }
+        let pointer = format!("/{}", spec.field_path.replace('.', "/"));
+        let mutation = spec.mutation;
+        if let Some(slot) = payload_data.pointer_mut(&pointer) {
+            if let Some(mutated) = mutate_json_scalar(slot, mutation, &mut self.rng) {
+                *slot = mutated;
+                self.path_injection_applied = true;
+            }
+        }
     }
 
     fn rs2_source_json(rs2: i32, is_rs2_imm: bool) -> Value {
@@ -211,11 +479,12 @@ impl GlobalState {
         next_timestamp: u32,
         opcode: u32,
         operands: [u32; 7],
+        out_of_field: &[usize],
     ) {
         // Start a new instruction step (advance + reset per-step counters).
         self.inc_step();
 
-        let micro_op = json!(
+        let mut micro_op = json!(
         {"type": "instruction",
         "data": {
             "seq": self.seq,
@@ -227,6 +496,11 @@ impl GlobalState {
             "opcode": opcode,
             "operands": operands,
         }});
+        // A non-canonical operand means the instruction did not come from a real program (e.g.
+        // a mutation bug); flag it so the trace side can bucket it instead of trusting it.
+        if !out_of_field.is_empty() {
+            micro_op["data"]["out_of_field_operands"] = json!(out_of_field);
+        }
         self.emit_micro_op(micro_op);
     }
 
@@ -250,8 +524,10 @@ impl GlobalState {
         chip_name: &str,
         timestamp: Option<u32>,
         payload_type: &str,
-        payload_data: Value,
+        mut payload_data: Value,
     ) {
+        self.apply_path_injection(kind, &mut payload_data);
+
         // Generate an anchor row id for downstream interaction events.
         // Format is intentionally simple and stable.
         let row_id = format!("step{}_row{}", self.step_idx, self.row_count);
@@ -267,6 +543,7 @@ impl GlobalState {
             base.insert("timestamp".to_string(), json!(ts));
         }
         base.insert("chip_name".to_string(), json!(chip_name));
+        base.insert("row_id".to_string(), json!(row_id));
 
         let micro_op = json!({
             "type": "chip_row",
@@ -294,6 +571,7 @@ impl GlobalState {
         timestamp: Option<u32>,
         payload_type: &str,
         payload_data: Value,
+        multiplicity: Option<u32>,
     ) {
         if direction != "send" && direction != "receive" {
             panic!("Invalid direction: {}", direction);
@@ -313,6 +591,7 @@ impl GlobalState {
             "direction": direction,
             "kind": kind,
             "timestamp": timestamp,
+            "multiplicity": multiplicity.unwrap_or(1),
         });
 
         // JSON shape:
@@ -617,6 +896,7 @@ impl GlobalState {
         imm_sign: bool,
         mem_as: u32,
         effective_ptr: u32,
+        rs1_val: Option<u32>,
         is_store: bool,
         needs_write: bool,
         is_load: bool,
@@ -633,6 +913,7 @@ impl GlobalState {
             "imm_sign": imm_sign,
             "mem_as": mem_as,
             "effective_ptr": effective_ptr,
+            "rs1_val": rs1_val,
             "is_store": is_store,
             "needs_write": needs_write,
             "is_load": is_load,
@@ -659,6 +940,7 @@ impl GlobalState {
         imm_sign: bool,
         mem_as: u32,
         effective_ptr: u32,
+        rs1_val: Option<u32>,
         needs_write: bool,
         prev_data: [u8; N],
         shifted_read_data: [u8; N],
@@ -676,6 +958,7 @@ impl GlobalState {
             "imm_sign": imm_sign,
             "mem_as": mem_as,
             "effective_ptr": effective_ptr,
+            "rs1_val": rs1_val,
             "needs_write": needs_write,
             "prev_data": prev_data.to_vec(),
             "shifted_read_data": shifted_read_data.to_vec(),
@@ -694,8 +977,13 @@ impl GlobalState {
         );
     }
 
-    pub fn emit_phantom_chip_row(&mut self) {
-        self.emit_chip_row_envelope("phantom", "Phantom", None, "phantom", json!({}));
+    /// `sub_kind` is the phantom discriminant (low 16 bits of `c`); `operands` are `[a, b, c]`.
+    pub fn emit_phantom_chip_row(&mut self, sub_kind: u32, operands: [u32; 3]) {
+        let payload_data = json!({
+            "sub_kind": sub_kind,
+            "operands": operands,
+        });
+        self.emit_chip_row_envelope("phantom", "Phantom", None, "phantom", payload_data);
     }
 
     pub fn emit_program_chip_row(
@@ -747,6 +1035,37 @@ impl GlobalState {
         self.emit_chip_row_envelope("padding", "RowMajorMatrix", None, "padding", payload_data);
     }
 
+    pub fn emit_range_check_chip_row(&mut self, value: u32, max_bits: u32, mult: u32) {
+        let payload_data = json!({
+            "value": value,
+            "max_bits": max_bits,
+            "mult": mult,
+        });
+        self.emit_chip_row_envelope(
+            "range_check",
+            "VariableRangeCheckerAir",
+            None,
+            "range_check",
+            payload_data,
+        );
+    }
+
+    pub fn emit_bitwise_chip_row(&mut self, x: u32, y: u32, mult_range: u32, mult_xor: u32) {
+        let payload_data = json!({
+            "x": x,
+            "y": y,
+            "mult_range": mult_range,
+            "mult_xor": mult_xor,
+        });
+        self.emit_chip_row_envelope(
+            "bitwise",
+            "BitwiseOperationLookupAir",
+            None,
+            "bitwise",
+            payload_data,
+        );
+    }
+
     pub fn get_last_row_id(&self) -> String {
         self.last_row_id.clone().unwrap_or_default()
     }
@@ -761,6 +1080,7 @@ impl GlobalState {
         row_id: Option<&str>,
         pc: u32,
         timestamp: u32,
+        multiplicity: Option<u32>,
     ) {
         let payload_data = json!({
             "pc": pc,
@@ -773,6 +1093,7 @@ impl GlobalState {
             Some(timestamp),
             "execution",
             payload_data,
+            multiplicity,
         );
     }
 
@@ -783,13 +1104,22 @@ impl GlobalState {
         pc: u32,
         opcode: u32,
         operands: [u32; 7],
+        multiplicity: Option<u32>,
     ) {
         let payload_data = json!({
             "pc": pc,
             "opcode": opcode,
             "operands": operands,
         });
-        self.emit_interaction_envelope("program", direction, row_id, None, "program", payload_data);
+        self.emit_interaction_envelope(
+            "program",
+            direction,
+            row_id,
+            None,
+            "program",
+            payload_data,
+            multiplicity,
+        );
     }
 
     pub fn emit_memory_interaction(
@@ -800,6 +1130,7 @@ impl GlobalState {
         pointer: u32,
         data: Vec<u32>,
         timestamp: u32,
+        multiplicity: Option<u32>,
     ) {
         let payload_data = json!({
             "address_space": address_space,
@@ -814,6 +1145,7 @@ impl GlobalState {
             Some(timestamp),
             "memory",
             payload_data,
+            multiplicity,
         );
     }
 
@@ -823,6 +1155,7 @@ impl GlobalState {
         row_id: Option<&str>,
         value: u32,
         max_bits: u32,
+        multiplicity: Option<u32>,
     ) {
         let payload_data = json!({
             "value": value,
@@ -835,6 +1168,7 @@ impl GlobalState {
             None,
             "range_check",
             payload_data,
+            multiplicity,
         );
     }
 
@@ -846,6 +1180,7 @@ impl GlobalState {
         y: u32,
         z: u32,
         op: u32,
+        multiplicity: Option<u32>,
     ) {
         let payload_data = json!({
             "x": x,
@@ -853,7 +1188,15 @@ impl GlobalState {
             "z": z,
             "op": op,
         });
-        self.emit_interaction_envelope("bitwise", direction, row_id, None, "bitwise", payload_data);
+        self.emit_interaction_envelope(
+            "bitwise",
+            direction,
+            row_id,
+            None,
+            "bitwise",
+            payload_data,
+            multiplicity,
+        );
     }
 }
 
@@ -861,6 +1204,19 @@ lazy_static! {
     static ref GLOBAL_STATE: Mutex<GlobalState> = Mutex::new(GlobalState::new());
 }
 
+/// Lock `GLOBAL_STATE`, recovering from poisoning.
+///
+/// A panic while an emit holds the lock (e.g. an assertion in instrumented chip code) would
+/// otherwise make every later emit panic too, turning one failed request into a dead worker.
+/// The per-request reset in `take_json_logs` discards whatever the panicking emit left behind.
+fn global_state() -> MutexGuard<'static, GlobalState> {
+    GLOBAL_STATE.lock().unwrap_or_else(|poisoned| {
+        eprintln!("[fuzzer_utils] GLOBAL_STATE was poisoned by a panicking emit; recovering");
+        GLOBAL_STATE.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
 // -----------------------------------------------------------------------------
 // Module-level emit API (locks GLOBAL_STATE internally)
 // -----------------------------------------------------------------------------
@@ -872,34 +1228,151 @@ pub fn emit_instruction(
     next_timestamp: u32,
     opcode: u32,
     operands: [u32; 7],
+    out_of_field: &[usize],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_instruction(pc, timestamp, next_pc, next_timestamp, opcode, operands);
+    let mut state = global_state();
+    state.emit_instruction(pc, timestamp, next_pc, next_timestamp, opcode, operands, out_of_field);
+}
+
+/// Operand indices of the instruction `(opcode, operands)` that `random_mutate_instruction` drew
+/// at or above the field order before the field reduced them; empty for any other instruction.
+///
+/// Call sites check this before emitting, since the canonical operands they hold can no longer
+/// show the out-of-field value.
+pub fn out_of_field_operands(opcode: u32, operands: &[u32; 7]) -> Vec<usize> {
+    let state = global_state();
+    state.out_of_field_mutations.get(&(opcode, *operands)).cloned().unwrap_or_default()
 }
 
 pub fn next_witness_step() -> u64 {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.next_witness_step()
 }
 
 pub fn should_inject_witness(kind: &str, step: u64) -> bool {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.note_witness_site(kind, step);
-    state.should_inject_witness(kind, step)
+    let mut state = global_state();
+    state.should_inject_witness_at_fill_site(kind, step)
 }
 
-pub fn configure_witness_injection(kind: Option<&str>, step: u64) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.configure_witness_injection(kind, step);
+pub fn configure_witness_injection(kind: Option<&str>, step: u64, op_idx: Option<u64>) {
+    let mut state = global_state();
+    state.configure_witness_injection(kind, step, op_idx);
+}
+
+pub fn configure_witness_injection_path(
+    step: u64,
+    op_idx: Option<u64>,
+    chip_kind: &str,
+    field_path: &str,
+    mutation: WitnessMutation,
+) {
+    let mut state = global_state();
+    state.configure_witness_injection_path(step, op_idx, chip_kind, field_path, mutation);
+}
+
+pub fn clear_witness_injection_path() {
+    let mut state = global_state();
+    state.clear_witness_injection_path();
+}
+
+pub fn take_path_injection_applied() -> bool {
+    let mut state = global_state();
+    state.take_path_injection_applied()
+}
+
+/// Witness columns of the chip rows in `logs`, named `"{kind}:{field_path}"` after the target of
+/// `configure_witness_injection_path`, with the steps that emitted them.
+///
+/// Only integer and boolean payload fields are listed, since those are what a mutation can flip.
+pub fn witness_flip_sites(logs: &[Value]) -> BTreeMap<String, Vec<u64>> {
+    fn scalar_paths(value: &Value, path: &mut Vec<String>, out: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    path.push(key.clone());
+                    scalar_paths(child, path, out);
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    scalar_paths(child, path, out);
+                    path.pop();
+                }
+            }
+            Value::Bool(_) => out.push(path.join(".")),
+            Value::Number(n) if n.is_u64() || n.is_i64() => out.push(path.join(".")),
+            _ => {}
+        }
+    }
+
+    let mut sites: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    for micro_op in logs {
+        if micro_op["type"] != "chip_row" {
+            continue;
+        }
+        let data = &micro_op["data"];
+        let (Some(kind), Some(step)) = (data["kind"].as_str(), data["base"]["step_idx"].as_u64())
+        else {
+            continue;
+        };
+        let mut fields = Vec::new();
+        scalar_paths(&data["payload"]["data"], &mut Vec::new(), &mut fields);
+        for field in fields {
+            let steps = sites.entry(format!("{kind}:{field}")).or_default();
+            if steps.last() != Some(&step) {
+                steps.push(step);
+            }
+        }
+    }
+    sites
+}
+
+pub fn micro_ops_digest() -> String {
+    let state = global_state();
+    state.micro_ops_digest()
 }
 
 pub fn take_json_logs() -> Vec<serde_json::Value> {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.take_json_logs()
 }
 
+/// Record the register value and pointer the load/store adapter computed, for the core's row.
+pub fn note_load_store_address(rs1_val: u32, effective_ptr: u32) {
+    let mut state = global_state();
+    state.pending_load_store_address = Some((rs1_val, effective_ptr));
+}
+
+/// Take the address noted by `note_load_store_address` for this step, if any.
+pub fn take_load_store_address() -> Option<(u32, u32)> {
+    let mut state = global_state();
+    state.pending_load_store_address.take()
+}
+
+pub fn set_max_micro_ops(max: usize) {
+    let mut state = global_state();
+    state.set_max_micro_ops(max);
+}
+
+pub fn set_opcode_weights(weights: OpcodeWeights) {
+    let mut state = global_state();
+    state.set_opcode_weights(weights);
+}
+
+pub fn trace_truncated() -> bool {
+    let state = global_state();
+    state.trace_truncated()
+}
+
+pub fn take_step_nanos() -> BTreeMap<u64, u128> {
+    let mut state = global_state();
+    state.take_step_nanos()
+}
+
 pub fn take_observed_witness_sites() -> BTreeMap<String, Vec<u64>> {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.take_observed_witness_sites()
 }
 
@@ -913,7 +1386,7 @@ pub fn emit_base_alu_chip_row<const N: usize>(
     b: [u8; N],
     c: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_base_alu_chip_row(opcode, rd_ptr, rs1_ptr, rs2, is_rs2_imm, a, b, c);
 }
 
@@ -927,7 +1400,7 @@ pub fn emit_shift_chip_row<const N: usize>(
     b: [u8; N],
     c: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_shift_chip_row(opcode, rd_ptr, rs1_ptr, rs2, is_rs2_imm, a, b, c);
 }
 
@@ -941,7 +1414,7 @@ pub fn emit_less_than_chip_row<const N: usize>(
     b: [u8; N],
     c: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_less_than_chip_row(opcode, rd_ptr, rs1_ptr, rs2, is_rs2_imm, a, b, c);
 }
 
@@ -954,7 +1427,7 @@ pub fn emit_mul_chip_row<const N: usize>(
     b: [u8; N],
     c: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_mul_chip_row(opcode, rd_ptr, rs1_ptr, rs2_ptr, a, b, c);
 }
 
@@ -967,7 +1440,7 @@ pub fn emit_mulh_chip_row<const N: usize>(
     b: [u8; N],
     c: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_mulh_chip_row(opcode, rd_ptr, rs1_ptr, rs2_ptr, a, b, c);
 }
 
@@ -980,7 +1453,7 @@ pub fn emit_divrem_chip_row<const N: usize>(
     b: [u8; N],
     c: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_divrem_chip_row(opcode, rd_ptr, rs1_ptr, rs2_ptr, a, b, c);
 }
 
@@ -996,7 +1469,7 @@ pub fn emit_branch_equal_chip_row<const N: usize>(
     b: [u8; N],
     cmp_result: bool,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_branch_equal_chip_row(
         opcode, rs1_ptr, rs2_ptr, imm, is_taken, from_pc, to_pc, a, b, cmp_result,
     );
@@ -1014,7 +1487,7 @@ pub fn emit_branch_less_than_chip_row<const N: usize>(
     b: [u8; N],
     cmp_result: bool,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_branch_less_than_chip_row(
         opcode, rs1_ptr, rs2_ptr, imm, is_taken, from_pc, to_pc, a, b, cmp_result,
     );
@@ -1030,7 +1503,7 @@ pub fn emit_jal_lui_chip_row<const N: usize>(
     rd_data: [u8; N],
     is_jal: bool,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_jal_lui_chip_row(
         opcode,
         rd_ptr,
@@ -1055,7 +1528,7 @@ pub fn emit_jalr_chip_row<const N: usize>(
     rs1_val: u32,
     rd_data: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_jalr_chip_row(
         opcode,
         rd_ptr,
@@ -1077,7 +1550,7 @@ pub fn emit_auipc_chip_row<const N: usize>(
     from_pc: u32,
     rd_data: [u8; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_auipc_chip_row(opcode, rd_ptr, imm, from_pc, rd_data);
 }
 
@@ -1089,6 +1562,7 @@ pub fn emit_load_store_chip_row<const N: usize>(
     imm_sign: bool,
     mem_as: u32,
     effective_ptr: u32,
+    rs1_val: Option<u32>,
     is_store: bool,
     needs_write: bool,
     is_load: bool,
@@ -1097,7 +1571,7 @@ pub fn emit_load_store_chip_row<const N: usize>(
     prev_data: [u32; N],
     write_data: [u32; N],
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_load_store_chip_row(
         opcode,
         rs1_ptr,
@@ -1106,6 +1580,7 @@ pub fn emit_load_store_chip_row<const N: usize>(
         imm_sign,
         mem_as,
         effective_ptr,
+        rs1_val,
         is_store,
         needs_write,
         is_load,
@@ -1124,6 +1599,7 @@ pub fn emit_load_sign_extend_chip_row<const N: usize>(
     imm_sign: bool,
     mem_as: u32,
     effective_ptr: u32,
+    rs1_val: Option<u32>,
     needs_write: bool,
     prev_data: [u8; N],
     shifted_read_data: [u8; N],
@@ -1133,7 +1609,7 @@ pub fn emit_load_sign_extend_chip_row<const N: usize>(
     opcode_loadb_flag1: bool,
     opcode_loadb_flag0: bool,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_load_sign_extend_chip_row(
         opcode,
         rs1_ptr,
@@ -1142,6 +1618,7 @@ pub fn emit_load_sign_extend_chip_row<const N: usize>(
         imm_sign,
         mem_as,
         effective_ptr,
+        rs1_val,
         needs_write,
         prev_data,
         shifted_read_data,
@@ -1153,13 +1630,13 @@ pub fn emit_load_sign_extend_chip_row<const N: usize>(
     );
 }
 
-pub fn emit_phantom_chip_row() {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_phantom_chip_row();
+pub fn emit_phantom_chip_row(sub_kind: u32, operands: [u32; 3]) {
+    let mut state = global_state();
+    state.emit_phantom_chip_row(sub_kind, operands);
 }
 
 pub fn emit_program_chip_row(opcode: u32, operands: [u32; 7], execution_frequency: u32) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_program_chip_row(opcode, operands, execution_frequency);
 }
 
@@ -1171,7 +1648,7 @@ pub fn emit_connector_chip_row(
     is_terminate: bool,
     exit_code: Option<u32>,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_connector_chip_row(
         from_pc,
         to_pc,
@@ -1183,18 +1660,34 @@ pub fn emit_connector_chip_row(
 }
 
 pub fn emit_padding_chip_row(data: &str) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.emit_padding_chip_row(data);
 }
 
+pub fn emit_range_check_chip_row(value: u32, max_bits: u32, mult: u32) {
+    let mut state = global_state();
+    state.emit_range_check_chip_row(value, max_bits, mult);
+}
+
+pub fn emit_bitwise_chip_row(x: u32, y: u32, mult_range: u32, mult_xor: u32) {
+    let mut state = global_state();
+    state.emit_bitwise_chip_row(x, y, mult_range, mult_xor);
+}
+
 pub fn get_last_row_id() -> String {
-    let state = GLOBAL_STATE.lock().unwrap();
+    let state = global_state();
     state.get_last_row_id()
 }
 
-pub fn emit_execution_interaction(direction: &str, row_id: Option<&str>, pc: u32, timestamp: u32) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_execution_interaction(direction, row_id, pc, timestamp);
+pub fn emit_execution_interaction(
+    direction: &str,
+    row_id: Option<&str>,
+    pc: u32,
+    timestamp: u32,
+    multiplicity: Option<u32>,
+) {
+    let mut state = global_state();
+    state.emit_execution_interaction(direction, row_id, pc, timestamp, multiplicity);
 }
 
 pub fn emit_program_interaction(
@@ -1203,9 +1696,10 @@ pub fn emit_program_interaction(
     pc: u32,
     opcode: u32,
     operands: [u32; 7],
+    multiplicity: Option<u32>,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_program_interaction(direction, row_id, pc, opcode, operands);
+    let mut state = global_state();
+    state.emit_program_interaction(direction, row_id, pc, opcode, operands, multiplicity);
 }
 
 pub fn emit_memory_interaction(
@@ -1215,9 +1709,18 @@ pub fn emit_memory_interaction(
     pointer: u32,
     data: Vec<u32>,
     timestamp: u32,
+    multiplicity: Option<u32>,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_memory_interaction(direction, row_id, address_space, pointer, data, timestamp);
+    let mut state = global_state();
+    state.emit_memory_interaction(
+        direction,
+        row_id,
+        address_space,
+        pointer,
+        data,
+        timestamp,
+        multiplicity,
+    );
 }
 
 pub fn emit_range_check_interaction(
@@ -1225,9 +1728,10 @@ pub fn emit_range_check_interaction(
     row_id: Option<&str>,
     value: u32,
     max_bits: u32,
+    multiplicity: Option<u32>,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_range_check_interaction(direction, row_id, value, max_bits);
+    let mut state = global_state();
+    state.emit_range_check_interaction(direction, row_id, value, max_bits, multiplicity);
 }
 
 pub fn emit_bitwise_interaction(
@@ -1237,13 +1741,14 @@ pub fn emit_bitwise_interaction(
     y: u32,
     z: u32,
     op: u32,
+    multiplicity: Option<u32>,
 ) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    state.emit_bitwise_interaction(direction, row_id, x, y, z, op);
+    let mut state = global_state();
+    state.emit_bitwise_interaction(direction, row_id, x, y, z, op, multiplicity);
 }
 
 pub fn is_assertions_enabled() -> bool {
-    let state = GLOBAL_STATE.lock().unwrap();
+    let state = global_state();
     state.assertions_enabled
 }
 
@@ -1355,7 +1860,7 @@ macro_rules! fuzzer_assert_ne {
 /////////
 
 pub fn random_bool() -> bool {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     state.rng.random::<bool>()
 }
 
@@ -1363,12 +1868,86 @@ pub fn random_from_choices<T>(choices: Vec<T>) -> T
 where
     T: Clone,
 {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
     choices.choose(&mut state.rng).unwrap().clone()
 }
 
+/// Number of opcodes `random_opcode` selects from (selector values `0..OPCODE_COUNT`).
+pub const OPCODE_COUNT: usize = 41;
+
 pub fn random_opcode(rng: &mut StdRng) -> VmOpcode {
-    match rng.random_range(0..=40) {
+    opcode_by_selector(rng.random_range(0..OPCODE_COUNT))
+}
+
+/// Relative selection weight per `random_opcode` selector value (0 excludes the opcode).
+#[derive(Debug, Clone)]
+pub struct OpcodeWeights(pub [u32; OPCODE_COUNT]);
+
+impl Default for OpcodeWeights {
+    /// Favors ALU and memory ops; TERMINATE is excluded and the other system/hint opcodes are
+    /// rare, so a program is not cut short by a TERMINATE drawn for its second instruction.
+    fn default() -> Self {
+        let mut weights = [0u32; OPCODE_COUNT];
+        for (selector, weight) in weights.iter_mut().enumerate() {
+            *weight = match selector {
+                0..=9 => 8,   // base ALU, shifts, set-less-than
+                10..=17 => 8, // loads and stores
+                18..=23 => 4, // branches
+                24..=27 => 3, // jal, lui, jalr, auipc
+                28..=35 => 4, // mul/div
+                36 | 37 => 1, // hint stores
+                38 => 0,      // TERMINATE
+                _ => 1,       // PHANTOM, PUBLISH
+            };
+        }
+        Self(weights)
+    }
+}
+
+impl std::str::FromStr for OpcodeWeights {
+    type Err = String;
+
+    /// Parses comma-separated `selector=weight` overrides applied on top of the defaults, e.g.
+    /// `38=1,28=0` lets TERMINATE through and drops MUL.
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut weights = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (selector, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected selector=weight, got {entry:?}"))?;
+            let selector: usize =
+                selector.trim().parse().map_err(|e| format!("selector {selector:?}: {e}"))?;
+            let weight: u32 =
+                weight.trim().parse().map_err(|e| format!("weight {weight:?}: {e}"))?;
+            let slot = weights
+                .0
+                .get_mut(selector)
+                .ok_or_else(|| format!("selector {selector} is not below {OPCODE_COUNT}"))?;
+            *slot = weight;
+        }
+        if weights.0.iter().all(|&weight| weight == 0) {
+            return Err("every opcode weight is zero".to_string());
+        }
+        Ok(weights)
+    }
+}
+
+/// Like `random_opcode`, but draws each selector with probability proportional to `weights`.
+pub fn random_opcode_weighted(rng: &mut StdRng, weights: &OpcodeWeights) -> VmOpcode {
+    let total: u64 = weights.0.iter().map(|&w| u64::from(w)).sum();
+    assert!(total > 0, "OpcodeWeights must have at least one nonzero weight");
+    let mut pick = rng.random_range(0..total);
+    for (selector, &weight) in weights.0.iter().enumerate() {
+        if pick < u64::from(weight) {
+            return opcode_by_selector(selector);
+        }
+        pick -= u64::from(weight);
+    }
+    unreachable!("weighted pick is below the total weight")
+}
+
+fn opcode_by_selector(selector: usize) -> VmOpcode {
+    match selector {
         0 => BaseAluOpcode::ADD.global_opcode(),
         1 => BaseAluOpcode::SUB.global_opcode(),
         2 => BaseAluOpcode::XOR.global_opcode(),
@@ -1418,13 +1997,58 @@ pub fn random_opcode(rng: &mut StdRng) -> VmOpcode {
     }
 }
 
-pub fn random_new_opcode(opcode: VmOpcode, rng: &mut StdRng) -> VmOpcode {
-    loop {
-        let new_opcode = random_opcode(rng);
-        if new_opcode != opcode {
-            return new_opcode;
+/// Kind of the execution chip row (`chip_row.kind`) the selector's opcode is dispatched to;
+/// `None` for opcodes that have no execution row of their own.
+fn chip_kind_by_selector(selector: usize) -> Option<&'static str> {
+    Some(match selector {
+        0..=4 => "base_alu",
+        5..=7 => "shift",
+        8..=9 => "less_than",
+        10..=15 => "load_store",
+        16..=17 => "load_sign_extend",
+        18..=19 => "branch_equal",
+        20..=23 => "branch_less_than",
+        24..=25 => "jal_lui",
+        26 => "jalr",
+        27 => "auipc",
+        28 => "mul",
+        29..=31 => "mul_h",
+        32..=35 => "div_rem",
+        39 => "phantom",
+        _ => return None,
+    })
+}
+
+lazy_static! {
+    static ref OPCODE_CHIP_KINDS: BTreeMap<usize, &'static str> = (0..OPCODE_COUNT)
+        .filter_map(|selector| {
+            Some((opcode_by_selector(selector).as_usize(), chip_kind_by_selector(selector)?))
+        })
+        .collect();
+}
+
+/// Execution chip-row kind the global opcode `opcode` (`VmOpcode::as_usize`) is dispatched to,
+/// or `None` for hint-store, terminate, publish and unknown opcodes.
+pub fn opcode_chip_kind(opcode: usize) -> Option<&'static str> {
+    OPCODE_CHIP_KINDS.get(&opcode).copied()
+}
+
+/// Draws an opcode other than `opcode` with probability proportional to `weights`. `opcode`
+/// itself is masked out up front, so the draw cannot spin; if no other opcode has weight, it
+/// falls back to a uniform draw among the others.
+pub fn random_new_opcode(opcode: VmOpcode, weights: &OpcodeWeights, rng: &mut StdRng) -> VmOpcode {
+    let mut others = weights.clone();
+    for (selector, weight) in others.0.iter_mut().enumerate() {
+        if opcode_by_selector(selector) == opcode {
+            *weight = 0;
+        }
+    }
+    if others.0.iter().all(|&weight| weight == 0) {
+        for (selector, weight) in others.0.iter_mut().enumerate() {
+            *weight = u32::from(opcode_by_selector(selector) != opcode);
         }
     }
+    random_opcode_weighted(rng, &others)
 }
 
 fn internal_random_mod_of_u32(element: u32, rng: &mut StdRng) -> u32 {
@@ -1437,8 +2061,10 @@ fn internal_random_mod_of_u32(element: u32, rng: &mut StdRng) -> u32 {
             2 => 0xffffffff,
             3 => 0xfffffffe,
             4 => {
-                let n = rng.random_range(1..=31);
-                let bits_to_flip = rand::seq::index::sample(rng, 31, n).into_vec();
+                // Any of the 32 bits, including the sign bit; flipping n >= 1 distinct bits
+                // always changes the element.
+                let n = rng.random_range(1..=32);
+                let bits_to_flip = rand::seq::index::sample(rng, 32, n).into_vec();
                 let mut flipped_element = element;
                 for bit_to_flip in bits_to_flip {
                     flipped_element ^= 1 << bit_to_flip;
@@ -1455,7 +2081,7 @@ fn internal_random_mod_of_u32(element: u32, rng: &mut StdRng) -> u32 {
 }
 
 pub fn random_mod_of_u32_array<const LEN: usize>(elements: &[u32; LEN]) -> [u32; LEN] {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
 
     let mut new_elements = *elements;
     let mut indices: Vec<usize> = (0..LEN).collect();
@@ -1470,13 +2096,23 @@ pub fn random_mod_of_u32_array<const LEN: usize>(elements: &[u32; LEN]) -> [u32;
 }
 
 pub fn random_mutate_field_element<F: Field + PrimeField32>(element: F, rng: &mut StdRng) -> F {
-    F::from_canonical_u32(internal_random_mod_of_u32(element.as_canonical_u32(), rng))
+    random_mutate_field_element_raw(element, rng).0
+}
+
+/// Like `random_mutate_field_element`, also returning the drawn value before the field reduced it.
+fn random_mutate_field_element_raw<F: Field + PrimeField32>(
+    element: F,
+    rng: &mut StdRng,
+) -> (F, u32) {
+    let raw = internal_random_mod_of_u32(element.as_canonical_u32(), rng);
+    (F::from_canonical_u32(raw), raw)
 }
 
 pub fn random_mutate_instruction<F: Field + PrimeField32>(
     instruction: &Instruction<F>,
 ) -> Instruction<F> {
-    let mut state = GLOBAL_STATE.lock().unwrap();
+    let mut state = global_state();
+    let weights = state.opcode_weights.clone();
 
     // create a mutable copy of the old instruction
     let mut new_instruction = instruction.clone();
@@ -1492,37 +2128,192 @@ pub fn random_mutate_instruction<F: Field + PrimeField32>(
     // sort the options such that we first pick the new opcode if it is there
     update_options.sort();
 
-    // execute the picked modifications
+    // execute the picked modifications, remembering operands drawn outside the field
+    let mut out_of_field = Vec::new();
     for option in update_options {
         match option {
             0 => {
                 new_instruction = Instruction::default(); // full reset
-                new_instruction.opcode = random_new_opcode(instruction.opcode, &mut state.rng);
-            }
-            1 => {
-                new_instruction.a = random_mutate_field_element(new_instruction.a, &mut state.rng);
+                new_instruction.opcode =
+                    random_new_opcode(instruction.opcode, &weights, &mut state.rng);
             }
-            2 => {
-                new_instruction.b = random_mutate_field_element(new_instruction.b, &mut state.rng);
-            }
-            3 => {
-                new_instruction.c = random_mutate_field_element(new_instruction.c, &mut state.rng);
-            }
-            4 => {
-                new_instruction.d = random_mutate_field_element(new_instruction.d, &mut state.rng);
-            }
-            5 => {
-                new_instruction.e = random_mutate_field_element(new_instruction.e, &mut state.rng);
-            }
-            6 => {
-                new_instruction.f = random_mutate_field_element(new_instruction.f, &mut state.rng);
-            }
-            7 => {
-                new_instruction.g = random_mutate_field_element(new_instruction.g, &mut state.rng);
+            1..=7 => {
+                let field = match option {
+                    1 => &mut new_instruction.a,
+                    2 => &mut new_instruction.b,
+                    3 => &mut new_instruction.c,
+                    4 => &mut new_instruction.d,
+                    5 => &mut new_instruction.e,
+                    6 => &mut new_instruction.f,
+                    _ => &mut new_instruction.g,
+                };
+                let (mutated, raw) = random_mutate_field_element_raw(*field, &mut state.rng);
+                *field = mutated;
+                if raw >= F::ORDER_U32 {
+                    out_of_field.push(usize::from(option - 1));
+                }
             }
             _ => unreachable!(),
         };
     }
 
+    if !out_of_field.is_empty() {
+        let i = &new_instruction;
+        let operands = [i.a, i.b, i.c, i.d, i.e, i.f, i.g].map(|x| x.as_canonical_u32());
+        let opcode = i.opcode.as_usize() as u32;
+        state.out_of_field_mutations.insert((opcode, operands), out_of_field);
+    }
+
     new_instruction
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_injection_fires_on_the_matching_fill_site_row_only() {
+        let mut state = GlobalState::new();
+        state.configure_witness_injection(Some("k"), 3, Some(1));
+        // Execution-time counters must not influence which filled row is injected.
+        state.chip_row_op_idx_in_step = 1;
+        let fired: Vec<bool> =
+            (0..3).map(|_| state.should_inject_witness_at_fill_site("k", 3)).collect();
+        assert_eq!(fired, vec![false, true, false]);
+        assert!(!state.should_inject_witness_at_fill_site("k", 4));
+        assert_eq!(state.take_observed_witness_sites().get("k"), Some(&vec![3, 4]));
+
+        // Re-arming restarts the row numbering.
+        state.configure_witness_injection(Some("k"), 3, Some(0));
+        assert!(state.should_inject_witness_at_fill_site("k", 3));
+    }
+
+    #[test]
+    fn flip_sites_name_the_path_injection_target_of_each_scalar_field() {
+        let mut state = GlobalState::new();
+        state.emit_instruction(0, 0, 4, 1, 0, [0; 7], &[]);
+        state.emit_chip_row_envelope("alu", "Alu", None, "alu", json!({ "a": [1, 2], "ok": true }));
+        state.emit_instruction(4, 1, 8, 2, 0, [0; 7], &[]);
+        state.emit_chip_row_envelope("alu", "Alu", None, "alu", json!({ "a": [3, 4], "s": "x" }));
+        let sites = witness_flip_sites(&state.take_json_logs());
+        assert_eq!(sites.keys().collect::<Vec<_>>(), ["alu:a.0", "alu:a.1", "alu:ok"]);
+        assert_eq!(sites["alu:a.1"], vec![0, 1]);
+        assert_eq!(sites["alu:ok"], vec![0]);
+
+        state.configure_witness_injection_path(1, None, "alu", "a.1", WitnessMutation::FlipBit(0));
+        state.emit_instruction(0, 0, 4, 1, 0, [0; 7], &[]);
+        state.emit_chip_row_envelope("alu", "Alu", None, "alu", json!({ "a": [3, 4] }));
+        assert!(!state.take_path_injection_applied());
+        state.emit_instruction(4, 1, 8, 2, 0, [0; 7], &[]);
+        state.emit_chip_row_envelope("alu", "Alu", None, "alu", json!({ "a": [3, 4] }));
+        assert!(state.take_path_injection_applied());
+        let logs = state.take_json_logs();
+        assert_eq!(logs[3]["data"]["payload"]["data"]["a"], json!([3, 5]));
+    }
+
+    #[test]
+    fn lookup_chip_rows_carry_their_multiplicities() {
+        let mut state = GlobalState::new();
+        state.emit_instruction(0, 0, 4, 1, 0, [0; 7], &[]);
+        state.emit_range_check_chip_row(300, 9, 2);
+        state.emit_bitwise_chip_row(5, 6, 0, 1);
+        let logs = state.take_json_logs();
+        let range = &logs[1]["data"];
+        assert_eq!(range["kind"], "range_check");
+        assert_eq!(range["base"]["chip_name"], "VariableRangeCheckerAir");
+        assert_eq!(range["payload"]["type"], "range_check");
+        assert_eq!(range["payload"]["data"], json!({ "value": 300, "max_bits": 9, "mult": 2 }));
+        let bitwise = &logs[2]["data"];
+        assert_eq!(bitwise["kind"], "bitwise");
+        assert_eq!(bitwise["base"]["chip_name"], "BitwiseOperationLookupAir");
+        assert_eq!(
+            bitwise["payload"]["data"],
+            json!({ "x": 5, "y": 6, "mult_range": 0, "mult_xor": 1 })
+        );
+    }
+
+    #[test]
+    fn a_panicking_emit_does_not_wedge_later_emits() {
+        // The only test touching GLOBAL_STATE, so poisoning it cannot disturb the others.
+        let _ = std::thread::spawn(|| {
+            let _state = global_state();
+            panic!("assertion in instrumented chip code");
+        })
+        .join();
+        assert!(GLOBAL_STATE.is_poisoned());
+
+        emit_instruction(0, 0, 4, 1, 0, [0; 7], &[]);
+        assert!(!GLOBAL_STATE.is_poisoned());
+        assert_eq!(take_json_logs().len(), 1);
+    }
+
+    #[test]
+    fn opcode_chip_kinds_follow_the_dispatching_chip() {
+        let kind = |opcode: VmOpcode| opcode_chip_kind(opcode.as_usize());
+        assert_eq!(kind(ShiftOpcode::SRA.global_opcode()), Some("shift"));
+        assert_eq!(kind(Rv32LoadStoreOpcode::LOADB.global_opcode()), Some("load_sign_extend"));
+        assert_eq!(kind(MulHOpcode::MULHU.global_opcode()), Some("mul_h"));
+        assert_eq!(kind(SystemOpcode::PHANTOM.global_opcode()), Some("phantom"));
+        assert_eq!(kind(SystemOpcode::TERMINATE.global_opcode()), None);
+        assert_eq!(OPCODE_CHIP_KINDS.len(), 37);
+    }
+
+    #[test]
+    fn configuring_without_a_row_keeps_the_environment_pin() {
+        std::env::set_var("BEAK_OPENVM_WITNESS_INJECT_OP_IDX", "2");
+        let mut state = GlobalState::new();
+        state.configure_witness_injection(Some("k"), 0, None);
+        assert_eq!(state.injection_op_idx, Some(2));
+        state.configure_witness_injection(Some("k"), 0, Some(5));
+        assert_eq!(state.injection_op_idx, Some(5));
+        std::env::remove_var("BEAK_OPENVM_WITNESS_INJECT_OP_IDX");
+    }
+
+    #[test]
+    fn micro_ops_past_the_cap_are_dropped_and_flagged_until_the_next_run() {
+        let mut state = GlobalState::new();
+        state.set_max_micro_ops(2);
+        for step in 0..3 {
+            state.emit_instruction(4 * step, step, 4 * step + 4, step + 1, 0, [0; 7], &[]);
+        }
+        assert!(state.trace_truncated());
+        assert_eq!(state.take_json_logs().len(), 2);
+
+        assert!(!state.trace_truncated());
+        state.emit_instruction(0, 0, 4, 1, 0, [0; 7], &[]);
+        assert!(!state.trace_truncated());
+    }
+
+    #[test]
+    fn weighted_opcode_draws_follow_the_configured_weights() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let add = BaseAluOpcode::ADD.global_opcode();
+        let sub = BaseAluOpcode::SUB.global_opcode();
+        let terminate = SystemOpcode::TERMINATE.global_opcode();
+
+        let defaults = OpcodeWeights::default();
+        assert!((0..500).all(|_| random_new_opcode(add, &defaults, &mut rng) != terminate));
+
+        let mut only_add_sub = OpcodeWeights([0; OPCODE_COUNT]);
+        only_add_sub.0[..2].fill(1);
+        assert!((0..50).all(|_| random_new_opcode(add, &only_add_sub, &mut rng) == sub));
+
+        // Only the current opcode has weight: the draw still ends, with a different opcode.
+        let mut only_add = OpcodeWeights([0; OPCODE_COUNT]);
+        only_add.0[0] = 1;
+        assert!((0..50).all(|_| random_new_opcode(add, &only_add, &mut rng) != add));
+    }
+
+    #[test]
+    fn opcode_weight_overrides_apply_on_top_of_the_defaults() {
+        let weights: OpcodeWeights = "38=2, 28=0".parse().unwrap();
+        assert_eq!(weights.0[38], 2);
+        assert_eq!(weights.0[28], 0);
+        assert_eq!(weights.0[0], OpcodeWeights::default().0[0]);
+
+        assert!("99=1".parse::<OpcodeWeights>().is_err());
+        assert!("0=x".parse::<OpcodeWeights>().is_err());
+        let all_zero: Vec<String> = (0..OPCODE_COUNT).map(|s| format!("{s}=0")).collect();
+        assert!(all_zero.join(",").parse::<OpcodeWeights>().is_err());
+    }
+}
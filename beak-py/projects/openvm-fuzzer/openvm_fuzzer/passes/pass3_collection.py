@@ -63,6 +63,19 @@ def _insert_before_fn_close(contents: str, *, fn_name: str, insert: str, guard:
     raise RuntimeError(f"unterminated function body for injection: {needle!r}")
 
 
+def _insert_after_fn_open(contents: str, *, fn_name: str, insert: str, guard: str) -> str:
+    if guard in contents:
+        return contents
+    match = re.search(rf"fn {re.escape(fn_name)}\b", contents)
+    if match is None:
+        raise RuntimeError(f"function not found for injection: {fn_name!r}")
+    brace_open = contents.find("{", match.end())
+    if brace_open < 0:
+        raise RuntimeError(f"function body not found for injection: {fn_name!r}")
+    pos = brace_open + 1
+    return contents[:pos] + insert + contents[pos:]
+
+
 def _ensure_use_fuzzer_utils(path: Path) -> None:
     if not path.exists():
         return
@@ -166,6 +179,14 @@ def _patch_regzero_interpreter_preflight_emit_instruction(openvm_install_path: P
             pc_entry.insn.g.as_canonical_u32(),
         ];
         let beak_opcode = pc_entry.insn.opcode.as_usize() as u32;
+        // Operands a mutation drew outside the field were reduced by now; look them up.
+        let beak_out_of_field = fuzzer_utils::out_of_field_operands(beak_opcode, &beak_operands);
+        fuzzer_utils::fuzzer_assert!(
+            beak_out_of_field.is_empty(),
+            "operands {:?} of opcode {} were drawn outside the field",
+            beak_out_of_field,
+            beak_opcode
+        );
         // BEAK-INSERT-END
 """,
     )
@@ -186,22 +207,33 @@ def _patch_regzero_interpreter_preflight_emit_instruction(openvm_install_path: P
                 beak_to_timestamp,
                 beak_opcode,
                 beak_operands,
+                &beak_out_of_field,
             );
             // BEAK-TODO: Maybe we should use the row_id here?
+            // A valid step is one program lookup and one execution-bus hop, each counted once (the
+            // AIRs use `is_valid` as the multiplicity).
             fuzzer_utils::emit_program_interaction(
                 "receive",
                 None,
                 beak_from_pc,
                 beak_opcode,
                 beak_operands,
+                Some(1),
             );
             fuzzer_utils::emit_execution_interaction(
                 "receive",
                 None,
                 beak_from_pc,
                 beak_from_timestamp,
+                Some(1),
+            );
+            fuzzer_utils::emit_execution_interaction(
+                "send",
+                None,
+                beak_to_pc,
+                beak_to_timestamp,
+                Some(1),
             );
-            fuzzer_utils::emit_execution_interaction("send", None, beak_to_pc, beak_to_timestamp);
             // BEAK-INSERT-END
 """,
     )
@@ -223,23 +255,34 @@ def _patch_regzero_interpreter_preflight_emit_instruction(openvm_install_path: P
             beak_to_timestamp,
             beak_opcode,
             beak_operands,
+            &beak_out_of_field,
         );
 
         // BEAK-TODO: Maybe we should use the row_id here?
+        // A valid step is one program lookup and one execution-bus hop, each counted once (the
+        // AIRs use `is_valid` as the multiplicity).
         fuzzer_utils::emit_program_interaction(
             "receive",
             None,
             beak_from_pc,
             beak_opcode,
             beak_operands,
+            Some(1),
         );
         fuzzer_utils::emit_execution_interaction(
             "receive",
             None,
             beak_from_pc,
             beak_from_timestamp,
+            Some(1),
+        );
+        fuzzer_utils::emit_execution_interaction(
+            "send",
+            None,
+            beak_to_pc,
+            beak_to_timestamp,
+            Some(1),
         );
-        fuzzer_utils::emit_execution_interaction("send", None, beak_to_pc, beak_to_timestamp);
         // BEAK-INSERT-END
 """,
     )
@@ -561,6 +604,7 @@ def _patch_regzero_rv32im_cores_emit_chip_row(openvm_install_path: Path) -> None
         let beak_cols: &Rv32LoadStoreAdapterCols<F> = adapter_slice.borrow();
 
         let rs1_ptr = beak_cols.rs1_ptr.as_canonical_u32();
+        let rs1_val = u32::from_le_bytes(beak_cols.rs1_data.map(|x| x.as_canonical_u32() as u8));
         let rd_rs2_ptr = beak_cols.rd_rs2_ptr.as_canonical_u32();
 
         let imm_sign = beak_cols.imm_sign.as_canonical_u32() == 1;
@@ -585,6 +629,7 @@ def _patch_regzero_rv32im_cores_emit_chip_row(openvm_install_path: Path) -> None
             imm_sign,
             mem_as,
             effective_ptr,
+            Some(rs1_val),
             is_store,
             needs_write,
             is_load,
@@ -608,6 +653,7 @@ def _patch_regzero_rv32im_cores_emit_chip_row(openvm_install_path: Path) -> None
         let beak_cols: &Rv32LoadStoreAdapterCols<F> = adapter_slice.borrow();
 
         let rs1_ptr = beak_cols.rs1_ptr.as_canonical_u32();
+        let rs1_val = u32::from_le_bytes(beak_cols.rs1_data.map(|x| x.as_canonical_u32() as u8));
         // LoadStore adapter uses a unified pointer: rd for loads, rs2 for stores.
         let rd_ptr = beak_cols.rd_rs2_ptr.as_canonical_u32();
 
@@ -635,6 +681,7 @@ def _patch_regzero_rv32im_cores_emit_chip_row(openvm_install_path: Path) -> None
             imm_sign,
             mem_as,
             effective_ptr,
+            Some(rs1_val),
             needs_write,
             record.prev_data,
             shifted_read_data,
@@ -709,7 +756,8 @@ def _patch_regzero_system_connector_emit_chip_row(openvm_install_path: Path) ->
                 insert=r""";
         // BEAK-INSERT: guard.system.phantom_chip_row
         // BEAK-INSERT: Emit chip-row micro-op.
-        fuzzer_utils::emit_phantom_chip_row();
+        // Operand c carries the phantom discriminant in its low 16 bits.
+        fuzzer_utils::emit_phantom_chip_row(record.operands[2] & 0xffff, record.operands);
         // BEAK-INSERT-END
 """,
             )
@@ -998,6 +1046,9 @@ def _patch_336f_loadstore_core_emit_chip_row(openvm_install_path: Path) -> None:
         let read_data_u8: [u8; NUM_CELLS] = read_data.map(|x| x.as_canonical_u32() as u8);
         let prev_data_u32: [u32; NUM_CELLS] = prev_data.map(|x| x.as_canonical_u32());
         let write_data_u32: [u32; NUM_CELLS] = write_data.map(|x| x.as_canonical_u32());
+        // The core only sees the pointer's low bits (`shift`); the adapter noted the rest.
+        let (rs1_val, effective_ptr) = fuzzer_utils::take_load_store_address()
+            .map_or((None, 0u32), |(rs1_val, ptr)| (Some(rs1_val), ptr));
 
         fuzzer_utils::emit_load_store_chip_row(
             opcode_u32,
@@ -1006,7 +1057,8 @@ def _patch_336f_loadstore_core_emit_chip_row(openvm_install_path: Path) -> None:
             imm_i32,
             imm_sign,
             mem_as,
-            0u32,
+            effective_ptr,
+            rs1_val,
             is_store,
             needs_write,
             is_load,
@@ -1022,6 +1074,29 @@ def _patch_336f_loadstore_core_emit_chip_row(openvm_install_path: Path) -> None:
         return
     path.write_text(c)
 
+    adapter_path = path.parents[1] / "adapters" / "loadstore.rs"
+    if not adapter_path.exists():
+        return
+    _ensure_use_fuzzer_utils(adapter_path)
+    c = adapter_path.read_text()
+    try:
+        c = _insert_before(
+            c,
+            anchor="""        Ok((
+            (
+                [prev_data, read_record.1],""",
+            guard="// BEAK-INSERT: guard.336f.loadstore.adapter.note_address",
+            insert=r"""        // BEAK-INSERT: guard.336f.loadstore.adapter.note_address
+        // BEAK-INSERT: Hand rs1 and the resolved pointer to the core's chip-row emit.
+        fuzzer_utils::note_load_store_address(rs1_val, ptr_val);
+        // BEAK-INSERT-END
+
+""",
+        )
+    except RuntimeError:
+        return
+    adapter_path.write_text(c)
+
 
 def _patch_336f_divrem_core_emit_chip_row(openvm_install_path: Path) -> None:
     path = (
@@ -1369,6 +1444,74 @@ def _patch_336f_bitwise_lookup_shadow_multiplicity_injection(openvm_install_path
     path.write_text(c)
 
 
+def _patch_336f_lookup_chips_emit_chip_row(openvm_install_path: Path) -> None:
+    """
+    Emit one chip row per nonzero-multiplicity entry of the range-checker and bitwise lookup
+    chips, read from their counters before `generate_trace` drains them into the matrix.
+    Rows are emitted at trace generation, so they carry the last execution step.
+    """
+    primitives = openvm_install_path / "crates" / "circuits" / "primitives" / "src"
+
+    var_range = primitives / "var_range" / "mod.rs"
+    if var_range.exists():
+        _ensure_use_fuzzer_utils(var_range)
+        c = var_range.read_text()
+        try:
+            c = _insert_after_fn_open(
+                c,
+                fn_name="generate_trace",
+                guard="// BEAK-INSERT: guard.336f.var_range.emit_chip_row",
+                insert=r"""
+        // BEAK-INSERT: guard.336f.var_range.emit_chip_row
+        // BEAK-INSERT: Emit range_check chip-row micro-ops. Entry `n` counts lookups of
+        // `(value, max_bits)` with `n = (1 << max_bits) + value`; entry 0 is unused.
+        for (n, count) in self.count.iter().enumerate().skip(1) {
+            let mult = count.load(std::sync::atomic::Ordering::SeqCst);
+            if mult == 0 {
+                continue;
+            }
+            let max_bits = usize::BITS - 1 - n.leading_zeros();
+            let value = (n - (1 << max_bits)) as u32;
+            fuzzer_utils::emit_range_check_chip_row(value, max_bits, mult);
+        }
+        // BEAK-INSERT-END
+""",
+            )
+        except RuntimeError:
+            pass
+        var_range.write_text(c)
+
+    bitwise = primitives / "bitwise_op_lookup" / "mod.rs"
+    if bitwise.exists():
+        _ensure_use_fuzzer_utils(bitwise)
+        c = bitwise.read_text()
+        try:
+            c = _insert_after_fn_open(
+                c,
+                fn_name="generate_trace",
+                guard="// BEAK-INSERT: guard.336f.bitwise.lookup.emit_chip_row",
+                insert=r"""
+        // BEAK-INSERT: guard.336f.bitwise.lookup.emit_chip_row
+        // BEAK-INSERT: Emit bitwise chip-row micro-ops. Entry `n` counts lookups of `(x, y)`
+        // with `n = (x << NUM_BITS) + y`.
+        for n in 0..self.count_range.len() {
+            let mult_range = self.count_range[n].load(std::sync::atomic::Ordering::SeqCst);
+            let mult_xor = self.count_xor[n].load(std::sync::atomic::Ordering::SeqCst);
+            if mult_range == 0 && mult_xor == 0 {
+                continue;
+            }
+            let x = (n >> NUM_BITS) as u32;
+            let y = (n & ((1 << NUM_BITS) - 1)) as u32;
+            fuzzer_utils::emit_bitwise_chip_row(x, y, mult_range, mult_xor);
+        }
+        // BEAK-INSERT-END
+""",
+            )
+        except RuntimeError:
+            pass
+        bitwise.write_text(c)
+
+
 def _patch_f038_volatile_witness_injection(openvm_install_path: Path) -> None:
     path = (
         openvm_install_path
@@ -1413,6 +1556,7 @@ def _patch_f038_volatile_witness_injection(openvm_install_path: Path) -> None:
                         1 << 29,
                         vec![0],
                         0,
+                        None,
                     );
                 }
                 // BEAK-INSERT-END
@@ -1431,6 +1575,7 @@ def _patch_f038_volatile_witness_injection(openvm_install_path: Path) -> None:
                         1 << 29,
                         vec![0],
                         0,
+                        None,
                     );
 """,
             1,
@@ -1545,6 +1690,7 @@ def _patch_f038_loadstore_mem_as_witness_injection(openvm_install_path: Path) ->
                 ptr_val,
                 read_record.1.iter().map(|x| x.as_canonical_u32()).collect(),
                 0,
+                None,
             );
         }
 
@@ -1580,6 +1726,7 @@ def _patch_f038_loadstore_mem_as_witness_injection(openvm_install_path: Path) ->
                 ptr_val,
                 read_record.1.iter().map(|x| x.as_canonical_u32()).collect(),
                 0,
+                None,
             );
 """,
             1,
@@ -1592,12 +1739,8 @@ def _patch_witness_step_wildcard_support(openvm_install_path: Path) -> None:
     if not path.exists():
         return
     c = path.read_text()
-    old = "        self.injection_enabled && self.injection_kind == kind && self.injection_step == step\n"
-    new = (
-        "        self.injection_enabled\n"
-        "            && self.injection_kind == kind\n"
-        "            && (self.injection_step == step || self.injection_step == u64::MAX)\n"
-    )
+    old = "            && self.injection_step == step\n"
+    new = "            && (self.injection_step == step || self.injection_step == u64::MAX)\n"
     if old in c and "self.injection_step == u64::MAX" not in c:
         c = c.replace(old, new, 1)
     path.write_text(c)
@@ -2257,6 +2400,7 @@ def apply(*, openvm_install_path: Path, commit_or_branch: str) -> None:
         _patch_336f_auipc_core_emit_chip_row(openvm_install_path)
         _patch_336f_loadstore_core_emit_chip_row(openvm_install_path)
         _patch_336f_divrem_core_emit_chip_row(openvm_install_path)
+        _patch_336f_lookup_chips_emit_chip_row(openvm_install_path)
         _patch_336f_auipc_core_witness_injection(openvm_install_path)
         _patch_336f_loadstore_adapter_witness_injection(openvm_install_path)
         _patch_336f_divrem_core_witness_injection(openvm_install_path)
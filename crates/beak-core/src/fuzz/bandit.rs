@@ -27,6 +27,11 @@ impl BanditArmStats {
     }
 }
 
+/// Upper bound for epsilon after repeated exploration boosts.
+const MAX_EPSILON: f64 = 0.5;
+/// Upper bound for the UCB exploration constant after repeated exploration boosts.
+const MAX_UCB_C: f64 = 8.0;
+
 #[derive(Debug, Clone)]
 struct Bandit {
     arms: Vec<BanditArmStats>,
@@ -95,6 +100,11 @@ impl Bandit {
         best_i
     }
 
+    fn boost_exploration(&mut self) {
+        self.epsilon = (self.epsilon * 2.0).clamp(0.05, MAX_EPSILON);
+        self.ucb_c = (self.ucb_c * 1.5).min(MAX_UCB_C);
+    }
+
     fn update(&mut self, arm_idx: usize, reward: f64) {
         if self.arms.is_empty() {
             return;
@@ -127,6 +137,33 @@ pub fn update(arm_idx: usize, reward: f64) {
     b.update(arm_idx, reward);
 }
 
+/// Shift the bandit toward exploration (e.g. after a coverage plateau).
+pub fn boost_exploration() {
+    let mut b = BANDIT.lock().unwrap();
+    b.boost_exploration();
+}
+
+/// Pull count and reward of one arm, as exported at the end of a campaign.
+#[derive(Debug, Clone)]
+pub struct ArmSnapshot {
+    pub pulls: u64,
+    pub total_reward: f64,
+    pub mean_reward: f64,
+}
+
+/// Current statistics of every arm, in arm index order.
+pub fn snapshot() -> Vec<ArmSnapshot> {
+    let b = BANDIT.lock().unwrap();
+    b.arms
+        .iter()
+        .map(|arm| ArmSnapshot {
+            pulls: arm.pulls,
+            total_reward: arm.total_reward,
+            mean_reward: arm.mean_reward(),
+        })
+        .collect()
+}
+
 pub fn set_last_arm(arm_idx: usize) {
     *LAST_ARM.lock().unwrap() = Some(arm_idx);
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
@@ -7,15 +7,18 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use libafl::inputs::BytesInput;
 use serde_json::json;
 
-use crate::fuzz::jsonl::{BugRecord, CorpusRecord, JsonlWriter, RunRecord};
-use crate::fuzz::seed::FuzzingSeed;
-use crate::rv32im::instruction::RV32IMInstruction;
-use crate::rv32im::oracle::{OracleConfig, RISCVOracle};
+use crate::fuzz::jsonl::{BugRecord, CorpusRecord, JsonlWriter, RunRecord, RECORD_SCHEMA_VERSION};
+use crate::fuzz::loop1::{
+    self, is_csr_semantics_divergence, mismatch_regs, read_program_stdin, write_bucket_histogram,
+    LoopBackend, SingleEvalReport, DEFAULT_IGNORED_REGS,
+};
+use crate::fuzz::seed::{diagnose_seed_line, summarize_dropped_seeds};
+use crate::rv32im::oracle::{mismatch_memory, OracleConfig, RISCVOracle};
 use crate::trace::{
     sorted_signatures_from_hits, sorted_signatures_from_signals, BucketHit, TraceSignal,
 };
 
-pub use crate::fuzz::loop1::{BackendEval, DEFAULT_RNG_SEED};
+pub use crate::fuzz::loop1::{BackendCapabilities, BackendEval, DEFAULT_RNG_SEED};
 
 #[derive(Debug, Clone)]
 pub struct BenchmarkConfig {
@@ -24,6 +27,9 @@ pub struct BenchmarkConfig {
     pub rng_seed: u64,
     pub timeout_ms: u64,
     pub oracle: OracleConfig,
+    /// Register indices excluded from `mismatch_regs`, as in `Loop1Config::ignored_regs`. `None`
+    /// falls back to `DEFAULT_IGNORED_REGS`.
+    pub ignored_regs: Option<Vec<u8>>,
 
     pub seeds_jsonl: PathBuf,
     pub out_dir: PathBuf,
@@ -38,6 +44,9 @@ pub struct BenchmarkConfig {
     pub semantic_step_stride: u64,
     pub semantic_max_trials_per_bucket: usize,
     pub stack_size_bytes: usize,
+    /// Write the backend's raw trace of every bug-producing run to
+    /// `{out_dir}/traces/{prefix}-eval{eval_id}.json` (see `BenchmarkBackend::persist_last_trace`).
+    pub record_bug_traces: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +54,8 @@ pub struct BenchmarkOutputs {
     pub corpus_path: PathBuf,
     pub bugs_path: PathBuf,
     pub runs_path: Option<PathBuf>,
+    /// `{prefix}-bucket-histogram.json`: how many evaluations hit each bucket id.
+    pub bucket_histogram_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -65,12 +76,27 @@ pub struct SemanticInjectionCandidate {
 }
 
 pub trait BenchmarkBackend {
+    /// Static description of what this backend supports, as for `LoopBackend::capabilities`.
+    /// The default claims neither memory readback nor witness injection.
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities::default()
+    }
+
     fn is_usable_seed(&self, _words: &[u32]) -> bool {
         true
     }
 
     fn prepare_for_run(&mut self, _rng_seed: u64) {}
 
+    /// Bring the backend to a ready state (e.g. spawn a worker and run a trivial program) before
+    /// the per-input timeout starts. Called before every evaluation, so it must return quickly
+    /// once warm; a failure is logged and left for the evaluation itself to surface.
+    fn warm_up(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn set_memory_probe(&mut self, _addrs: &[u32]) {}
+
     fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String>;
 
     fn collect_eval(&mut self) -> BackendEval;
@@ -87,6 +113,81 @@ pub trait BenchmarkBackend {
     ) -> Vec<SemanticInjectionCandidate> {
         Vec::new()
     }
+
+    /// Keep the raw trace of each run for `persist_last_trace`; only enabled when bug traces are
+    /// recorded, since holding a full trace per run is not free.
+    fn set_retain_last_trace(&mut self, _retain: bool) {}
+
+    /// Write the retained raw trace of the last run to `path` as
+    /// `{ "logs": [...], "bucket_hits_sig", "words" }`, the archive format `rebucket_corpus` reads.
+    fn persist_last_trace(&mut self, _path: &Path) -> Result<(), String> {
+        Err("backend does not retain traces".to_string())
+    }
+}
+
+/// Presents a `BenchmarkBackend` as the `LoopBackend` that `loop1::evaluate_single` drives.
+struct SingleEvalBackend<'a, B>(&'a mut B);
+
+impl<B: BenchmarkBackend> LoopBackend for SingleEvalBackend<'_, B> {
+    fn capabilities(&self) -> BackendCapabilities {
+        self.0.capabilities()
+    }
+
+    fn set_memory_probe(&mut self, addrs: &[u32]) {
+        self.0.set_memory_probe(addrs);
+    }
+
+    fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+        self.0.prove_and_read_final_regs(words)
+    }
+
+    fn collect_eval(&mut self) -> BackendEval {
+        self.0.collect_eval()
+    }
+}
+
+/// `loop1::evaluate_single` for a benchmark backend: warm it up, then run `words` once through
+/// the oracle and the backend without injection.
+pub fn evaluate_single<B: BenchmarkBackend>(
+    words: &[u32],
+    backend: &mut B,
+    oracle_cfg: OracleConfig,
+) -> SingleEvalReport {
+    if let Err(e) = backend.warm_up() {
+        eprintln!("[BENCHMARK][WARN] backend warm-up failed: {e}");
+    }
+    backend.clear_semantic_injection();
+    loop1::evaluate_single(words, &mut SingleEvalBackend(backend), oracle_cfg)
+}
+
+/// `evaluate_single` on a program read from stdin (see `loop1::parse_program_text`).
+pub fn evaluate_stdin<B: BenchmarkBackend>(
+    backend: &mut B,
+    oracle_cfg: OracleConfig,
+) -> Result<SingleEvalReport, String> {
+    let words = read_program_stdin()?;
+    Ok(evaluate_single(&words, backend, oracle_cfg))
+}
+
+/// `evaluate_stdin` with the backend built and run on a thread sized like
+/// `run_benchmark_threaded`'s, using `cfg.oracle`.
+pub fn evaluate_stdin_threaded<B, F>(
+    cfg: &BenchmarkConfig,
+    build_backend: F,
+) -> Result<SingleEvalReport, String>
+where
+    B: BenchmarkBackend,
+    F: FnOnce() -> B + Send + 'static,
+{
+    let words = read_program_stdin()?;
+    let oracle_cfg = cfg.oracle;
+    let stack = cfg.stack_size_bytes.max(16 * 1024 * 1024);
+    let handle = std::thread::Builder::new()
+        .name("beak-eval-stdin".into())
+        .stack_size(stack)
+        .spawn(move || evaluate_single(&words, &mut build_backend(), oracle_cfg))
+        .map_err(|e| format!("spawn evaluation thread failed: {e}"))?;
+    handle.join().map_err(|_| "evaluation thread panicked".to_string())
 }
 
 #[derive(Debug, Clone, Default)]
@@ -98,6 +199,9 @@ struct EvalStats {
     bucket_hits: Vec<BucketHit>,
     trace_signals: Vec<TraceSignal>,
     mismatch_regs: Vec<(u32, u32, u32)>,
+    mismatch_memory: Vec<(u32, u8, u8)>,
+    /// The register mismatch is confined to CSR destinations (see `loop1`'s CSR classification).
+    csr_semantics_divergence: bool,
     backend_error: Option<String>,
     oracle_error: Option<String>,
     timed_out: bool,
@@ -110,6 +214,11 @@ struct EvalStats {
     baseline_bucket_hits_sig: Option<String>,
     underconstrained_candidate: bool,
     semantic_injection_applied: bool,
+    injection_caught: Option<bool>,
+    /// Copied from `BackendEval::trace_truncated`.
+    trace_truncated: bool,
+    /// Where the raw trace of this (bug) evaluation was written, if it was.
+    trace_path: Option<PathBuf>,
 }
 
 fn now_ts_millis() -> u128 {
@@ -144,33 +253,27 @@ fn load_initial_seeds(
     let f = File::open(path).expect("open initial seeds");
     let r = BufReader::new(f);
     let mut out = Vec::new();
-    for line in r.lines().flatten() {
+    let mut dropped = Vec::new();
+    for (idx, line) in r.lines().flatten().enumerate() {
         let s = line.trim();
         if s.is_empty() {
             continue;
         }
-        let seed: FuzzingSeed = serde_json::from_str(s).expect("parse seed jsonl");
-        let mut words = seed.instructions;
-        words.truncate(max_instructions);
-        if !is_usable(&words) {
-            continue;
-        }
-        if words.iter().any(|w| RV32IMInstruction::from_word(*w).is_err()) {
+        let diagnosis = diagnose_seed_line(s, max_instructions, is_usable);
+        if let Some(rejection) = diagnosis.rejection {
+            dropped.push((idx + 1, rejection));
             continue;
         }
-        out.push((encode_words(&words), serde_json::Value::Object(seed.metadata)));
+        let Some(seed) = diagnosis.seed else { continue };
+        out.push((encode_words(&seed.instructions), serde_json::Value::Object(seed.metadata)));
     }
-    out
-}
-
-fn mismatch_regs(oracle: &[u32; 32], prover: &[u32; 32]) -> Vec<(u32, u32, u32)> {
-    let mut out = Vec::new();
-    for i in 0..32u32 {
-        let a = oracle[i as usize];
-        let b = prover[i as usize];
-        if a != b {
-            out.push((i, a, b));
-        }
+    if !dropped.is_empty() {
+        eprintln!(
+            "[BENCHMARK][WARN] dropped {} seed line(s) from {}: {}",
+            dropped.len(),
+            path.display(),
+            summarize_dropped_seeds(&dropped)
+        );
     }
     out
 }
@@ -267,16 +370,23 @@ fn eval_once<B: BenchmarkBackend>(
     backend: &mut B,
     words: &[u32],
 ) -> EvalStats {
+    if let Err(e) = backend.warm_up() {
+        eprintln!("[BENCHMARK][WARN] backend warm-up failed: {e}");
+    }
     let start = Instant::now();
     backend.prepare_for_run(cfg.rng_seed);
 
     let oracle_regs = catch_unwind_nonfatal(std::panic::AssertUnwindSafe(|| {
-        RISCVOracle::execute_with_config(words, cfg.oracle)
+        RISCVOracle::execute_with_memory(words, cfg.oracle)
     }));
     let panic_oracle_error = match oracle_regs.as_ref() {
         Err(p) => Some(panic_payload_to_string(p.as_ref())),
         _ => None,
     };
+    if let (Ok((_, memory)), true) = (oracle_regs.as_ref(), backend.capabilities().memory_readback)
+    {
+        backend.set_memory_probe(&memory.keys().copied().collect::<Vec<_>>());
+    }
 
     let backend_regs = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         backend.prove_and_read_final_regs(words)
@@ -292,11 +402,17 @@ fn eval_once<B: BenchmarkBackend>(
         Err(_) => None,
     };
     let mismatches = match (oracle_regs.as_ref(), final_regs.as_ref()) {
-        (Ok(oracle), Some(regs)) => mismatch_regs(oracle, regs),
+        (Ok((oracle, _)), Some(regs)) => {
+            mismatch_regs(oracle, regs, cfg.ignored_regs.as_deref().unwrap_or(DEFAULT_IGNORED_REGS))
+        }
         _ => Vec::new(),
     };
 
     let eval = backend.collect_eval();
+    let memory_mismatches = match (oracle_regs.as_ref(), eval.final_memory.as_ref()) {
+        (Ok((_, oracle)), Some(memory)) => mismatch_memory(oracle, memory),
+        _ => Vec::new(),
+    };
     let backend_error = eval.backend_error.clone().or(panic_backend_error);
     let oracle_error = panic_oracle_error.map(|e| format!("oracle {e}"));
     let bucket_sigs = sorted_signatures_from_hits(&eval.bucket_hits);
@@ -307,6 +423,8 @@ fn eval_once<B: BenchmarkBackend>(
     let backend_timed_out =
         backend_error.as_deref().map(|e| e.contains("timed out")).unwrap_or(false);
     let timed_out = start.elapsed() > timeout || backend_timed_out;
+    let csr_semantics_divergence =
+        is_csr_semantics_divergence(words, &mismatches, &memory_mismatches);
 
     EvalStats {
         bucket_hits_sig: sig,
@@ -316,6 +434,8 @@ fn eval_once<B: BenchmarkBackend>(
         bucket_hits: eval.bucket_hits,
         trace_signals: eval.trace_signals,
         mismatch_regs: mismatches,
+        mismatch_memory: memory_mismatches,
+        csr_semantics_divergence,
         backend_error,
         oracle_error,
         timed_out,
@@ -328,6 +448,9 @@ fn eval_once<B: BenchmarkBackend>(
         baseline_bucket_hits_sig: None,
         underconstrained_candidate: false,
         semantic_injection_applied: eval.semantic_injection_applied,
+        injection_caught: eval.injection_caught,
+        trace_truncated: eval.trace_truncated,
+        trace_path: None,
     }
 }
 
@@ -348,6 +471,8 @@ fn bug_kind(stats: &EvalStats) -> Option<&'static str> {
         && (stats.backend_error.is_some() || stats.oracle_error.is_some() || stats.timed_out)
     {
         Some("exception")
+    } else if baseline_mismatch && stats.csr_semantics_divergence {
+        Some("csr_semantics_divergence")
     } else if baseline_mismatch {
         Some("mismatch")
     } else if stats.underconstrained_candidate {
@@ -357,12 +482,28 @@ fn bug_kind(stats: &EvalStats) -> Option<&'static str> {
     }
 }
 
+/// Bugs from injected faults the backend silently accepted outrank every other bug kind.
+const UNCAUGHT_INJECTION_BUG_WEIGHT: f64 = 10.0;
+
+fn bug_weight(stats: &EvalStats) -> f64 {
+    if stats.injection_caught == Some(false) { UNCAUGHT_INJECTION_BUG_WEIGHT } else { 1.0 }
+}
+
+/// Count each bucket id hit by one evaluation once.
+fn count_bucket_hits(counts: &mut HashMap<String, u64>, hits: &[BucketHit]) {
+    let ids: HashSet<&str> = hits.iter().map(|hit| hit.bucket_id.as_str()).collect();
+    for id in ids {
+        *counts.entry(id.to_string()).or_default() += 1;
+    }
+}
+
 fn semantic_search_solved(stats: &EvalStats) -> bool {
     stats.phase == "semantic_search" && stats.underconstrained_candidate
 }
 
 fn is_baseline_mismatch(stats: &EvalStats) -> bool {
-    stats.phase == "baseline" && !stats.mismatch_regs.is_empty()
+    stats.phase == "baseline"
+        && (!stats.mismatch_regs.is_empty() || !stats.mismatch_memory.is_empty())
 }
 
 fn write_run_record(
@@ -389,11 +530,14 @@ fn write_run_record(
         .insert("underconstrained_candidate".to_string(), json!(stats.underconstrained_candidate));
     metadata
         .insert("semantic_injection_applied".to_string(), json!(stats.semantic_injection_applied));
+    metadata.insert("injection_caught".to_string(), json!(stats.injection_caught));
+    metadata.insert("trace_truncated".to_string(), json!(stats.trace_truncated));
     metadata.insert("attempt_index".to_string(), json!(attempt_index));
     metadata.insert("kind".to_string(), json!("run"));
     metadata.insert("is_bug".to_string(), json!(bug_kind(stats).is_some()));
 
     let rec = RunRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
         zkvm_commit: cfg.zkvm_commit.clone(),
         rng_seed: cfg.rng_seed,
         timeout_ms: cfg.timeout_ms,
@@ -426,6 +570,7 @@ fn write_corpus_record(
     metadata.insert("kind".to_string(), json!("baseline_seed"));
 
     let rec = CorpusRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
         zkvm_commit: cfg.zkvm_commit.clone(),
         rng_seed: cfg.rng_seed,
         timeout_ms: cfg.timeout_ms,
@@ -456,6 +601,8 @@ fn write_bug_record(
     metadata.insert("phase".to_string(), json!(stats.phase));
     metadata.insert("seed_index".to_string(), json!(seed_index));
     metadata.insert("kind".to_string(), json!(kind));
+    let csr_divergence = kind == "csr_semantics_divergence";
+    metadata.insert("csr_semantics_divergence".to_string(), json!(csr_divergence));
     metadata.insert("semantic_class".to_string(), json!(stats.semantic_class));
     metadata.insert("inject_kind".to_string(), json!(stats.inject_kind));
     metadata.insert("inject_step".to_string(), json!(stats.inject_step));
@@ -466,9 +613,17 @@ fn write_bug_record(
         .insert("underconstrained_candidate".to_string(), json!(stats.underconstrained_candidate));
     metadata
         .insert("semantic_injection_applied".to_string(), json!(stats.semantic_injection_applied));
+    metadata.insert("injection_caught".to_string(), json!(stats.injection_caught));
+    metadata.insert("trace_truncated".to_string(), json!(stats.trace_truncated));
+    metadata.insert("weight".to_string(), json!(bug_weight(stats)));
     metadata.insert("attempt_index".to_string(), json!(attempt_index));
+    metadata.insert(
+        "trace_path".to_string(),
+        json!(stats.trace_path.as_ref().map(|p| p.display().to_string())),
+    );
 
     let rec = BugRecord {
+        schema_version: RECORD_SCHEMA_VERSION,
         zkvm_commit: cfg.zkvm_commit.clone(),
         rng_seed: cfg.rng_seed,
         timeout_ms: cfg.timeout_ms,
@@ -480,6 +635,7 @@ fn write_bug_record(
         oracle_error: stats.oracle_error.clone(),
         bucket_hits: stats.bucket_hits.clone(),
         mismatch_regs: stats.mismatch_regs.clone(),
+        mismatch_memory: stats.mismatch_memory.clone(),
         instructions: words.to_vec(),
         metadata: serde_json::Value::Object(metadata),
     };
@@ -487,6 +643,30 @@ fn write_bug_record(
     Ok(true)
 }
 
+/// Write the backend's trace of run `eval_id` under `traces_dir` when `stats` is a bug, returning
+/// the path for the bug record (`None` when not a bug, not recording, or the write failed).
+fn persist_bug_trace<B: BenchmarkBackend>(
+    backend: &mut B,
+    traces_dir: Option<&Path>,
+    prefix: &str,
+    eval_id: u64,
+    stats: &EvalStats,
+) -> Option<PathBuf> {
+    let dir = traces_dir?;
+    bug_kind(stats)?;
+    let path = dir.join(format!("{prefix}-eval{eval_id}.json"));
+    match backend.persist_last_trace(&path) {
+        Ok(()) => {
+            eprintln!("[BENCHMARK] wrote bug trace {}", path.display());
+            Some(path)
+        }
+        Err(e) => {
+            eprintln!("[BENCHMARK][WARN] persist bug trace {} failed: {e}", path.display());
+            None
+        }
+    }
+}
+
 fn centered_steps(
     anchor: u64,
     before: u64,
@@ -608,10 +788,20 @@ pub fn run_benchmark<B: BenchmarkBackend>(
     let corpus_path = cfg.out_dir.join(format!("{base_prefix}-corpus.jsonl"));
     let bugs_path = cfg.out_dir.join(format!("{base_prefix}-bugs.jsonl"));
     let runs_path = cfg.out_dir.join(format!("{base_prefix}-runs.jsonl"));
+    let bucket_histogram_path = cfg.out_dir.join(format!("{base_prefix}-bucket-histogram.json"));
 
     let corpus_writer = JsonlWriter::open_append(&corpus_path)?;
     let bug_writer = JsonlWriter::open_append(&bugs_path)?;
     let run_writer = JsonlWriter::open_append(&runs_path)?;
+    let traces_dir = if cfg.record_bug_traces {
+        let dir = cfg.out_dir.join("traces");
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("create traces dir {} failed: {e}", dir.display()))?;
+        backend.set_retain_last_trace(true);
+        Some(dir)
+    } else {
+        None
+    };
 
     let seeds = load_initial_seeds(&cfg.seeds_jsonl, cfg.max_instructions, &|words| {
         backend.is_usable_seed(words)
@@ -626,6 +816,7 @@ pub fn run_benchmark<B: BenchmarkBackend>(
 
     let mut bug_count = 0usize;
     let mut eval_id: u64 = 0;
+    let mut bucket_hit_counts = HashMap::<String, u64>::new();
 
     for (seed_index, (input, seed_meta)) in seeds.into_iter().take(take_n).enumerate() {
         let words = decode_words_from_input(&input, cfg.max_instructions);
@@ -659,8 +850,9 @@ pub fn run_benchmark<B: BenchmarkBackend>(
         }
 
         backend.clear_semantic_injection();
-        let baseline = eval_once(&cfg, timeout, &mut backend, &words);
+        let mut baseline = eval_once(&cfg, timeout, &mut backend, &words);
         eval_id = eval_id.saturating_add(1);
+        count_bucket_hits(&mut bucket_hit_counts, &baseline.bucket_hits);
         write_corpus_record(&cfg, &corpus_writer, &words, seed_index, &seed_meta, &baseline)?;
         write_run_record(
             &cfg,
@@ -672,6 +864,13 @@ pub fn run_benchmark<B: BenchmarkBackend>(
             &baseline,
             None,
         )?;
+        baseline.trace_path = persist_bug_trace(
+            &mut backend,
+            traces_dir.as_deref(),
+            &base_prefix,
+            eval_id,
+            &baseline,
+        );
         if write_bug_record(&cfg, &bug_writer, &words, seed_index, &seed_meta, &baseline, None)? {
             bug_count = bug_count.saturating_add(1);
         }
@@ -707,12 +906,14 @@ pub fn run_benchmark<B: BenchmarkBackend>(
                 injected.trigger_bucket_id = Some(candidate.bucket_id.clone());
                 injected.trigger_signal_id = candidate.trigger_signal_id.clone();
                 injected.baseline_bucket_hits_sig = Some(baseline.bucket_hits_sig.clone());
-                injected.underconstrained_candidate = injected.backend_error.is_none()
-                    && injected.oracle_error.is_none()
-                    && !injected.timed_out
-                    && injected.semantic_injection_applied;
+                injected.underconstrained_candidate = injected.injection_caught == Some(false)
+                    || (injected.backend_error.is_none()
+                        && injected.oracle_error.is_none()
+                        && !injected.timed_out
+                        && injected.semantic_injection_applied);
 
                 eval_id = eval_id.saturating_add(1);
+                count_bucket_hits(&mut bucket_hit_counts, &injected.bucket_hits);
                 write_run_record(
                     &cfg,
                     &run_writer,
@@ -723,6 +924,13 @@ pub fn run_benchmark<B: BenchmarkBackend>(
                     &injected,
                     Some(attempt_index),
                 )?;
+                injected.trace_path = persist_bug_trace(
+                    &mut backend,
+                    traces_dir.as_deref(),
+                    &base_prefix,
+                    eval_id,
+                    &injected,
+                );
                 if write_bug_record(
                     &cfg,
                     &bug_writer,
@@ -754,6 +962,7 @@ pub fn run_benchmark<B: BenchmarkBackend>(
     corpus_writer.flush()?;
     bug_writer.flush()?;
     run_writer.flush()?;
+    write_bucket_histogram(&bucket_histogram_path, &bucket_hit_counts)?;
 
     if bug_count > 0 {
         eprintln!("[BENCHMARK][DONE] bug_records={bug_count}");
@@ -761,12 +970,72 @@ pub fn run_benchmark<B: BenchmarkBackend>(
         eprintln!("[BENCHMARK][DONE] bug_records=0");
     }
 
-    Ok(BenchmarkOutputs { corpus_path, bugs_path, runs_path: Some(runs_path) })
+    Ok(BenchmarkOutputs {
+        corpus_path,
+        bugs_path,
+        runs_path: Some(runs_path),
+        bucket_histogram_path: Some(bucket_histogram_path),
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{bug_kind, centered_steps, sweep_steps, EvalStats};
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use super::{
+        bug_kind, bug_weight, centered_steps, count_bucket_hits, eval_once, evaluate_single,
+        run_benchmark, sweep_steps, BackendEval, BenchmarkBackend, BenchmarkConfig, EvalStats,
+    };
+    use crate::rv32im::oracle::OracleConfig;
+    use crate::trace::BucketHit;
+
+    /// Fails every run; `persist_last_trace` writes the words of the last one.
+    #[derive(Default)]
+    struct FailingBackend {
+        retain: bool,
+        last_words: Vec<u32>,
+        /// Report every run as having hit the trace-size cap.
+        truncated: bool,
+    }
+
+    impl BenchmarkBackend for FailingBackend {
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            self.last_words = words.to_vec();
+            Err("proving failed".to_string())
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval {
+                backend_error: Some("proving failed".to_string()),
+                trace_truncated: self.truncated,
+                ..Default::default()
+            }
+        }
+
+        fn set_retain_last_trace(&mut self, retain: bool) {
+            self.retain = retain;
+        }
+
+        fn persist_last_trace(&mut self, path: &Path) -> Result<(), String> {
+            assert!(self.retain, "trace persisted without retention");
+            let archive = serde_json::json!({ "logs": [], "words": self.last_words });
+            std::fs::write(path, archive.to_string()).map_err(|e| e.to_string())
+        }
+    }
+
+    /// Returns fixed final registers and a clean eval.
+    struct FixedRegsBackend([u32; 32]);
+
+    impl BenchmarkBackend for FixedRegsBackend {
+        fn prove_and_read_final_regs(&mut self, _words: &[u32]) -> Result<[u32; 32], String> {
+            Ok(self.0)
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval::default()
+        }
+    }
 
     #[test]
     fn centered_steps_expand_from_anchor() {
@@ -810,4 +1079,143 @@ mod tests {
         injected.mismatch_regs = vec![(1, 2, 3)];
         assert_eq!(bug_kind(&injected), Some("underconstrained_candidate"));
     }
+
+    #[test]
+    fn uncaught_injection_gets_high_bug_weight() {
+        let mut injected = EvalStats {
+            phase: "semantic_search".to_string(),
+            semantic_injection_applied: true,
+            underconstrained_candidate: true,
+            ..EvalStats::default()
+        };
+        assert_eq!(bug_weight(&injected), 1.0);
+
+        injected.injection_caught = Some(false);
+        assert_eq!(bug_kind(&injected), Some("underconstrained_candidate"));
+        assert!(bug_weight(&injected) > 1.0);
+    }
+
+    /// A one-seed (`addi x1, x0, 5`) benchmark config writing `bench-*` outputs into `dir`.
+    fn mock_config(dir: &Path) -> BenchmarkConfig {
+        let _ = std::fs::remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+        BenchmarkConfig {
+            zkvm_tag: "mock".to_string(),
+            zkvm_commit: "0000000000".to_string(),
+            rng_seed: 7,
+            timeout_ms: 60_000,
+            oracle: OracleConfig::default(),
+            ignored_regs: None,
+            seeds_jsonl: dir.join("seeds.jsonl"),
+            out_dir: dir.to_path_buf(),
+            output_prefix: Some("bench".to_string()),
+            initial_limit: 0,
+            max_instructions: 16,
+            precheck_oracle_max_steps: 0,
+            semantic_search_enabled: false,
+            semantic_window_before: 0,
+            semantic_window_after: 0,
+            semantic_step_stride: 1,
+            semantic_max_trials_per_bucket: 0,
+            stack_size_bytes: 0,
+            record_bug_traces: true,
+        }
+    }
+
+    #[test]
+    fn bug_traces_are_persisted_and_referenced_from_the_bug_record() {
+        let dir = std::env::temp_dir().join(format!("beak-bench-traces-{}", std::process::id()));
+        let outputs = run_benchmark(mock_config(&dir), FailingBackend::default()).unwrap();
+
+        let bugs = std::fs::read_to_string(&outputs.bugs_path).unwrap();
+        let bug: serde_json::Value = serde_json::from_str(bugs.lines().next().unwrap()).unwrap();
+        let trace_path = bug["metadata"]["trace_path"].as_str().unwrap();
+        assert_eq!(Path::new(trace_path), dir.join("traces").join("bench-eval1.json"));
+        let archive: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(trace_path).unwrap()).unwrap();
+        assert_eq!(archive["words"], serde_json::json!([0x0050_0093u32]));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn truncated_traces_are_flagged_in_run_and_bug_records() {
+        let dir = std::env::temp_dir().join(format!("beak-bench-truncated-{}", std::process::id()));
+        let backend = FailingBackend { truncated: true, ..Default::default() };
+        let outputs = run_benchmark(mock_config(&dir), backend).unwrap();
+
+        for path in [outputs.runs_path.as_ref().unwrap(), &outputs.bugs_path] {
+            let records = std::fs::read_to_string(path).unwrap();
+            let record: serde_json::Value =
+                serde_json::from_str(records.lines().next().unwrap()).unwrap();
+            assert_eq!(record["metadata"]["trace_truncated"], serde_json::json!(true));
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ignored_registers_are_left_out_of_the_mismatch_check() {
+        let dir = std::env::temp_dir().join(format!("beak-bench-ignored-{}", std::process::id()));
+        let mut cfg = mock_config(&dir);
+        let timeout = std::time::Duration::from_millis(cfg.timeout_ms);
+        // `addi x1, x0, 5` on a backend that leaves garbage in x0.
+        let mut regs = [0u32; 32];
+        regs[0] = 1;
+        regs[1] = 5;
+        let mut backend = FixedRegsBackend(regs);
+
+        let stats = eval_once(&cfg, timeout, &mut backend, &[0x0050_0093]);
+        assert!(stats.mismatch_regs.is_empty());
+
+        cfg.ignored_regs = Some(Vec::new());
+        let stats = eval_once(&cfg, timeout, &mut backend, &[0x0050_0093]);
+        assert_eq!(stats.mismatch_regs, vec![(0, 0, 1)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn mismatches_confined_to_csr_destinations_are_classified_apart() {
+        let dir = std::env::temp_dir().join(format!("beak-bench-csr-{}", std::process::id()));
+        let cfg = mock_config(&dir);
+        let timeout = std::time::Duration::from_millis(cfg.timeout_ms);
+        // `csrrs x2, cycle, x0` on a backend that leaves 7 in x2.
+        let words = [0xc000_2173];
+        let mut regs = [0u32; 32];
+        regs[2] = 7;
+
+        let stats = eval_once(&cfg, timeout, &mut FixedRegsBackend(regs), &words);
+        assert!(stats.csr_semantics_divergence);
+        assert_eq!(bug_kind(&stats), Some("csr_semantics_divergence"));
+
+        regs[3] = 1;
+        let stats = eval_once(&cfg, timeout, &mut FixedRegsBackend(regs), &words);
+        assert!(!stats.csr_semantics_divergence);
+        assert_eq!(bug_kind(&stats), Some("mismatch"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn single_evaluation_reports_both_sides_of_a_benchmark_backend() {
+        // `addi x1, x0, 5` on a backend that gets x1 wrong.
+        let mut regs = [0u32; 32];
+        regs[1] = 6;
+        let report =
+            evaluate_single(&[0x0050_0093], &mut FixedRegsBackend(regs), OracleConfig::default());
+
+        assert_eq!(report.oracle_regs.map(|r| r[1]), Some(5));
+        assert_eq!(report.backend_regs, Some(regs));
+        assert_eq!(report.backend_error, None);
+        assert_eq!(report.mismatch_regs, vec![(1, 5, 6)]);
+    }
+
+    #[test]
+    fn bucket_histogram_counts_each_id_once_per_evaluation() {
+        let hit = |id: &str| BucketHit { bucket_id: id.to_string(), details: HashMap::new() };
+        let mut counts = HashMap::new();
+        count_bucket_hits(&mut counts, &[hit("a"), hit("a"), hit("b")]);
+        count_bucket_hits(&mut counts, &[hit("a")]);
+        assert_eq!(counts.get("a"), Some(&2));
+        assert_eq!(counts.get("b"), Some(&1));
+    }
 }
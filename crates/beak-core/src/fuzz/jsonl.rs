@@ -1,14 +1,67 @@
+use std::collections::{BTreeSet, HashSet};
 use std::fs::{File, OpenOptions};
-use std::io::{LineWriter, Write};
+use std::io::{BufRead, BufReader, LineWriter, Read, Write};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
+use crate::rv32im::instruction::RV32IMInstruction;
 use crate::trace::BucketHit;
 
-#[derive(Debug, Clone, Serialize)]
+/// On-disk encoding of corpus/bug records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// One JSON object per line.
+    #[default]
+    Jsonl,
+    /// Bincode frames, each prefixed with its little-endian `u32` byte length.
+    Bincode,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Bincode => "bin",
+        }
+    }
+}
+
+/// Serde adapter for JSON-typed fields: unchanged in JSON, embedded as JSON text in binary
+/// encodings, whose decoders cannot drive `serde_json::Value` (it needs `deserialize_any`).
+mod json_text_in_binary {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: Serialize, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            return value.serialize(s);
+        }
+        let text = serde_json::to_string(value).map_err(serde::ser::Error::custom)?;
+        s.serialize_str(&text)
+    }
+
+    pub fn deserialize<'de, T: DeserializeOwned, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<T, D::Error> {
+        if d.is_human_readable() {
+            return T::deserialize(d);
+        }
+        let text = String::deserialize(d)?;
+        serde_json::from_str(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Current shape of the JSONL records; bump together with a step in `migrate_record`.
+pub const RECORD_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorpusRecord {
+    /// Record format version (0 for files written before versioning).
+    #[serde(default)]
+    pub schema_version: u32,
     pub zkvm_commit: String,
     pub rng_seed: u64,
     pub timeout_ms: u64,
@@ -18,11 +71,14 @@ pub struct CorpusRecord {
     pub bucket_hits_sig: String,
     pub signal_sig: String,
     pub instructions: Vec<u32>,
+    #[serde(with = "json_text_in_binary")]
     pub metadata: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BugRecord {
+    #[serde(default)]
+    pub schema_version: u32,
     pub zkvm_commit: String,
     pub rng_seed: u64,
     pub timeout_ms: u64,
@@ -34,14 +90,19 @@ pub struct BugRecord {
     pub micro_op_count: usize,
     pub backend_error: Option<String>,
     pub oracle_error: Option<String>,
+    #[serde(with = "json_text_in_binary")]
     pub bucket_hits: Vec<BucketHit>,
     pub mismatch_regs: Vec<(u32, u32, u32)>, // (idx, oracle, backend)
+    pub mismatch_memory: Vec<(u32, u8, u8)>, // (addr, oracle, backend)
     pub instructions: Vec<u32>,
+    #[serde(with = "json_text_in_binary")]
     pub metadata: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunRecord {
+    #[serde(default)]
+    pub schema_version: u32,
     pub zkvm_commit: String,
     pub rng_seed: u64,
     pub timeout_ms: u64,
@@ -55,23 +116,189 @@ pub struct RunRecord {
     pub oracle_error: Option<String>,
     pub mismatch_regs: Vec<(u32, u32, u32)>, // (idx, oracle, backend)
     pub instructions: Vec<u32>,
+    #[serde(with = "json_text_in_binary")]
     pub metadata: serde_json::Value,
 }
 
+/// Upgrade a raw corpus/bug/run record to `RECORD_SCHEMA_VERSION`, one version step at a time.
+///
+/// Fields are only added when absent, so the same steps serve every record kind.
+pub fn migrate_record(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let obj = value.as_object_mut().ok_or_else(|| "record is not a JSON object".to_string())?;
+    let mut version = match obj.get("schema_version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("invalid schema_version {v}"))?,
+    };
+    if version > RECORD_SCHEMA_VERSION {
+        return Err(format!(
+            "schema_version {version} is newer than supported {RECORD_SCHEMA_VERSION}"
+        ));
+    }
+    while version < RECORD_SCHEMA_VERSION {
+        match version {
+            // v0 -> v1: signal signatures, memory mismatches and metadata became mandatory.
+            0 => {
+                obj.entry("signal_sig").or_insert_with(|| serde_json::json!(""));
+                obj.entry("metadata").or_insert_with(|| serde_json::json!({}));
+                if obj.contains_key("bucket_hits") {
+                    obj.entry("mismatch_memory").or_insert_with(|| serde_json::json!([]));
+                }
+            }
+            _ => unreachable!("missing migration step from schema_version {version}"),
+        }
+        version += 1;
+    }
+    obj.insert("schema_version".to_string(), serde_json::json!(RECORD_SCHEMA_VERSION));
+    Ok(value)
+}
+
+/// Stream the JSONL records at `path`, migrating each line to the current schema before decoding.
+pub fn read_records<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+    let f = File::open(path).map_err(|e| format!("open {} failed: {e}", path.display()))?;
+    let mut out = Vec::new();
+    for (idx, line) in BufReader::new(f).lines().enumerate() {
+        let line = line.map_err(|e| format!("read {} failed: {e}", path.display()))?;
+        let s = line.trim();
+        if s.is_empty() {
+            continue;
+        }
+        let parsed = serde_json::from_str(s)
+            .map_err(|e| e.to_string())
+            .and_then(migrate_record)
+            .and_then(|v| serde_json::from_value(v).map_err(|e| e.to_string()))
+            .map_err(|e| format!("parse {} line {} failed: {e}", path.display(), idx + 1))?;
+        out.push(parsed);
+    }
+    Ok(out)
+}
+
+/// Read the length-prefixed bincode records written by a `JsonlWriter` in `OutputFormat::Bincode`.
+///
+/// Binary files are written at the current schema, so no migration is applied.
+pub fn read_bincode_records<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+    let mut f = File::open(path).map_err(|e| format!("open {} failed: {e}", path.display()))?;
+    let mut bytes = Vec::new();
+    f.read_to_end(&mut bytes).map_err(|e| format!("read {} failed: {e}", path.display()))?;
+    let mut out = Vec::new();
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        let (len, tail) = rest
+            .split_first_chunk::<4>()
+            .ok_or_else(|| format!("{}: truncated frame length", path.display()))?;
+        let len = u32::from_le_bytes(*len) as usize;
+        if tail.len() < len {
+            return Err(format!("{}: truncated frame (record {})", path.display(), out.len()));
+        }
+        let (frame, tail) = tail.split_at(len);
+        let (record, _) = bincode::serde::decode_from_slice(frame, bincode::config::standard())
+            .map_err(|e| format!("decode {} record {} failed: {e}", path.display(), out.len()))?;
+        out.push(record);
+        rest = tail;
+    }
+    Ok(out)
+}
+
+/// Sorted distinct mnemonics of the decodable words (stored as `metadata.mnemonics`).
+pub fn distinct_mnemonics(words: &[u32]) -> Vec<String> {
+    let set: BTreeSet<String> = words
+        .iter()
+        .filter_map(|&w| RV32IMInstruction::from_word(w).ok())
+        .map(|insn| insn.mnemonic)
+        .collect();
+    set.into_iter().collect()
+}
+
+/// Decodable words whose `reencode()` does not reproduce them, as `(word, reencoded)`; the
+/// second element is `None` when the decoded fields cannot be encoded at all.
+pub fn reencode_mismatches(words: &[u32]) -> Vec<(u32, Option<u32>)> {
+    words
+        .iter()
+        .filter_map(|&w| {
+            let reencoded = RV32IMInstruction::from_word(w).ok()?.reencode().ok();
+            (reencoded != Some(w)).then_some((w, reencoded))
+        })
+        .collect()
+}
+
+fn record_has_mnemonic(rec: &CorpusRecord, mnemonic: &str) -> bool {
+    match rec.metadata.get("mnemonics").and_then(|v| v.as_array()) {
+        Some(tagged) => tagged.iter().any(|m| m.as_str() == Some(mnemonic)),
+        // Records written before mnemonic tagging: decode on the fly.
+        None => distinct_mnemonics(&rec.instructions).iter().any(|m| m == mnemonic),
+    }
+}
+
+/// Load the corpus records at `path` whose instructions include `mnemonic` (case-insensitive).
+pub fn filter_corpus_by_mnemonic(path: &Path, mnemonic: &str) -> Result<Vec<CorpusRecord>, String> {
+    let mnemonic = mnemonic.to_ascii_lowercase();
+    let records: Vec<CorpusRecord> = read_records(path)?;
+    Ok(records.into_iter().filter(|rec| record_has_mnemonic(rec, &mnemonic)).collect())
+}
+
+/// Records at `path`, read as bincode for `.bin` files and as JSONL otherwise.
+fn read_records_by_extension<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+    if path.extension().is_some_and(|ext| ext == OutputFormat::Bincode.extension()) {
+        read_bincode_records(path)
+    } else {
+        read_records(path)
+    }
+}
+
+/// Distinct non-empty `bucket_hits_sig` values of the bug records at `path` (`.bin` files are
+/// read as bincode, anything else as JSONL).
+pub fn load_bug_signatures(path: &Path) -> Result<HashSet<String>, String> {
+    let records: Vec<BugRecord> = read_records_by_extension(path)?;
+    Ok(records.into_iter().map(|rec| rec.bucket_hits_sig).filter(|sig| !sig.is_empty()).collect())
+}
+
+/// Corpus records written by an earlier campaign (`.bin` files are read as bincode, anything
+/// else as JSONL).
+pub fn load_corpus_records(path: &Path) -> Result<Vec<CorpusRecord>, String> {
+    read_records_by_extension(path)
+}
+
 #[derive(Clone)]
 pub struct JsonlWriter {
     // LineWriter flushes on newline, so corpus/bugs entries appear even for long runs.
     inner: Arc<Mutex<LineWriter<File>>>,
+    format: OutputFormat,
 }
 
 impl JsonlWriter {
     pub fn open_append(path: &Path) -> Result<Self, String> {
+        Self::open_append_as(path, OutputFormat::Jsonl)
+    }
+
+    /// Open a writer whose `append_record` uses `format`; `append_json_line` always writes JSON.
+    pub fn open_append_as(path: &Path, format: OutputFormat) -> Result<Self, String> {
         let f = OpenOptions::new()
             .create(true)
             .append(true)
             .open(path)
             .map_err(|e| format!("open {} failed: {e}", path.display()))?;
-        Ok(Self { inner: Arc::new(Mutex::new(LineWriter::new(f))) })
+        Ok(Self { inner: Arc::new(Mutex::new(LineWriter::new(f))), format })
+    }
+
+    /// Append one record in the writer's `OutputFormat`.
+    pub fn append_record<T: Serialize>(&self, value: &T) -> Result<(), String> {
+        match self.format {
+            OutputFormat::Jsonl => self.append_json_line(value),
+            OutputFormat::Bincode => {
+                let frame = bincode::serde::encode_to_vec(value, bincode::config::standard())
+                    .map_err(|e| format!("bincode encode failed: {e}"))?;
+                let len = u32::try_from(frame.len())
+                    .map_err(|_| format!("bincode record too large ({} bytes)", frame.len()))?;
+                let mut w = self.inner.lock().map_err(|_| "writer mutex poisoned".to_string())?;
+                w.write_all(&len.to_le_bytes())
+                    .and_then(|_| w.write_all(&frame))
+                    .and_then(|_| w.flush())
+                    .map_err(|e| format!("write bincode failed: {e}"))?;
+                Ok(())
+            }
+        }
     }
 
     pub fn append_json_line<T: Serialize>(&self, value: &T) -> Result<(), String> {
@@ -87,3 +314,120 @@ impl JsonlWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_corpus_by_tagged_or_decoded_mnemonics() {
+        let add = RV32IMInstruction::from_asm("add x1, x2, x3").unwrap().word;
+        let div = RV32IMInstruction::from_asm("div x1, x2, x3").unwrap().word;
+        assert_eq!(distinct_mnemonics(&[div, add, add]), vec!["add", "div"]);
+
+        let record = |instructions: Vec<u32>, metadata: serde_json::Value| CorpusRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
+            zkvm_commit: String::new(),
+            rng_seed: 0,
+            timeout_ms: 0,
+            timed_out: false,
+            mismatch: false,
+            bucket_hits_sig: String::new(),
+            signal_sig: String::new(),
+            instructions,
+            metadata,
+        };
+        let path =
+            std::env::temp_dir().join(format!("beak-jsonl-filter-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let writer = JsonlWriter::open_append(&path).unwrap();
+        let tagged =
+            record(vec![div], serde_json::json!({ "mnemonics": distinct_mnemonics(&[div]) }));
+        writer.append_json_line(&tagged).unwrap();
+        writer.append_json_line(&record(vec![add], serde_json::json!({}))).unwrap();
+        writer.append_json_line(&record(vec![add, div], serde_json::json!({}))).unwrap();
+
+        assert_eq!(filter_corpus_by_mnemonic(&path, "DIV").unwrap().len(), 2);
+        assert_eq!(filter_corpus_by_mnemonic(&path, "add").unwrap().len(), 2);
+        assert!(filter_corpus_by_mnemonic(&path, "rem").unwrap().is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn reports_words_that_do_not_reencode() {
+        let add = RV32IMInstruction::from_asm("add x1, x2, x3").unwrap().word;
+        let div = RV32IMInstruction::from_asm("div x1, x2, x3").unwrap().word;
+        let lui = RV32IMInstruction::from_asm("lui x31, 0x108a").unwrap().word;
+        assert!(reencode_mismatches(&[add, div, lui]).is_empty());
+        // `fence iorw, iorw` keeps pred/succ bits the encoder drops.
+        assert_eq!(
+            reencode_mismatches(&[add, 0x0ff0_000f, 0x3020_0073]),
+            vec![(0x0ff0_000f, Some(0x0000_000f))]
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_bug_records_on_read() {
+        let legacy = serde_json::json!({
+            "zkvm_commit": "abc",
+            "rng_seed": 1,
+            "timeout_ms": 10,
+            "timed_out": false,
+            "bucket_hits_sig": "",
+            "micro_op_count": 0,
+            "backend_error": null,
+            "oracle_error": null,
+            "bucket_hits": [],
+            "mismatch_regs": [[1, 2, 3]],
+            "instructions": [19],
+        });
+        let migrated = migrate_record(legacy).unwrap();
+        let rec: BugRecord = serde_json::from_value(migrated).unwrap();
+        assert_eq!(rec.schema_version, RECORD_SCHEMA_VERSION);
+        assert!(rec.mismatch_memory.is_empty());
+        assert_eq!(rec.signal_sig, "");
+
+        let future = serde_json::json!({ "schema_version": RECORD_SCHEMA_VERSION + 1 });
+        assert!(migrate_record(future).is_err());
+    }
+
+    #[test]
+    fn bincode_records_round_trip() {
+        let mut details = std::collections::HashMap::new();
+        details.insert("step_idx".to_string(), serde_json::json!(3));
+        let bug = BugRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
+            zkvm_commit: "abc".to_string(),
+            rng_seed: 7,
+            timeout_ms: 10,
+            timed_out: false,
+            bucket_hits_sig: "sem.time.connector_chain_break".to_string(),
+            signal_sig: String::new(),
+            micro_op_count: 4,
+            backend_error: None,
+            oracle_error: Some("oracle panic".to_string()),
+            bucket_hits: vec![BucketHit {
+                bucket_id: "sem.time.connector_chain_break".to_string(),
+                details,
+            }],
+            mismatch_regs: vec![(1, 2, 3)],
+            mismatch_memory: Vec::new(),
+            instructions: vec![0x13, 0x93],
+            metadata: serde_json::json!({ "kind": "mismatch", "slow": false }),
+        };
+        let path =
+            std::env::temp_dir().join(format!("beak-jsonl-bincode-{}.bin", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let writer = JsonlWriter::open_append_as(&path, OutputFormat::Bincode).unwrap();
+        writer.append_record(&bug).unwrap();
+        writer.append_record(&bug).unwrap();
+
+        let read: Vec<BugRecord> = read_bincode_records(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].metadata, bug.metadata);
+        assert_eq!(read[1].bucket_hits[0].details["step_idx"], 3);
+        assert_eq!(read[1].oracle_error, bug.oracle_error);
+        assert_eq!(read[1].instructions, bug.instructions);
+        let _ = std::fs::remove_file(&path);
+    }
+}
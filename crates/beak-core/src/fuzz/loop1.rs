@@ -1,30 +1,62 @@
-use std::collections::HashSet;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
-use std::sync::{LazyLock, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, LazyLock, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::fuzz::jsonl::{BugRecord, CorpusRecord, JsonlWriter, RunRecord};
-use crate::fuzz::seed::FuzzingSeed;
-use crate::rv32im::instruction::RV32IMInstruction;
-use crate::rv32im::oracle::{OracleConfig, RISCVOracle};
+use crate::fuzz::jsonl::{
+    distinct_mnemonics, load_bug_signatures, load_corpus_records, reencode_mismatches, BugRecord,
+    CorpusRecord, JsonlWriter, OutputFormat, RunRecord, RECORD_SCHEMA_VERSION,
+};
+use crate::fuzz::seed::{diagnose_seed_line, greedy_unique_bucket_order, summarize_dropped_seeds};
+use crate::rv32im::instruction::{canonicalize_program, RV32IMInstruction};
+use crate::rv32im::oracle::{
+    mismatch_memory, MemorySnapshot, OracleConfig, OracleExecution, OracleExit, RISCVOracle,
+};
+use crate::rv32im::supported::SupportedOpcodes;
 use crate::trace::{
-    sorted_signatures_from_hits, sorted_signatures_from_signals, BucketHit, TraceSignal,
+    semantic, sorted_signatures_from_hits, sorted_signatures_from_signals, BucketHit, TraceSignal,
 };
 use libafl::prelude::*;
-use libafl_bolts::rands::StdRand;
+use libafl_bolts::rands::{Rand, StdRand};
 use libafl_bolts::tuples::tuple_list;
 use libafl_bolts::Named;
 
 use super::bandit;
-use super::mutators::{SeedMutator, SEED_MUTATOR_NUM_ARMS};
+use super::mutators::{
+    generate_random_program, SeedMutator, SEED_MUTATOR_ARM_NAMES, SEED_MUTATOR_NUM_ARMS,
+};
+use super::result_cache::ResultCache;
 
 pub const DEFAULT_RNG_SEED: u64 = 2026;
+/// Registers skipped by the oracle comparison unless configured otherwise: `x0` is hardwired to
+/// zero, and a backend that never re-zeroes it would otherwise report it on every run.
+pub const DEFAULT_IGNORED_REGS: &[u8] = &[0];
+/// Longest program synthesized by the random seed bootstrap.
+const RANDOM_BOOTSTRAP_MAX_LEN: usize = 32;
+/// Generated programs tried per requested bootstrap seed before giving up on `is_usable_seed`.
+const RANDOM_BOOTSTRAP_ATTEMPTS_PER_SEED: usize = 8;
 
 type LoopState =
     StdState<InMemoryCorpus<BytesInput>, BytesInput, StdRand, InMemoryCorpus<BytesInput>>;
 
+/// What distinguishes two bug records of the same kind, signature and errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BugDedupKey {
+    /// The full instruction list (every distinct program is its own record).
+    #[default]
+    RawInstructions,
+    /// The program without the instructions whose results cannot reach a mismatching register
+    /// (see `strip_dead_instructions`), plus the mismatching register indices.
+    MinimizedPlusRegs,
+    /// Only the mismatching register indices.
+    RegsOnly,
+}
+
 #[derive(Debug, Clone)]
 pub struct Loop1Config {
     pub zkvm_tag: String,
@@ -32,18 +64,98 @@ pub struct Loop1Config {
     pub rng_seed: u64,
     pub timeout_ms: u64,
     pub oracle: OracleConfig,
+    /// Register indices excluded from `mismatch_regs`, for backends that do not model some
+    /// registers. `None` falls back to `DEFAULT_IGNORED_REGS`.
+    pub ignored_regs: Option<Vec<u8>>,
 
     pub seeds_jsonl: PathBuf,
     pub out_dir: PathBuf,
     pub output_prefix: Option<String>,
+    /// Encoding of the corpus and bug files (`.jsonl` or length-prefixed bincode `.bin`). The
+    /// runs log is always JSONL.
+    pub output_format: OutputFormat,
 
     pub initial_limit: usize,
+    /// Shuffle loaded seeds with `rng_seed` before applying `initial_limit`, so the cap samples
+    /// across the whole seed file instead of its first entries.
+    pub shuffle_initial_seeds: bool,
+    /// Drop initial seeds whose `canonicalize_program` form (registers renamed in first-use
+    /// order) matches an earlier seed, collapsing inputs that differ only in temporaries.
+    pub dedup_alpha_equivalent_seeds: bool,
+    /// If > 0 and no usable seed was loaded (or `seeds_jsonl` does not exist), start from this
+    /// many random valid programs (`generate_random_program`) that pass `is_usable_seed`.
+    pub random_bootstrap_seeds: usize,
+    /// Optional prior `corpus.jsonl` to continue from: its programs join the corpus (without being
+    /// re-evaluated) and its `bucket_hits_sig`s pre-populate the novelty sets, so only coverage
+    /// beyond the earlier campaign counts as new. `seeds_jsonl` may then be absent.
+    pub resume_from: Option<PathBuf>,
     pub max_instructions: usize,
+    /// Mutated inputs shorter than this are padded with NOPs (0 disables the floor); longer
+    /// than `max_instructions` are truncated, so stored testcases match what is executed.
+    pub min_instructions: usize,
     pub iters: usize,
     pub chain_direct_injection: bool,
     /// If > 0, run a cheap oracle pre-check and skip backend execution when the input reaches
     /// this step bound (likely non-terminating path).
     pub precheck_oracle_max_steps: u32,
+    /// Skip the backend for inputs the oracle proves never terminate (`OracleExit::Looped`),
+    /// even when the step-bound precheck is off. Looping inputs are never reported as bugs.
+    pub skip_oracle_looped: bool,
+    /// Probability in [0, 1] of keeping a non-novel input in the corpus anyway (tagged
+    /// `metadata.kind = "baseline"`). Drawn from the fuzzer state RNG for reproducibility.
+    pub keep_boring_rate: f64,
+    /// If set, stop admitting inputs to the corpus once this fraction (in (0, 1]) of the bucket
+    /// universe has been hit: `required_buckets` if non-empty, else `semantic::ALL_BUCKETS` (the
+    /// generated families such as the opcode bigrams are too large to ever be mostly covered).
+    /// Bugs and runs are still recorded after the freeze; only corpus growth stops.
+    pub corpus_freeze_coverage: Option<f64>,
+    /// If set, keep an input only when its `interestingness_score` exceeds this value instead of
+    /// whenever its bucket signature is new.
+    pub interesting_threshold: Option<f64>,
+    /// Re-sort signatures lexically after dedup so the joined signature does not depend on
+    /// bucket-type registration order (which `sorted_signatures_from_hits` sorts by first).
+    pub sort_signature_lexically: bool,
+    /// Optional hook whose JSON object result is merged into corpus and bug record metadata.
+    pub enrich_metadata: Option<MetadataEnricher>,
+    /// If set, an input that times out is re-run once with `timeout_ms * multiplier`; only a
+    /// second timeout is recorded as an exception. A successful retry is tagged `slow`.
+    pub timeout_retry_multiplier: Option<f64>,
+    /// If > 0, a coverage plateau is declared after this many iterations without a new distinct
+    /// bucket id. On a plateau the loop stops (`stop_on_plateau`) or boosts bandit exploration.
+    pub plateau_window: usize,
+    pub stop_on_plateau: bool,
+    /// If set, a bucket signature or bucket id not hit for this many evaluations is forgotten by
+    /// the novelty filter, so hitting it again counts as new (and earns bandit reward). Eviction
+    /// is driven by the evaluation count, so it is deterministic for a given `rng_seed`.
+    pub novelty_ttl_iters: Option<usize>,
+    /// Capacity of the LRU cache of baseline results keyed by input bytes (0 disables). A hit
+    /// skips the baseline backend run but still flows through feedback; timed-out and slow
+    /// results are never cached.
+    pub result_cache_size: usize,
+    /// Extra backends that evaluate initial seeds concurrently before fuzzing starts (0 keeps the
+    /// serial warmup). Only honored by `run_loop1_threaded_pool`, which can build more backends;
+    /// results still reach the feedback in corpus index order.
+    pub initial_eval_workers: usize,
+    /// Also write the seed-corpus coverage logged after the initial evaluation (distinct bucket
+    /// ids and signatures, redundant seeds) to `{prefix}-initial-coverage.json`.
+    pub initial_coverage_report: bool,
+    /// After the initial evaluation, reorder the corpus so seeds contributing the most bucket ids
+    /// no earlier-scheduled seed covers are fuzzed first (greedy set cover); seeds that add
+    /// nothing go last. Matters most when `iters` is small relative to the seed count.
+    pub schedule_seeds_by_unique_buckets: bool,
+    /// Optional prior `bugs.jsonl`; runs whose bucket signature matches one of its bug signatures
+    /// earn `known_bug_reward` extra bandit reward, keeping mutation near known bug clusters.
+    pub known_bug_sigs_path: Option<PathBuf>,
+    pub known_bug_reward: f64,
+    /// Program part of the bug-record dedup key (kind, signature and errors always take part).
+    pub dedup_bugs_by: BugDedupKey,
+    /// Add `metadata.disasm`, the per-instruction assembly of the program, to bug records.
+    pub include_disasm_in_bugs: bool,
+    /// Bucket ids the campaign must reach: if any is missing from the seen bucket ids at the end,
+    /// `run_loop1` returns an error listing them (after writing its outputs as usual).
+    pub required_buckets: Vec<String>,
+    /// Single-bit witness flips `run_loop2` tries per seed program.
+    pub witness_flips_per_seed: usize,
 
     pub stack_size_bytes: usize,
 }
@@ -53,10 +165,143 @@ pub struct Loop1Outputs {
     pub corpus_path: PathBuf,
     pub bugs_path: PathBuf,
     pub runs_path: Option<PathBuf>,
+    /// JSON object of bucket id -> number of runs that hit it over the whole campaign.
+    pub bucket_histogram_path: Option<PathBuf>,
+    /// Per-arm pull counts and rewards of the mutator bandit at the end of the campaign.
+    pub bandit_stats_path: Option<PathBuf>,
+}
+
+pub type MetadataEnricherFn = dyn Fn(&[u32], &RunStats) -> serde_json::Value + Send + Sync;
+
+/// Caller-supplied metadata annotator: receives the decoded words and run stats of an input.
+#[derive(Clone)]
+pub struct MetadataEnricher(pub Arc<MetadataEnricherFn>);
+
+impl std::fmt::Debug for MetadataEnricher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MetadataEnricher(..)")
+    }
+}
+
+/// Merge the enricher's object fields into `metadata` without overriding built-in keys.
+fn enrich_record_metadata(
+    enricher: Option<&MetadataEnricher>,
+    words: &[u32],
+    stats: &RunStats,
+    metadata: &mut serde_json::Value,
+) {
+    let Some(enricher) = enricher else {
+        return;
+    };
+    let (serde_json::Value::Object(extra), Some(meta)) =
+        ((enricher.0)(words, stats), metadata.as_object_mut())
+    else {
+        return;
+    };
+    for (key, value) in extra {
+        meta.entry(key).or_insert(value);
+    }
+}
+
+/// Assembly text of each word for `metadata.disasm`; undecodable words are kept as `.word 0x..`.
+pub(crate) fn disassemble_words(words: &[u32]) -> Vec<String> {
+    words
+        .iter()
+        .map(|&w| match RV32IMInstruction::from_word(w) {
+            Ok(insn) => insn.asm,
+            Err(_) => format!(".word {w:#010x}"),
+        })
+        .collect()
+}
+
+/// Mnemonics whose only effect is writing `rd`.
+const PURE_MNEMONICS: &[&str] = &[
+    "add", "addi", "sub", "sll", "slli", "slt", "slti", "sltu", "sltiu", "xor", "xori", "srl",
+    "srli", "sra", "srai", "or", "ori", "and", "andi", "lui", "auipc", "mul", "mulh", "mulhsu",
+    "mulhu", "div", "divu", "rem", "remu",
+];
+
+/// Loads and stores: kept since they can fault, which ends the run with the registers as they are.
+const MEMORY_MNEMONICS: &[&str] = &["lb", "lh", "lw", "lbu", "lhu", "sb", "sh", "sw"];
+
+/// Drop the ALU/MUL/DIV/LUI/AUIPC instructions whose result cannot reach a register in the
+/// `live_out` bitmask: writes to `x0`, and writes that are overwritten or never read before the
+/// end of the program. Programs differing only in such filler reduce to the same sequence.
+///
+/// A backward liveness pass over program order. Loads and stores are kept and make their
+/// operands and `live_out` live; control flow, system and undecodable instructions are kept and
+/// make every register live, since any instruction may be reached from them.
+fn strip_dead_instructions(words: &[u32], live_out: u32) -> Vec<u32> {
+    let mut live = live_out;
+    let mut keep = vec![true; words.len()];
+    for (idx, &word) in words.iter().enumerate().rev() {
+        let Ok(insn) = RV32IMInstruction::from_word(word) else {
+            live = u32::MAX;
+            continue;
+        };
+        let reads = [insn.rs1, insn.rs2].into_iter().flatten().fold(0, |m, r| m | 1 << r);
+        if PURE_MNEMONICS.contains(&insn.mnemonic.as_str()) {
+            let rd = insn.rd.unwrap_or(0);
+            if rd == 0 || live & (1 << rd) == 0 {
+                keep[idx] = false;
+                continue;
+            }
+            live = (live & !(1 << rd)) | reads;
+        } else if MEMORY_MNEMONICS.contains(&insn.mnemonic.as_str()) {
+            live |= live_out | reads;
+        } else {
+            live = u32::MAX;
+        }
+    }
+    words.iter().zip(keep).filter_map(|(&w, keep)| keep.then_some(w)).collect()
+}
+
+/// Program/register part of the bug dedup key selected by `mode`.
+fn bug_dedup_program_key(
+    mode: BugDedupKey,
+    words: &[u32],
+    mismatch_regs: &[(u32, u32, u32)],
+) -> String {
+    let hex = |ws: &[u32]| ws.iter().map(|w| format!("{w:08x}")).collect::<Vec<_>>().join(",");
+    let regs =
+        mismatch_regs.iter().map(|(idx, _, _)| format!("x{idx}")).collect::<Vec<_>>().join(",");
+    match mode {
+        BugDedupKey::RawInstructions => hex(words),
+        BugDedupKey::MinimizedPlusRegs => {
+            // Without a register mismatch (memory mismatches, errors), every register is observed.
+            let live_out = match mismatch_regs {
+                [] => u32::MAX,
+                regs => regs.iter().fold(0, |m, (idx, _, _)| m | 1 << idx),
+            };
+            format!("{}|{regs}", hex(&strip_dead_instructions(words, live_out)))
+        }
+        BugDedupKey::RegsOnly => regs,
+    }
 }
 
 fn is_baseline_mismatch(stats: &RunStats) -> bool {
-    !stats.injected_phase && !stats.mismatch_regs.is_empty()
+    !stats.injected_phase && (!stats.mismatch_regs.is_empty() || !stats.mismatch_memory.is_empty())
+}
+
+/// A register-only mismatch confined to the `rd`s of the input's CSR instructions.
+///
+/// Many zkVMs treat CSR ops as unsupported NOPs while the oracle models them, so such a mismatch
+/// is a known CSR modeling gap rather than an arithmetic bug.
+pub(crate) fn is_csr_semantics_divergence(
+    words: &[u32],
+    mismatch_regs: &[(u32, u32, u32)],
+    mismatch_memory: &[(u32, u8, u8)],
+) -> bool {
+    let csr_rds: HashSet<u32> = words
+        .iter()
+        .filter_map(|&w| RV32IMInstruction::from_word(w).ok())
+        .filter(|insn| insn.mnemonic.starts_with("csr"))
+        .flat_map(|insn| insn.reg_effects().writes)
+        .collect();
+    !csr_rds.is_empty()
+        && mismatch_memory.is_empty()
+        && !mismatch_regs.is_empty()
+        && mismatch_regs.iter().all(|(reg, _, _)| csr_rds.contains(reg))
 }
 
 #[derive(Debug, Clone, Default)]
@@ -71,9 +316,122 @@ pub struct BackendEval {
     pub final_regs: Option<[u32; 32]>,
     pub backend_error: Option<String>,
     pub semantic_injection_applied: bool,
+    /// For injected runs checked against a clean reference run: `Some(true)` if the backend
+    /// rejected the injected witness, `Some(false)` if it was silently accepted. `None` when no
+    /// comparison was made.
+    pub injection_caught: Option<bool>,
+    /// Number of continuation segments the backend split execution into (0 if not reported).
+    pub segment_count: usize,
+    /// Final bytes at the addresses passed to `set_memory_probe` (`None` if not reported).
+    pub final_memory: Option<MemorySnapshot>,
+    /// The backend hit its trace-size cap and dropped micro-ops, so `bucket_hits` and
+    /// `trace_signals` only cover a prefix of the execution.
+    pub trace_truncated: bool,
+}
+
+/// A witness column the last run emitted, with the instruction steps it was emitted at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessFlipSite {
+    /// Backend-defined column name, e.g. a chip kind and field path.
+    pub column: String,
+    pub steps: Vec<u64>,
+}
+
+/// One bit of one witness cell to flip on the next run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessBitFlip {
+    pub column: String,
+    pub step: u64,
+    pub bit: u32,
+}
+
+/// An execution submitted with `LoopBackend::submit`.
+#[derive(Debug)]
+pub enum RequestHandle {
+    /// Finished during `submit` (the blocking default).
+    Ready(Result<[u32; 32], String>),
+    /// Backend-defined id of an in-flight request; resolve it with `LoopBackend::poll`.
+    Pending(u64),
+}
+
+/// Longest `wait_for_request` hands to a single `LoopBackend::poll` call.
+const REQUEST_POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// A pending request is given up on after this many run timeouts: the backend enforces the run
+/// timeout itself, so a request still silent by then belongs to a wedged peer.
+const REQUEST_WAIT_TIMEOUTS: u32 = 2;
+
+/// Wait budget for requests made outside a campaign, which has no run timeout (matches the
+/// campaign's in-process hard timeout).
+const DEFAULT_REQUEST_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// What a backend can execute and report, queried once per campaign with
+/// `LoopBackend::capabilities`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackendCapabilities {
+    /// Instruction subset the backend executes; the default admits every decodable word.
+    pub supported_opcodes: SupportedOpcodes,
+    /// Backend-defined ids of the memory address spaces its trace distinguishes (empty if not
+    /// reported).
+    pub address_spaces: Vec<u32>,
+    /// Longest program the backend accepts, in instructions (`None` = no backend limit).
+    pub max_program_len: Option<usize>,
+    /// The backend reports `BackendEval::final_memory` for `set_memory_probe` addresses.
+    pub memory_readback: bool,
+    /// The backend supports direct witness injection (`arm_direct_injection_from_hits`).
+    pub witness_injection: bool,
+    /// The backend also runs words `RV32IMInstruction::from_word` rejects (the oracle faults on
+    /// them), still subject to `is_usable_seed`.
+    pub undecodable_words: bool,
+}
+
+impl BackendCapabilities {
+    /// Whether `words` fits the program length limit and only uses supported instructions.
+    pub fn allows_program(&self, words: &[u32]) -> bool {
+        self.max_program_len.is_none_or(|max| words.len() <= max)
+            && words.iter().all(|w| self.allows_word(*w))
+    }
+
+    /// Whether `word` is a supported instruction, or an undecodable one the backend opted into.
+    pub fn allows_word(&self, word: u32) -> bool {
+        self.supported_opcodes.allows_word(word)
+            || (self.undecodable_words && RV32IMInstruction::from_word(word).is_err())
+    }
+
+    /// One-line summary for the campaign log.
+    pub fn summary(&self) -> String {
+        let ops = &self.supported_opcodes;
+        let list = |opcodes: &[u32], mnemonics: &[String]| {
+            opcodes
+                .iter()
+                .map(|op| format!("{op:#04x}"))
+                .chain(mnemonics.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let allow = list(&ops.allow_opcodes, &ops.allow_mnemonics);
+        let deny = list(&ops.deny_opcodes, &ops.deny_mnemonics);
+        format!(
+            "opcodes allow=[{}] deny=[{deny}] address_spaces={:?} max_program_len={} \
+             memory_readback={} witness_injection={} undecodable_words={}",
+            if allow.is_empty() { "*" } else { &allow },
+            self.address_spaces,
+            self.max_program_len.map_or_else(|| "unbounded".to_string(), |n| n.to_string()),
+            self.memory_readback,
+            self.witness_injection,
+            self.undecodable_words
+        )
+    }
 }
 
 pub trait LoopBackend {
+    /// Static description of what this backend supports. The default admits every decodable
+    /// program but claims neither memory readback nor witness injection, so the harness skips
+    /// the memory probe and direct injection for backends that do not opt in.
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities::default()
+    }
+
     /// Filter seeds that are known to be invalid/unsupported for this backend.
     fn is_usable_seed(&self, _words: &[u32]) -> bool {
         true
@@ -82,13 +440,47 @@ pub trait LoopBackend {
     /// Backend-specific per-run setup (e.g. enable JSON capture, disable assertions).
     fn prepare_for_run(&mut self, _rng_seed: u64) {}
 
+    /// Bring the backend to a ready state (e.g. spawn a worker and run a trivial program) before
+    /// the per-input timeout starts, so cold-start cost is not charged to the first input. Called
+    /// before every evaluation and must return quickly once warm; a failure is only logged.
+    fn warm_up(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Override the backend's own wall-clock budget (used for timeout retries).
+    fn set_timeout_ms(&mut self, _timeout_ms: u64) {}
+
+    /// Byte addresses the oracle stored to; backends that can read final memory report these
+    /// addresses in `BackendEval::final_memory` for the next run.
+    fn set_memory_probe(&mut self, _addrs: &[u32]) {}
+
     /// Prove (or otherwise execute) and return final architectural regs (best-effort).
     fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String>;
 
+    /// Start executing `words` without blocking. Backends that can run requests concurrently
+    /// (e.g. on worker processes) return `RequestHandle::Pending`; the default runs
+    /// `prove_and_read_final_regs` inline and returns `RequestHandle::Ready`.
+    fn submit(&mut self, words: &[u32]) -> RequestHandle {
+        RequestHandle::Ready(self.prove_and_read_final_regs(words))
+    }
+
+    /// Result of a pending request, or `None` if it is still running after blocking for up to
+    /// `wait` (e.g. `recv_timeout(wait)` on the worker's response channel). Only called with ids
+    /// this backend returned from `submit`.
+    fn poll(&mut self, _request_id: u64, _wait: Duration) -> Option<Result<[u32; 32], String>> {
+        None
+    }
+
     /// Collect trace-derived feedback (bucket ids, hit count, trace stats). This is allowed to be
     /// best-effort; failures should be reflected in `backend_error`.
     fn collect_eval(&mut self) -> BackendEval;
 
+    /// Every bucket id this backend can emit, including backend-specific ones; a `{param}`
+    /// placeholder stands for a generated family. Defaults to `semantic::all_bucket_ids`.
+    fn bucket_ids(&self) -> Vec<String> {
+        semantic::all_bucket_ids()
+    }
+
     /// Whether this backend has a direct witness-injection mapping for a bucket id.
     /// Used by direct bucket->injection mode.
     fn bucket_has_direct_injection(&self, _bucket_id: &str) -> bool {
@@ -103,49 +495,113 @@ pub trait LoopBackend {
     fn arm_direct_injection_from_hits(&mut self, _hits: &[BucketHit]) -> Option<String> {
         None
     }
+
+    /// Witness columns of the last run that `arm_witness_bit_flip` can target.
+    fn witness_flip_sites(&self) -> Vec<WitnessFlipSite> {
+        Vec::new()
+    }
+
+    /// Arm a single-bit witness flip for the next run; cleared by `clear_direct_injection`.
+    /// The run reports whether the flip landed in `BackendEval::semantic_injection_applied` and
+    /// whether the constraints rejected it via `backend_error` / `injection_caught`.
+    fn arm_witness_bit_flip(&mut self, _flip: &WitnessBitFlip) -> Result<(), String> {
+        Err("backend does not support witness bit flips".to_string())
+    }
+}
+
+/// Block until `handle` resolves, or fail once a pending request has produced no result within
+/// `budget`, so a wedged peer cannot hang the caller.
+pub fn wait_for_request<B: LoopBackend + ?Sized>(
+    backend: &mut B,
+    handle: RequestHandle,
+    budget: Duration,
+) -> Result<[u32; 32], String> {
+    let id = match handle {
+        RequestHandle::Ready(result) => return result,
+        RequestHandle::Pending(id) => id,
+    };
+    let deadline = Instant::now() + budget;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(format!(
+                "request {id} produced no result within {} ms",
+                budget.as_millis()
+            ));
+        }
+        if let Some(result) = backend.poll(id, remaining.min(REQUEST_POLL_INTERVAL)) {
+            return result;
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
-struct RunStats {
-    eval_id: u64,
-    bucket_hits_sig: String,
-    signal_sig: String,
+/// Per-input evaluation result passed to feedback and to `Loop1Config::enrich_metadata`.
+pub struct RunStats {
+    pub eval_id: u64,
+    pub bucket_hits_sig: String,
+    pub signal_sig: String,
     /// Copied from `BackendEval::micro_op_count` for logging/bug records.
-    micro_op_count: usize,
-    bucket_hits: Vec<BucketHit>,
-    mismatch_regs: Vec<(u32, u32, u32)>,
-    backend_error: Option<String>,
-    oracle_error: Option<String>,
-    timed_out: bool,
-    has_direct_injection_target: bool,
-    injected_phase: bool,
-    direct_injection_kind: Option<String>,
-    target_buckets: Vec<String>,
-    baseline_bucket_hits_sig: Option<String>,
-    underconstrained_candidate: bool,
-    skip_reason: Option<String>,
+    pub micro_op_count: usize,
+    pub bucket_hits: Vec<BucketHit>,
+    pub mismatch_regs: Vec<(u32, u32, u32)>,
+    /// Byte differences at oracle-stored addresses as `(addr, oracle, backend)`.
+    pub mismatch_memory: Vec<(u32, u8, u8)>,
+    pub backend_error: Option<String>,
+    pub oracle_error: Option<String>,
+    pub timed_out: bool,
+    pub has_direct_injection_target: bool,
+    pub injected_phase: bool,
+    pub direct_injection_kind: Option<String>,
+    pub target_buckets: Vec<String>,
+    pub baseline_bucket_hits_sig: Option<String>,
+    pub underconstrained_candidate: bool,
+    /// Copied from `BackendEval::injection_caught`.
+    pub injection_caught: Option<bool>,
+    pub skip_reason: Option<String>,
+    /// Completed only on a timeout retry with the larger `timeout_retry_multiplier` budget.
+    pub slow: bool,
+    /// The oracle found the program stuck in a loop (`OracleExit::Looped`).
+    pub oracle_looped: bool,
+    /// Copied from `BackendEval::trace_truncated`.
+    pub trace_truncated: bool,
 }
 
 static LAST_RUN: LazyLock<Mutex<RunStats>> = LazyLock::new(|| Mutex::new(RunStats::default()));
 
 fn eval_once<B: LoopBackend>(
     cfg: &Loop1Config,
+    caps: &BackendCapabilities,
     timeout: Duration,
     backend: &mut B,
     words: &[u32],
+    oracle_run: Option<(OracleExecution, MemorySnapshot)>,
 ) -> RunStats {
+    if let Err(e) = backend.warm_up() {
+        eprintln!("[LOOP1][WARN] backend warm-up failed: {e}");
+    }
     let start = Instant::now();
     backend.prepare_for_run(cfg.rng_seed);
 
-    let oracle_regs = catch_unwind_nonfatal(std::panic::AssertUnwindSafe(|| {
-        RISCVOracle::execute_with_config(words, cfg.oracle)
-    }));
+    // Reuse the harness's precheck run when there was one.
+    let oracle_run = match oracle_run {
+        Some(run) => Ok(run),
+        None => catch_unwind_nonfatal(std::panic::AssertUnwindSafe(|| {
+            RISCVOracle::execute_detailed(words, cfg.oracle)
+        })),
+    };
+    let oracle_looped = matches!(&oracle_run, Ok((exec, _)) if exec.exit == OracleExit::Looped);
+    let oracle_regs = oracle_run.map(|(exec, memory)| (exec.regs, memory));
     let panic_oracle_error = match oracle_regs.as_ref() {
         Err(p) => Some(panic_payload_to_string(p.as_ref())),
         _ => None,
     };
+    if let (Ok((_, memory)), true) = (oracle_regs.as_ref(), caps.memory_readback) {
+        backend.set_memory_probe(&memory.keys().copied().collect::<Vec<_>>());
+    }
     let backend_regs = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        backend.prove_and_read_final_regs(words)
+        let handle = backend.submit(words);
+        wait_for_request(backend, handle, timeout.saturating_mul(REQUEST_WAIT_TIMEOUTS))
     }));
     let panic_backend_error = match backend_regs.as_ref() {
         Err(p) => Some(panic_payload_to_string(p.as_ref())),
@@ -157,17 +613,23 @@ fn eval_once<B: LoopBackend>(
         Err(_) => None,
     };
     let mismatches = match (oracle_regs.as_ref(), final_regs.as_ref()) {
-        (Ok(oracle), Some(regs)) => mismatch_regs(oracle, regs),
+        (Ok((oracle, _)), Some(regs)) => {
+            mismatch_regs(oracle, regs, cfg.ignored_regs.as_deref().unwrap_or(DEFAULT_IGNORED_REGS))
+        }
         _ => Vec::new(),
     };
 
     let eval = backend.collect_eval();
+    let memory_mismatches = match (oracle_regs.as_ref(), eval.final_memory.as_ref()) {
+        (Ok((_, oracle)), Some(memory)) => mismatch_memory(oracle, memory),
+        _ => Vec::new(),
+    };
     let backend_error = eval.backend_error.clone().or(panic_backend_error);
     let oracle_error = panic_oracle_error.map(|e| format!("oracle {e}"));
     let bucket_sigs = sorted_signatures_from_hits(&eval.bucket_hits);
     let signal_sigs = sorted_signatures_from_signals(&eval.trace_signals);
-    let sig = canonical_bucket_sig(&bucket_sigs);
-    let signal_sig = canonical_bucket_sig(&signal_sigs);
+    let sig = canonical_bucket_sig(&bucket_sigs, cfg.sort_signature_lexically);
+    let signal_sig = canonical_bucket_sig(&signal_sigs, cfg.sort_signature_lexically);
     let backend_timed_out =
         backend_error.as_deref().map(|e| e.contains("timed out")).unwrap_or(false);
     let timed_out = start.elapsed() > timeout || backend_timed_out;
@@ -179,6 +641,7 @@ fn eval_once<B: LoopBackend>(
         micro_op_count: eval.micro_op_count,
         bucket_hits: eval.bucket_hits,
         mismatch_regs: mismatches,
+        mismatch_memory: memory_mismatches,
         backend_error,
         oracle_error,
         timed_out,
@@ -188,8 +651,146 @@ fn eval_once<B: LoopBackend>(
         target_buckets: Vec::new(),
         baseline_bucket_hits_sig: None,
         underconstrained_candidate: false,
+        injection_caught: eval.injection_caught,
         skip_reason: None,
+        slow: false,
+        oracle_looped,
+        trace_truncated: eval.trace_truncated,
+    }
+}
+
+/// Everything one input does under the oracle and the backend, for single-shot debugging.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SingleEvalReport {
+    pub words: Vec<u32>,
+    pub disasm: Vec<String>,
+    pub oracle_regs: Option<[u32; 32]>,
+    pub oracle_exit: Option<String>,
+    pub oracle_error: Option<String>,
+    pub backend_regs: Option<[u32; 32]>,
+    pub backend_error: Option<String>,
+    /// `(reg, oracle, backend)` for every register that differs.
+    pub mismatch_regs: Vec<(u32, u32, u32)>,
+    /// `(addr, oracle, backend)` for oracle-stored bytes the backend disagrees on.
+    pub mismatch_memory: Vec<(u32, u8, u8)>,
+    pub micro_op_count: usize,
+    pub bucket_hits_sig: String,
+    pub bucket_hits: Vec<BucketHit>,
+    pub trace_truncated: bool,
+}
+
+/// Run one input through the oracle and `backend` outside any campaign and report the result.
+///
+/// Panics from either side are caught and reported as errors; no register is ignored.
+pub fn evaluate_single<B: LoopBackend>(
+    words: &[u32],
+    backend: &mut B,
+    oracle_cfg: OracleConfig,
+) -> SingleEvalReport {
+    let oracle_run = catch_unwind_nonfatal(std::panic::AssertUnwindSafe(|| {
+        RISCVOracle::execute_detailed(words, oracle_cfg)
+    }));
+    let (oracle, oracle_error) = match oracle_run {
+        Ok(run) => (Some(run), None),
+        Err(p) => (None, Some(format!("oracle {}", panic_payload_to_string(p.as_ref())))),
+    };
+    if let (Some((_, memory)), true) = (oracle.as_ref(), backend.capabilities().memory_readback) {
+        backend.set_memory_probe(&memory.keys().copied().collect::<Vec<_>>());
+    }
+    let backend_run = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let handle = backend.submit(words);
+        wait_for_request(backend, handle, DEFAULT_REQUEST_WAIT)
+    }));
+    let (backend_regs, run_error) = match backend_run {
+        Ok(Ok(regs)) => (Some(regs), None),
+        Ok(Err(e)) => (None, Some(e)),
+        Err(p) => (None, Some(panic_payload_to_string(p.as_ref()))),
+    };
+    let eval = backend.collect_eval();
+
+    let mismatch_regs = match (oracle.as_ref(), backend_regs.as_ref()) {
+        (Some((exec, _)), Some(regs)) => mismatch_regs(&exec.regs, regs, &[]),
+        _ => Vec::new(),
+    };
+    let mismatch_memory = match (oracle.as_ref(), eval.final_memory.as_ref()) {
+        (Some((_, oracle_memory)), Some(memory)) => mismatch_memory(oracle_memory, memory),
+        _ => Vec::new(),
+    };
+    SingleEvalReport {
+        words: words.to_vec(),
+        disasm: disassemble_words(words),
+        oracle_regs: oracle.as_ref().map(|(exec, _)| exec.regs),
+        oracle_exit: oracle.as_ref().map(|(exec, _)| format!("{:?}", exec.exit)),
+        oracle_error,
+        backend_regs,
+        backend_error: eval.backend_error.or(run_error),
+        mismatch_regs,
+        mismatch_memory,
+        micro_op_count: eval.micro_op_count,
+        bucket_hits_sig: canonical_bucket_sig(
+            &sorted_signatures_from_hits(&eval.bucket_hits),
+            true,
+        ),
+        bucket_hits: eval.bucket_hits,
+        trace_truncated: eval.trace_truncated,
+    }
+}
+
+/// A program-text token spelled as a full instruction word: `0x`-prefixed hex, or exactly eight
+/// hex digits. Shorter bare hex (`add`, `a0`) is left to the assembler.
+fn parse_hex_word(token: &str) -> Option<u32> {
+    let digits = match token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+        Some(digits) => digits,
+        None if token.len() == 8 => token,
+        None => return None,
+    };
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// Parse a program given as text: one instruction per line (or comma/space separated hex words),
+/// each either a hex word (`0x00500093` or `00500093`) or RV32IM assembly. `#` starts a comment.
+pub fn parse_program_text(text: &str) -> Result<Vec<u32>, String> {
+    let mut words = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let hex: Option<Vec<u32>> = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+            .map(parse_hex_word)
+            .collect();
+        match hex {
+            Some(hex) => words.extend_from_slice(&hex),
+            None => words.push(
+                RV32IMInstruction::from_asm(line)
+                    .map_err(|e| format!("line {}: cannot parse {line:?}: {e:?}", idx + 1))?
+                    .word,
+            ),
+        }
+    }
+    if words.is_empty() {
+        return Err("no instructions in program text".to_string());
     }
+    Ok(words)
+}
+
+/// Read all of stdin as program text (see `parse_program_text` for the format).
+pub fn read_program_stdin() -> Result<Vec<u32>, String> {
+    let mut text = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)
+        .map_err(|e| format!("read stdin failed: {e}"))?;
+    parse_program_text(&text)
+}
+
+/// `evaluate_single` on a program read from stdin (see `parse_program_text` for the format).
+pub fn evaluate_stdin<B: LoopBackend>(
+    backend: &mut B,
+    oracle_cfg: OracleConfig,
+) -> Result<SingleEvalReport, String> {
+    let words = read_program_stdin()?;
+    Ok(evaluate_single(&words, backend, oracle_cfg))
 }
 
 fn now_ts_secs() -> u64 {
@@ -216,9 +817,16 @@ fn encode_words(words: &[u32]) -> BytesInput {
     BytesInput::new(bytes)
 }
 
-fn mismatch_regs(oracle: &[u32; 32], prover: &[u32; 32]) -> Vec<(u32, u32, u32)> {
+pub(crate) fn mismatch_regs(
+    oracle: &[u32; 32],
+    prover: &[u32; 32],
+    ignored: &[u8],
+) -> Vec<(u32, u32, u32)> {
     let mut out = Vec::new();
     for i in 0..32u32 {
+        if ignored.contains(&(i as u8)) {
+            continue;
+        }
         let a = oracle[i as usize];
         let b = prover[i as usize];
         if a != b {
@@ -238,6 +846,43 @@ fn panic_payload_to_string(p: &(dyn std::any::Any + Send)) -> String {
     "panic: non-string payload".to_string()
 }
 
+thread_local! {
+    /// Set on initial-eval worker threads, whose panics a `QuietPanicHook` already silences.
+    static QUIET_PANIC_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+type PanicHook = dyn Fn(&std::panic::PanicHookInfo<'_>) + Send + Sync;
+
+/// While alive, silences panics on `QUIET_PANIC_THREAD` threads and forwards all others to the
+/// hook it replaced; dropping it reinstalls that hook.
+struct QuietPanicHook {
+    prev: Arc<PanicHook>,
+}
+
+impl QuietPanicHook {
+    fn install() -> Self {
+        let prev: Arc<PanicHook> = Arc::from(std::panic::take_hook());
+        let forward = Arc::clone(&prev);
+        std::panic::set_hook(Box::new(move |info| {
+            if !QUIET_PANIC_THREAD.with(Cell::get) {
+                forward(info);
+            }
+        }));
+        Self { prev }
+    }
+}
+
+impl Drop for QuietPanicHook {
+    fn drop(&mut self) {
+        // The hook cannot be swapped from a panicking thread.
+        if std::thread::panicking() {
+            return;
+        }
+        let prev = Arc::clone(&self.prev);
+        std::panic::set_hook(Box::new(move |info| prev(info)));
+    }
+}
+
 /// Run a closure with a temporary non-fatal panic hook and catch unwind.
 ///
 /// This prevents libAFL's in-process panic hook from aborting the whole process
@@ -246,6 +891,10 @@ fn catch_unwind_nonfatal<T, F>(f: F) -> std::thread::Result<T>
 where
     F: FnOnce() -> T + std::panic::UnwindSafe,
 {
+    // Swapping the process-wide hook from several threads at once would race.
+    if QUIET_PANIC_THREAD.with(Cell::get) {
+        return std::panic::catch_unwind(f);
+    }
     let prev_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(|_panic_info| {}));
     let res = std::panic::catch_unwind(f);
@@ -257,9 +906,9 @@ where
 ///
 /// Contract:
 /// - Input must already be sorted canonically (by bucket id string).
-/// - Deduplicates while preserving the input order.
+/// - Deduplicates while preserving the input order, unless `sort_lexically` re-sorts the result.
 /// - Joins with ';'.
-fn canonical_bucket_sig(sigs: &[String]) -> String {
+pub(crate) fn canonical_bucket_sig(sigs: &[String], sort_lexically: bool) -> String {
     let mut seen = HashSet::<&str>::new();
     let mut out: Vec<&str> = Vec::new();
     for sig in sigs {
@@ -271,38 +920,172 @@ fn canonical_bucket_sig(sigs: &[String]) -> String {
             out.push(t);
         }
     }
+    if sort_lexically {
+        out.sort_unstable();
+    }
     out.join(";")
 }
 
+/// Deterministic Fisher-Yates shuffle driven by a dedicated RNG seeded with `rng_seed`, so the
+/// fuzzer state RNG sequence is unaffected.
+pub(crate) fn shuffle_seeds<T>(items: &mut [T], rng_seed: u64) {
+    let mut rand = StdRand::with_seed(rng_seed);
+    for i in (1..items.len()).rev() {
+        let j = rand.below(NonZeroUsize::new(i + 1).unwrap());
+        items.swap(i, j);
+    }
+}
+
+/// Up to `count` random programs accepted by `is_usable`, with lengths in `1..=max_len`, drawn
+/// from a dedicated RNG seeded with `rng_seed` so the fuzzer state RNG sequence is unaffected.
+fn random_bootstrap_seeds(
+    count: usize,
+    max_len: usize,
+    rng_seed: u64,
+    is_usable: &dyn Fn(&[u32]) -> bool,
+) -> Vec<BytesInput> {
+    let mut rand = StdRand::with_seed(rng_seed);
+    let mut out = Vec::new();
+    for _ in 0..count.saturating_mul(RANDOM_BOOTSTRAP_ATTEMPTS_PER_SEED) {
+        if out.len() >= count {
+            break;
+        }
+        let len = 1 + rand.below(NonZeroUsize::new(max_len.max(1)).unwrap());
+        let words = generate_random_program(len, &mut rand);
+        if is_usable(&words) {
+            out.push(encode_words(&words));
+        }
+    }
+    out
+}
+
 fn load_initial_seeds(
     path: &Path,
     max_instructions: usize,
+    dedup_alpha_equivalent: bool,
     is_usable: &dyn Fn(&[u32]) -> bool,
 ) -> Vec<(BytesInput, serde_json::Value)> {
     let f = File::open(path).expect("open initial seeds");
     let r = BufReader::new(f);
     let mut out = Vec::new();
-    for line in r.lines().flatten() {
+    let mut dropped = Vec::new();
+    let mut canonical_seen = HashSet::new();
+    let mut alpha_equivalent = 0usize;
+    for (idx, line) in r.lines().flatten().enumerate() {
         let s = line.trim();
         if s.is_empty() {
             continue;
         }
-        let seed: FuzzingSeed = serde_json::from_str(s).expect("parse seed jsonl");
-        let mut words = seed.instructions;
-        words.truncate(max_instructions);
-        if !is_usable(&words) {
+        let diagnosis = diagnose_seed_line(s, max_instructions, is_usable);
+        if let Some(rejection) = diagnosis.rejection {
+            dropped.push((idx + 1, rejection));
             continue;
         }
-        // Also filter out decode-invalid words (generic RISC-V sanity).
-        if words.iter().any(|w| RV32IMInstruction::from_word(*w).is_err()) {
+        let Some(seed) = diagnosis.seed else { continue };
+        if dedup_alpha_equivalent
+            && !canonical_seen.insert(canonicalize_program(&seed.instructions))
+        {
+            alpha_equivalent += 1;
             continue;
         }
-        out.push((encode_words(&words), serde_json::Value::Object(seed.metadata)));
+        out.push((encode_words(&seed.instructions), serde_json::Value::Object(seed.metadata)));
+    }
+    if alpha_equivalent > 0 {
+        eprintln!(
+            "[LOOP1] skipped {alpha_equivalent} seed(s) alpha-equivalent to an earlier seed in {}",
+            path.display()
+        );
+    }
+    if !dropped.is_empty() {
+        eprintln!(
+            "[LOOP1][WARN] dropped {} seed line(s) from {}: {}",
+            dropped.len(),
+            path.display(),
+            summarize_dropped_seeds(&dropped)
+        );
     }
     out
 }
 
-/// Feedback: keep inputs that yield a previously unseen bucket signature.
+/// Write `counts` as a JSON object sorted by bucket id.
+pub(crate) fn write_bucket_histogram(path: &Path, counts: &HashMap<String, u64>) -> Result<(), String> {
+    let sorted: BTreeMap<&str, u64> = counts.iter().map(|(id, n)| (id.as_str(), *n)).collect();
+    let json = serde_json::to_string_pretty(&sorted)
+        .map_err(|e| format!("serialize bucket histogram failed: {e}"))?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("write bucket histogram {} failed: {e}", path.display()))
+}
+
+/// Write the mutator bandit's per-arm statistics, with the mutator name of each arm.
+fn write_bandit_stats(path: &Path) -> Result<(), String> {
+    let arms: Vec<serde_json::Value> = bandit::snapshot()
+        .iter()
+        .enumerate()
+        .map(|(arm, stats)| {
+            serde_json::json!({
+                "arm": arm,
+                "mutator": SEED_MUTATOR_ARM_NAMES.get(arm),
+                "pulls": stats.pulls,
+                "total_reward": stats.total_reward,
+                "mean_reward": stats.mean_reward,
+            })
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&serde_json::json!({ "arms": arms }))
+        .map_err(|e| format!("serialize bandit stats failed: {e}"))?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("write bandit stats {} failed: {e}", path.display()))
+}
+
+/// Write the coverage reached by the initial seed evaluation, sorted for stable diffs.
+fn write_initial_coverage(
+    path: &Path,
+    feedback: &BucketNoveltyFeedback,
+    seeds: usize,
+    redundant_seeds: usize,
+) -> Result<(), String> {
+    let bucket_ids: BTreeSet<&str> = feedback.seen_bucket_ids.iter().map(String::as_str).collect();
+    let signatures: BTreeSet<&str> = feedback.seen.iter().map(String::as_str).collect();
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "seeds": seeds,
+        "redundant_seeds": redundant_seeds,
+        "bucket_ids": bucket_ids,
+        "signatures": signatures,
+    }))
+    .map_err(|e| format!("serialize initial coverage failed: {e}"))?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("write initial coverage {} failed: {e}", path.display()))
+}
+
+/// `interestingness_score` weight of a previously unseen bucket signature.
+const NEW_COMBO_SCORE: f64 = 1.0;
+/// `interestingness_score` weight of each bucket id hit for the first time.
+const NEW_BUCKET_ID_SCORE: f64 = 0.5;
+
+/// Corpus score of a run: `NEW_COMBO_SCORE` for a new signature, `NEW_BUCKET_ID_SCORE` per bucket
+/// id missing from `seen_bucket_ids`, plus `1 / (1 + runs)` for its rarest bucket id, where `runs`
+/// counts earlier runs hitting it in `histogram`. A new combination of common buckets thus scores
+/// little more than `NEW_COMBO_SCORE`.
+pub fn interestingness_score(
+    stats: &RunStats,
+    is_new_combo: bool,
+    seen_bucket_ids: &HashSet<String>,
+    histogram: &HashMap<String, u64>,
+) -> f64 {
+    let ids: HashSet<&str> = stats.bucket_hits.iter().map(|h| h.bucket_id.as_str()).collect();
+    let new_ids = ids.iter().filter(|id| !seen_bucket_ids.contains(**id)).count();
+    let rarest = ids
+        .iter()
+        .map(|id| histogram.get(*id).copied().unwrap_or(0))
+        .min()
+        .map_or(0.0, |runs| 1.0 / (1.0 + runs as f64));
+    (if is_new_combo { NEW_COMBO_SCORE } else { 0.0 })
+        + new_ids as f64 * NEW_BUCKET_ID_SCORE
+        + rarest
+}
+
+/// Feedback: keep inputs that yield a previously unseen bucket signature (or, with
+/// `interesting_threshold`, a high enough `interestingness_score`).
 struct BucketNoveltyFeedback {
     seen: HashSet<String>,
     seen_bucket_ids: HashSet<String>,
@@ -312,6 +1095,42 @@ struct BucketNoveltyFeedback {
     cfg: Loop1Config,
     name: std::borrow::Cow<'static, str>,
     written_bug_keys: HashSet<String>,
+    /// Runs per bucket id across the campaign (not only novel ones), for weight tuning.
+    bucket_hit_counts: HashMap<String, u64>,
+    known_bug_sigs: HashSet<String>,
+    /// Evaluations seen so far; the clock for `novelty_ttl_iters`.
+    evals: usize,
+    /// Evaluation at which each signature / bucket id was last hit.
+    sig_last_hit: HashMap<String, usize>,
+    bucket_id_last_hit: HashMap<String, usize>,
+    /// Bucket ids `corpus_freeze_coverage` is measured against.
+    coverage_universe: Vec<String>,
+    /// Set once `corpus_freeze_coverage` is reached; never cleared.
+    corpus_frozen: bool,
+}
+
+/// The ids in `ids` that are not in `known` (a `LoopBackend::bucket_ids` list), where a
+/// `{param}` entry matches every id of its family.
+fn unknown_bucket_ids<'a>(ids: &'a [String], known: &[String]) -> Vec<&'a str> {
+    ids.iter()
+        .map(String::as_str)
+        .filter(|id| {
+            !known.iter().any(|known| match known.split_once('{') {
+                Some((family, _)) => id.starts_with(family),
+                None => known == id,
+            })
+        })
+        .collect()
+}
+
+/// Bucket ids `corpus_freeze_coverage` is measured against: `required_buckets`, or by default
+/// every hand-registered bucket, leaving out the generated families.
+fn coverage_universe(cfg: &Loop1Config) -> Vec<String> {
+    if cfg.required_buckets.is_empty() {
+        semantic::ALL_BUCKETS.iter().map(|bucket| bucket.id.to_string()).collect()
+    } else {
+        cfg.required_buckets.clone()
+    }
 }
 
 impl BucketNoveltyFeedback {
@@ -320,6 +1139,7 @@ impl BucketNoveltyFeedback {
         bug_writer: JsonlWriter,
         run_writer: JsonlWriter,
         cfg: Loop1Config,
+        known_bug_sigs: HashSet<String>,
     ) -> Self {
         Self {
             seen: HashSet::new(),
@@ -327,13 +1147,68 @@ impl BucketNoveltyFeedback {
             corpus_writer,
             bug_writer,
             run_writer,
-            cfg,
             name: "BucketNoveltyFeedback".into(),
             written_bug_keys: HashSet::new(),
+            bucket_hit_counts: HashMap::new(),
+            known_bug_sigs,
+            evals: 0,
+            sig_last_hit: HashMap::new(),
+            bucket_id_last_hit: HashMap::new(),
+            coverage_universe: coverage_universe(&cfg),
+            corpus_frozen: false,
+            cfg,
+        }
+    }
+
+    /// Fraction of `coverage_universe` hit so far, counting ids `novelty_ttl_iters` forgot.
+    fn universe_coverage(&self) -> f64 {
+        if self.coverage_universe.is_empty() {
+            return 0.0;
+        }
+        let covered = self
+            .coverage_universe
+            .iter()
+            .filter(|id| {
+                self.seen_bucket_ids.contains(*id) || self.bucket_hit_counts.contains_key(*id)
+            })
+            .count();
+        covered as f64 / self.coverage_universe.len() as f64
+    }
+
+    /// Forget signatures and bucket ids not hit within the last `ttl` evaluations. Entries never
+    /// hit in this campaign (e.g. resumed ones) count as last hit at evaluation 0. The sweep runs
+    /// every `ttl / 8` evaluations, so an entry can outlive `ttl` by up to one sweep period.
+    fn evict_stale_novelty(&mut self, ttl: usize) {
+        if !self.evals.is_multiple_of(ttl.div_ceil(8).max(1)) {
+            return;
+        }
+        let Some(cutoff) = self.evals.checked_sub(ttl) else { return };
+        let (sigs_before, ids_before) = (self.seen.len(), self.seen_bucket_ids.len());
+        let sig_last_hit = &self.sig_last_hit;
+        self.seen.retain(|sig| sig_last_hit.get(sig).copied().unwrap_or(0) > cutoff);
+        let bucket_id_last_hit = &self.bucket_id_last_hit;
+        self.seen_bucket_ids.retain(|id| bucket_id_last_hit.get(id).copied().unwrap_or(0) > cutoff);
+        self.sig_last_hit.retain(|_, last| *last > cutoff);
+        self.bucket_id_last_hit.retain(|_, last| *last > cutoff);
+        let evicted = (sigs_before - self.seen.len(), ids_before - self.seen_bucket_ids.len());
+        if evicted != (0, 0) {
+            eprintln!(
+                "[LOOP1] novelty ttl: forgot {} signature(s) and {} bucket id(s) unseen for {ttl} evals",
+                evicted.0, evicted.1
+            );
         }
     }
 }
 
+/// Bandit reward for one run: a new bucket combination gets +1, plus weighted per-bucket novelty
+/// and the bonus for re-hitting a known bug signature.
+fn bandit_reward(is_new_combo: bool, new_bucket_id_count: usize, known_bug_bonus: f64) -> f64 {
+    const PER_BUCKET_REWARD: f64 = 0.25;
+    (if is_new_combo { 1.0 } else { 0.0 })
+        + (new_bucket_id_count as f64) * PER_BUCKET_REWARD
+        + known_bug_bonus
+}
+
 impl Named for BucketNoveltyFeedback {
     fn name(&self) -> &std::borrow::Cow<'static, str> {
         &self.name
@@ -345,13 +1220,23 @@ impl StateInitializer<LoopState> for BucketNoveltyFeedback {}
 impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
     fn is_interesting(
         &mut self,
-        _state: &mut LoopState,
+        state: &mut LoopState,
         _mgr: &mut EM,
         input: &BytesInput,
         _observers: &OT,
         _exit_kind: &ExitKind,
     ) -> Result<bool, Error> {
         let stats = LAST_RUN.lock().unwrap().clone();
+        self.evals += 1;
+        if let Some(ttl) = self.cfg.novelty_ttl_iters {
+            self.evict_stale_novelty(ttl);
+        }
+        let score = interestingness_score(
+            &stats,
+            !stats.bucket_hits_sig.is_empty() && !self.seen.contains(&stats.bucket_hits_sig),
+            &self.seen_bucket_ids,
+            &self.bucket_hit_counts,
+        );
 
         // Per-bucket novelty is computed independently of corpus signature novelty.
         // This will later serve as a finer-grained reward signal (vs. only new combinations).
@@ -361,16 +1246,48 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
                 new_bucket_id_count += 1;
             }
         }
+        let hit_ids: HashSet<&str> =
+            stats.bucket_hits.iter().map(|h| h.bucket_id.as_str()).collect();
+        for id in hit_ids {
+            *self.bucket_hit_counts.entry(id.to_string()).or_default() += 1;
+            self.bucket_id_last_hit.insert(id.to_string(), self.evals);
+        }
+        if !stats.bucket_hits_sig.is_empty() {
+            self.sig_last_hit.insert(stats.bucket_hits_sig.clone(), self.evals);
+        }
+        if let Some(threshold) = self.cfg.corpus_freeze_coverage.filter(|_| !self.corpus_frozen) {
+            let coverage = self.universe_coverage();
+            if coverage >= threshold {
+                self.corpus_frozen = true;
+                eprintln!(
+                    "[LOOP1] corpus frozen at eval {}: {:.1}% of {} bucket id(s) covered; \
+                     recording bugs only",
+                    self.evals,
+                    coverage * 100.0,
+                    self.coverage_universe.len()
+                );
+            }
+        }
 
         let underconstrained_candidate = stats.underconstrained_candidate;
         let baseline_mismatch = is_baseline_mismatch(&stats);
         let has_exception = !stats.injected_phase
             && (stats.timed_out || stats.backend_error.is_some() || stats.oracle_error.is_some());
-        let is_bug = baseline_mismatch || has_exception || underconstrained_candidate;
+        // A looping program has no final state to compare; the backend can only time out.
+        let is_bug = !stats.oracle_looped
+            && (baseline_mismatch || has_exception || underconstrained_candidate);
         if is_bug {
             let words = decode_words_from_input(input, 2048);
+            let csr_divergence = baseline_mismatch
+                && is_csr_semantics_divergence(
+                    &words,
+                    &stats.mismatch_regs,
+                    &stats.mismatch_memory,
+                );
             let kind = if has_exception {
                 "exception"
+            } else if csr_divergence {
+                "csr_semantics_divergence"
             } else if baseline_mismatch {
                 "mismatch"
             } else {
@@ -384,7 +1301,7 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
                 backend_err,
                 oracle_err,
                 stats.direct_injection_kind.clone().unwrap_or_else(|| "none".to_string()),
-                words.iter().map(|w| format!("{w:08x}")).collect::<Vec<_>>().join(",")
+                bug_dedup_program_key(self.cfg.dedup_bugs_by, &words, &stats.mismatch_regs)
             );
             if self.written_bug_keys.insert(bug_key) {
                 eprintln!(
@@ -396,7 +1313,8 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
                     stats.injected_phase,
                     stats.bucket_hits_sig
                 );
-                let rec = BugRecord {
+                let mut rec = BugRecord {
+                    schema_version: RECORD_SCHEMA_VERSION,
                     zkvm_commit: self.cfg.zkvm_commit.clone(),
                     rng_seed: self.cfg.rng_seed,
                     timeout_ms: self.cfg.timeout_ms,
@@ -412,9 +1330,15 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
                     } else {
                         Vec::new()
                     },
+                    mismatch_memory: if baseline_mismatch {
+                        stats.mismatch_memory.clone()
+                    } else {
+                        Vec::new()
+                    },
                     instructions: words,
                     metadata: serde_json::json!({
                         "kind": kind,
+                        "csr_semantics_divergence": csr_divergence,
                         "timed_out": stats.timed_out,
                         "injected_phase": stats.injected_phase,
                         "has_direct_injection_target": stats.has_direct_injection_target,
@@ -422,25 +1346,43 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
                         "target_buckets": stats.target_buckets,
                         "baseline_bucket_hits_sig": stats.baseline_bucket_hits_sig,
                         "underconstrained_candidate": underconstrained_candidate,
+                        "injection_caught": stats.injection_caught,
+                        "trace_truncated": stats.trace_truncated,
+                        "slow": stats.slow,
                     }),
                 };
-                self.bug_writer.append_json_line(&rec).map_err(|e| Error::unknown(e))?;
+                if self.cfg.include_disasm_in_bugs {
+                    rec.metadata["disasm"] =
+                        serde_json::json!(disassemble_words(&rec.instructions));
+                }
+                enrich_record_metadata(
+                    self.cfg.enrich_metadata.as_ref(),
+                    &rec.instructions,
+                    &stats,
+                    &mut rec.metadata,
+                );
+                self.bug_writer.append_record(&rec).map_err(|e| Error::unknown(e))?;
             }
         }
 
         let sig = stats.bucket_hits_sig.clone();
         let is_new_combo = !sig.is_empty() && self.seen.insert(sig.clone());
+        let interesting = !self.corpus_frozen
+            && match self.cfg.interesting_threshold {
+                Some(threshold) => score > threshold,
+                None => is_new_combo,
+            };
 
-        // Bandit reward: new combo gets +1, plus weighted per-bucket novelty.
-        const PER_BUCKET_REWARD: f64 = 0.25;
-        let reward = (if is_new_combo { 1.0 } else { 0.0 })
-            + (new_bucket_id_count as f64) * PER_BUCKET_REWARD;
+        let known_bug_bonus =
+            if self.known_bug_sigs.contains(&sig) { self.cfg.known_bug_reward } else { 0.0 };
+        let reward = bandit_reward(is_new_combo, new_bucket_id_count, known_bug_bonus);
         if let Some(arm_idx) = bandit::take_last_arm() {
             bandit::update(arm_idx, reward);
         }
 
         let words = decode_words_from_input(input, 2048);
         let run_rec = RunRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
             zkvm_commit: self.cfg.zkvm_commit.clone(),
             rng_seed: self.cfg.rng_seed,
             timeout_ms: self.cfg.timeout_ms,
@@ -456,24 +1398,38 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
             metadata: serde_json::json!({
                 "kind": "run",
                 "is_bug": is_bug,
-                "is_interesting": is_new_combo,
+                "is_interesting": interesting,
+                "corpus_frozen": self.corpus_frozen,
+                "interestingness_score": score,
                 "new_bucket_id_count": new_bucket_id_count,
                 "skip_reason": stats.skip_reason,
+                "oracle_looped": stats.oracle_looped,
+                "trace_truncated": stats.trace_truncated,
                 "injected_phase": stats.injected_phase,
                 "has_direct_injection_target": stats.has_direct_injection_target,
                 "direct_injection_kind": stats.direct_injection_kind,
                 "target_buckets": stats.target_buckets,
                 "baseline_bucket_hits_sig": stats.baseline_bucket_hits_sig,
                 "underconstrained_candidate": stats.underconstrained_candidate,
+                "slow": stats.slow,
             }),
         };
         self.run_writer.append_json_line(&run_rec).map_err(|e| Error::unknown(e))?;
 
-        if !is_new_combo {
+        // Occasionally keep an ordinary (non-novel) input as a baseline so mutators have a broader
+        // substrate than the strictly-novel corpus.
+        let keep_baseline = !interesting
+            && !self.corpus_frozen
+            && !sig.is_empty()
+            && self.cfg.keep_boring_rate > 0.0
+            && state.rand_mut().coinflip(self.cfg.keep_boring_rate.min(1.0));
+        if !interesting && !keep_baseline {
             return Ok(false);
         }
 
-        let rec = CorpusRecord {
+        let mnemonics = distinct_mnemonics(&words);
+        let mut rec = CorpusRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
             zkvm_commit: self.cfg.zkvm_commit.clone(),
             rng_seed: self.cfg.rng_seed,
             timeout_ms: self.cfg.timeout_ms,
@@ -483,7 +1439,7 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
             signal_sig: stats.signal_sig.clone(),
             instructions: words,
             metadata: serde_json::json!({
-                "kind": "interesting",
+                "kind": if interesting { "interesting" } else { "baseline" },
                 "new_bucket_id_count": new_bucket_id_count,
                 "injected_phase": stats.injected_phase,
                 "has_direct_injection_target": stats.has_direct_injection_target,
@@ -491,9 +1447,38 @@ impl<EM, OT> Feedback<EM, BytesInput, OT, LoopState> for BucketNoveltyFeedback {
                 "target_buckets": stats.target_buckets,
                 "baseline_bucket_hits_sig": stats.baseline_bucket_hits_sig,
                 "underconstrained_candidate": stats.underconstrained_candidate,
+                "slow": stats.slow,
+                "oracle_looped": stats.oracle_looped,
+                "mnemonics": mnemonics,
             }),
         };
-        self.corpus_writer.append_json_line(&rec).map_err(|e| Error::unknown(e))?;
+        enrich_record_metadata(
+            self.cfg.enrich_metadata.as_ref(),
+            &rec.instructions,
+            &stats,
+            &mut rec.metadata,
+        );
+        // Self-test of the instruction layer: kept words must survive decode -> reencode.
+        let reencode_mismatch = reencode_mismatches(&rec.instructions);
+        if !reencode_mismatch.is_empty() {
+            eprintln!(
+                "[LOOP1][WARN] eval_id={} {} kept word(s) do not reencode to themselves",
+                stats.eval_id,
+                reencode_mismatch.len()
+            );
+            if let Some(meta) = rec.metadata.as_object_mut() {
+                meta.insert(
+                    "reencode_mismatch".to_string(),
+                    reencode_mismatch
+                        .iter()
+                        .map(|(word, reencoded)| {
+                            serde_json::json!({ "word": word, "reencoded": reencoded })
+                        })
+                        .collect(),
+                );
+            }
+        }
+        self.corpus_writer.append_record(&rec).map_err(|e| Error::unknown(e))?;
         Ok(true)
     }
 }
@@ -550,26 +1535,319 @@ where
     handle.join().map_err(|_| "loop thread panicked".to_string())?
 }
 
+/// Like `run_loop1_threaded`, but `build_backend` is also used to build the
+/// `Loop1Config::initial_eval_workers` extra backends for the initial seed evaluation. Backends
+/// built by the factory must not share per-process state (e.g. they should drive worker
+/// processes), since they run concurrently.
+pub fn run_loop1_threaded_pool<B, F>(
+    cfg: Loop1Config,
+    build_backend: F,
+) -> Result<Loop1Outputs, String>
+where
+    B: LoopBackend,
+    F: Fn() -> B + Send + Sync + 'static,
+{
+    let stack = cfg.stack_size_bytes.max(16 * 1024 * 1024);
+    let handle = std::thread::Builder::new()
+        .name("beak-loop1".into())
+        .stack_size(stack)
+        .spawn(move || {
+            let mut backend = build_backend();
+            run_loop1_inner(cfg, &mut backend, Some(&build_backend)).map(|(outputs, _)| outputs)
+        })
+        .map_err(|e| format!("spawn loop thread failed: {e}"))?;
+    handle.join().map_err(|_| "loop thread panicked".to_string())?
+}
+
 pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loop1Outputs, String> {
-    std::fs::create_dir_all(&cfg.out_dir)
-        .map_err(|e| format!("create out_dir {} failed: {e}", cfg.out_dir.display()))?;
+    run_loop1_inner(cfg, &mut backend, None::<&fn() -> B>).map(|(outputs, _)| outputs)
+}
+
+/// Run `cfg` once per RNG seed in `seeds`, sequentially on one backend, for cross-seed statistics.
+///
+/// Each seed writes its usual files under the prefix `{base}-seed{seed}`; `{base}-aggregate.json`
+/// then records per-seed and union bucket coverage plus the total bug count. `base` is
+/// `cfg.output_prefix` or a generated `loop1-{tag}-{commit}-multi-{ts}`.
+pub fn run_loop1_multi<B, F>(
+    cfg: Loop1Config,
+    seeds: &[u64],
+    build_backend: F,
+) -> Result<Vec<Loop1Outputs>, String>
+where
+    B: LoopBackend,
+    F: FnOnce() -> B + Send + 'static,
+{
+    let seeds = seeds.to_vec();
+    let stack = cfg.stack_size_bytes.max(16 * 1024 * 1024);
+    let handle = std::thread::Builder::new()
+        .name("beak-loop1".into())
+        .stack_size(stack)
+        .spawn(move || {
+            let mut backend = build_backend();
+            run_loop1_multi_inner(cfg, &seeds, &mut backend)
+        })
+        .map_err(|e| format!("spawn loop thread failed: {e}"))?;
+    handle.join().map_err(|_| "loop thread panicked".to_string())?
+}
 
+fn run_loop1_multi_inner<B: LoopBackend>(
+    cfg: Loop1Config,
+    seeds: &[u64],
+    backend: &mut B,
+) -> Result<Vec<Loop1Outputs>, String> {
     let base_prefix = cfg.output_prefix.clone().unwrap_or_else(|| {
         format!(
-            "loop1-{}-{}-seed{}-{}",
+            "loop1-{}-{}-multi-{}",
             cfg.zkvm_tag,
             &cfg.zkvm_commit[..cfg.zkvm_commit.len().min(8)],
-            cfg.rng_seed,
             now_ts_secs()
         )
     });
-    let prefix = format!("{base_prefix}-iter{}", cfg.iters);
-    let corpus_path = cfg.out_dir.join(format!("{prefix}-corpus.jsonl"));
-    let bugs_path = cfg.out_dir.join(format!("{prefix}-bugs.jsonl"));
+    let mut outputs = Vec::with_capacity(seeds.len());
+    let mut per_seed = Vec::with_capacity(seeds.len());
+    let mut union_bucket_ids: BTreeSet<String> = BTreeSet::new();
+    let mut total_bugs = 0usize;
+    for &rng_seed in seeds {
+        eprintln!("[LOOP1] multi-seed run: rng_seed={rng_seed}");
+        let seed_cfg = Loop1Config {
+            rng_seed,
+            output_prefix: Some(format!("{base_prefix}-seed{rng_seed}")),
+            ..cfg.clone()
+        };
+        let (out, summary) = run_loop1_inner(seed_cfg, backend, None::<&fn() -> B>)?;
+        per_seed.push(serde_json::json!({
+            "rng_seed": rng_seed,
+            "distinct_bucket_ids": summary.bucket_ids.len(),
+            "bugs": summary.bugs,
+            "corpus_path": out.corpus_path,
+            "bugs_path": out.bugs_path,
+        }));
+        total_bugs += summary.bugs;
+        union_bucket_ids.extend(summary.bucket_ids);
+        outputs.push(out);
+    }
+
+    let aggregate_path = cfg.out_dir.join(format!("{base_prefix}-aggregate.json"));
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "seeds": seeds,
+        "per_seed": per_seed,
+        "union_distinct_bucket_ids": union_bucket_ids.len(),
+        "union_bucket_ids": union_bucket_ids,
+        "total_bugs": total_bugs,
+    }))
+    .map_err(|e| format!("serialize aggregate failed: {e}"))?;
+    std::fs::write(&aggregate_path, json)
+        .map_err(|e| format!("write aggregate {} failed: {e}", aggregate_path.display()))?;
+    eprintln!("[LOOP1] wrote multi-seed aggregate: {}", aggregate_path.display());
+    Ok(outputs)
+}
+
+/// Per-run coverage and bug totals, for aggregating several runs.
+struct Loop1RunSummary {
+    bucket_ids: HashSet<String>,
+    bugs: usize,
+}
+
+/// Whether the harness's input checks accept `words` (mirrors them, minus the oracle precheck).
+fn initial_input_runs_backend<B: LoopBackend>(
+    caps: &BackendCapabilities,
+    backend: &B,
+    words: &[u32],
+) -> bool {
+    caps.allows_program(words) && backend.is_usable_seed(words)
+}
+
+/// Outcome of the harness's oracle pre-run.
+enum OraclePrecheck {
+    /// Run the backend. Carries the pre-run when one happened, so `eval_once` can reuse it.
+    Run(Option<(OracleExecution, MemorySnapshot)>),
+    /// Skip the backend, recording this `skip_reason`.
+    Skip(&'static str, OracleExecution),
+}
+
+/// Oracle pre-run that lets the harness skip the backend. Runs only when the step-bound precheck
+/// or `skip_oracle_looped` is enabled, and only the latter turns on loop detection.
+fn oracle_precheck(cfg: &Loop1Config, words: &[u32]) -> OraclePrecheck {
+    let max_steps = match (cfg.precheck_oracle_max_steps, cfg.skip_oracle_looped) {
+        (0, false) => return OraclePrecheck::Run(None),
+        (0, true) => None,
+        (max_steps, _) => Some(max_steps),
+    };
+    let run = match max_steps {
+        Some(max_steps) => RISCVOracle::execute_detailed_with_step_limit(
+            words,
+            cfg.oracle,
+            max_steps,
+            cfg.skip_oracle_looped,
+        ),
+        None => RISCVOracle::execute_detailed(words, cfg.oracle),
+    };
+    match run.0.exit {
+        OracleExit::StepLimit if max_steps.is_some() => {
+            OraclePrecheck::Skip("oracle_precheck_step_limit", run.0)
+        }
+        OracleExit::Looped if cfg.skip_oracle_looped => {
+            OraclePrecheck::Skip("oracle_looped", run.0)
+        }
+        _ => OraclePrecheck::Run(Some(run)),
+    }
+}
+
+/// Evaluate baseline runs of `inputs` on `cfg.initial_eval_workers` freshly built backends.
+///
+/// Results arrive as `(index, stats)` in completion order; `None` marks an input the harness
+/// skips without running the backend. The caller keeps a `QuietPanicHook` alive until `scope`
+/// has joined the workers.
+fn spawn_initial_eval_workers<'scope, 'env, B, W>(
+    scope: &'scope std::thread::Scope<'scope, 'env>,
+    cfg: &'env Loop1Config,
+    caps: &'env BackendCapabilities,
+    timeout: Duration,
+    build_worker: &'env W,
+    inputs: &'env [BytesInput],
+) -> mpsc::Receiver<(usize, Option<RunStats>)>
+where
+    B: LoopBackend,
+    W: Fn() -> B + Sync,
+{
+    let (tx, rx) = mpsc::channel();
+    let next = Arc::new(AtomicUsize::new(0));
+    for worker in 0..cfg.initial_eval_workers {
+        let tx = tx.clone();
+        let next = next.clone();
+        let spawned = std::thread::Builder::new()
+            .name(format!("beak-loop1-initial-{worker}"))
+            .stack_size(cfg.stack_size_bytes.max(16 * 1024 * 1024))
+            .spawn_scoped(scope, move || {
+                QUIET_PANIC_THREAD.with(|quiet| quiet.set(true));
+                let mut backend = build_worker();
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(input) = inputs.get(idx) else { break };
+                    let words = decode_words_from_input(input, cfg.max_instructions);
+                    let stats = match initial_input_runs_backend(caps, &backend, &words)
+                        .then(|| oracle_precheck(cfg, &words))
+                    {
+                        Some(OraclePrecheck::Run(oracle_run)) => {
+                            Some(eval_once(cfg, caps, timeout, &mut backend, &words, oracle_run))
+                        }
+                        _ => None,
+                    };
+                    if tx.send((idx, stats)).is_err() {
+                        break;
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("[LOOP1][WARN] spawn initial eval worker {worker} failed: {e}");
+        }
+    }
+    rx
+}
+
+/// Re-add the corpus entries in `greedy_unique_bucket_order` of the initial seeds' bucket ids, so
+/// `QueueScheduler` visits the highest-contribution seeds first. Entries that are not initial
+/// seeds (e.g. ones `evaluate_input` re-added) and repeated seeds keep their relative order after
+/// the ordered seeds.
+fn reorder_corpus_by_unique_buckets(
+    state: &mut LoopState,
+    initial_inputs: &[BytesInput],
+    seed_bucket_ids: &[BTreeSet<String>],
+) -> Result<(), String> {
+    let order = greedy_unique_bucket_order(seed_bucket_ids);
+    let mut rank: HashMap<Vec<u8>, usize> = HashMap::new();
+    for (pos, &idx) in order.iter().enumerate() {
+        rank.entry(initial_inputs[idx].as_ref().to_vec()).or_insert(pos);
+    }
+
+    let ids: Vec<CorpusId> = state.corpus().ids().collect();
+    let mut entries = Vec::with_capacity(ids.len());
+    for id in ids {
+        let testcase = state
+            .corpus_mut()
+            .remove(id)
+            .map_err(|e| format!("remove corpus entry failed: {e}"))?;
+        entries.push(testcase);
+    }
+    // Only the first copy of each seed takes its rank; later copies sort with the non-seeds.
+    let mut ranked: Vec<(usize, Testcase<BytesInput>)> = entries
+        .into_iter()
+        .map(|tc| {
+            let key = tc.input().as_ref().and_then(|input| rank.remove(input.as_ref() as &[u8]));
+            (key.unwrap_or(usize::MAX), tc)
+        })
+        .collect();
+    ranked.sort_by_key(|(key, _)| *key);
+
+    let mut covered = BTreeSet::new();
+    let contributing = order
+        .iter()
+        .filter(|&&idx| {
+            let before = covered.len();
+            covered.extend(&seed_bucket_ids[idx]);
+            covered.len() > before
+        })
+        .count();
+    for (_, testcase) in ranked {
+        state.corpus_mut().add(testcase).map_err(|e| format!("re-add corpus entry failed: {e}"))?;
+    }
+    eprintln!(
+        "[LOOP1] scheduled {} of {} initial seeds by unique bucket contribution (first: {:?})",
+        contributing,
+        seed_bucket_ids.len(),
+        &order[..order.len().min(8)]
+    );
+    Ok(())
+}
+
+fn run_loop1_inner<B, W>(
+    cfg: Loop1Config,
+    backend: &mut B,
+    build_worker: Option<&W>,
+) -> Result<(Loop1Outputs, Loop1RunSummary), String>
+where
+    B: LoopBackend,
+    W: Fn() -> B + Sync,
+{
+    let mut cfg = cfg;
+    if let Some(threshold) = cfg.corpus_freeze_coverage.filter(|t| !(*t > 0.0 && *t <= 1.0)) {
+        return Err(format!("corpus_freeze_coverage must be in (0, 1], got {threshold}"));
+    }
+    let caps = backend.capabilities();
+    eprintln!("[LOOP1] backend capabilities: {}", caps.summary());
+    if let Some(max) = caps.max_program_len.filter(|max| *max < cfg.max_instructions) {
+        eprintln!(
+            "[LOOP1] clamping max_instructions {} -> {max} (backend limit)",
+            cfg.max_instructions
+        );
+        cfg.max_instructions = max;
+    }
+    std::fs::create_dir_all(&cfg.out_dir)
+        .map_err(|e| format!("create out_dir {} failed: {e}", cfg.out_dir.display()))?;
+
+    let base_prefix = cfg.output_prefix.clone().unwrap_or_else(|| {
+        format!(
+            "loop1-{}-{}-seed{}-{}",
+            cfg.zkvm_tag,
+            &cfg.zkvm_commit[..cfg.zkvm_commit.len().min(8)],
+            cfg.rng_seed,
+            now_ts_secs()
+        )
+    });
+    let prefix = format!("{base_prefix}-iter{}", cfg.iters);
+    let ext = cfg.output_format.extension();
+    let corpus_path = cfg.out_dir.join(format!("{prefix}-corpus.{ext}"));
+    let bugs_path = cfg.out_dir.join(format!("{prefix}-bugs.{ext}"));
     let runs_path = cfg.out_dir.join(format!("{prefix}-runs.jsonl"));
+    let bucket_histogram_path = cfg.out_dir.join(format!("{prefix}-bucket-histogram.json"));
+    let initial_coverage_path = cfg.out_dir.join(format!("{prefix}-initial-coverage.json"));
+    let bandit_stats_path = cfg.out_dir.join(format!("{prefix}-bandit-stats.json"));
 
-    let corpus_writer = JsonlWriter::open_append(&corpus_path)?;
-    let bug_writer = JsonlWriter::open_append(&bugs_path)?;
+    for id in unknown_bucket_ids(&cfg.required_buckets, &backend.bucket_ids()) {
+        eprintln!("[LOOP1][WARN] required bucket {id} is not a registered bucket id");
+    }
+    let corpus_writer = JsonlWriter::open_append_as(&corpus_path, cfg.output_format)?;
+    let bug_writer = JsonlWriter::open_append_as(&bugs_path, cfg.output_format)?;
     let run_writer = JsonlWriter::open_append(&runs_path)?;
 
     // --- libAFL setup ---
@@ -577,30 +1855,86 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
     let corpus = InMemoryCorpus::<BytesInput>::new();
     let solutions = InMemoryCorpus::<BytesInput>::new();
 
+    let known_bug_sigs = match cfg.known_bug_sigs_path.as_deref() {
+        Some(path) => load_bug_signatures(path)?,
+        None => HashSet::new(),
+    };
+    if !known_bug_sigs.is_empty() {
+        eprintln!("[LOOP1] loaded {} known bug signatures", known_bug_sigs.len());
+    }
     let mut feedback = BucketNoveltyFeedback::new(
         corpus_writer.clone(),
         bug_writer.clone(),
         run_writer.clone(),
         cfg.clone(),
+        known_bug_sigs,
     );
+    let resumed = match cfg.resume_from.as_deref() {
+        Some(path) => load_corpus_records(path)?,
+        None => Vec::new(),
+    };
+    for rec in resumed.iter().filter(|rec| !rec.bucket_hits_sig.is_empty()) {
+        feedback.seen_bucket_ids.extend(rec.bucket_hits_sig.split(';').map(str::to_string));
+        feedback.seen.insert(rec.bucket_hits_sig.clone());
+    }
+    let resumed_inputs: Vec<BytesInput> = resumed
+        .iter()
+        .filter(|rec| {
+            !rec.instructions.is_empty()
+                && rec.instructions.len() <= cfg.max_instructions
+                && caps.allows_program(&rec.instructions)
+                && backend.is_usable_seed(&rec.instructions)
+        })
+        .map(|rec| encode_words(&rec.instructions))
+        .collect();
     let mut objective = NeverObjective::new();
     let mut state: LoopState =
         StdState::new(rand, corpus, solutions, &mut feedback, &mut objective)
             .map_err(|e| format!("create state failed: {e}"))?;
 
     // Seed corpus with the initial JSONL.
-    for (input, _meta) in load_initial_seeds(&cfg.seeds_jsonl, cfg.max_instructions, &|words| {
-        backend.is_usable_seed(words)
-    })
-    .into_iter()
-    .take(if cfg.initial_limit == 0 { usize::MAX } else { cfg.initial_limit })
+    let mut seeds = if (cfg.random_bootstrap_seeds > 0 || !resumed_inputs.is_empty())
+        && !cfg.seeds_jsonl.exists()
+    {
+        Vec::new()
+    } else {
+        load_initial_seeds(
+            &cfg.seeds_jsonl,
+            cfg.max_instructions,
+            cfg.dedup_alpha_equivalent_seeds,
+            &|words| caps.allows_program(words) && backend.is_usable_seed(words),
+        )
+    };
+    if cfg.shuffle_initial_seeds {
+        shuffle_seeds(&mut seeds, cfg.rng_seed);
+    }
+    for (input, _meta) in
+        seeds.into_iter().take(if cfg.initial_limit == 0 { usize::MAX } else { cfg.initial_limit })
     {
         state
             .corpus_mut()
             .add(Testcase::new(input))
             .map_err(|e| format!("add initial seed failed: {e}"))?;
     }
-    if state.corpus().count() == 0 {
+    if state.corpus().count() == 0 && resumed_inputs.is_empty() && cfg.random_bootstrap_seeds > 0 {
+        let generated = random_bootstrap_seeds(
+            cfg.random_bootstrap_seeds,
+            cfg.max_instructions.min(RANDOM_BOOTSTRAP_MAX_LEN),
+            cfg.rng_seed,
+            &|words| caps.allows_program(words) && backend.is_usable_seed(words),
+        );
+        eprintln!(
+            "[LOOP1] no usable initial seeds; bootstrapped {} random program(s)",
+            generated.len()
+        );
+        for input in generated {
+            state
+                .corpus_mut()
+                .add(Testcase::new(input))
+                .map_err(|e| format!("add bootstrap seed failed: {e}"))?;
+        }
+    }
+    if state.corpus().count() == 0 && resumed_inputs.is_empty() {
         return Err(format!("No usable initial seeds loaded from {}", cfg.seeds_jsonl.display()));
     }
 
@@ -613,6 +1947,9 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
     let mut mgr = SimpleEventManager::new(monitor);
     let mut resolved_direct_buckets: HashSet<String> = HashSet::new();
     let mut eval_id_counter: u64 = 0;
+    let mut result_cache: ResultCache<RunStats> = ResultCache::new(cfg.result_cache_size);
+    // Baseline results computed ahead of time by the initial eval workers, keyed by input bytes.
+    let prefetched: RefCell<HashMap<Vec<u8>, RunStats>> = RefCell::new(HashMap::new());
 
     // Executor harness: run backend execution, collect trace/eval, and compare regs.
     let timeout = Duration::from_millis(cfg.timeout_ms);
@@ -620,9 +1957,7 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
         eval_id_counter = eval_id_counter.saturating_add(1);
         let eval_id = eval_id_counter;
         let words = decode_words_from_input(input, cfg.max_instructions);
-        if !backend.is_usable_seed(&words)
-            || words.iter().any(|w| RV32IMInstruction::from_word(*w).is_err())
-        {
+        if !caps.allows_program(&words) || !backend.is_usable_seed(&words) {
             let mut last = LAST_RUN.lock().unwrap();
             *last = RunStats {
                 eval_id,
@@ -631,15 +1966,11 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
             };
             return ExitKind::Ok;
         }
-        if cfg.precheck_oracle_max_steps > 0 {
-            let pre = RISCVOracle::execute_with_step_limit(
-                &words,
-                cfg.oracle,
-                cfg.precheck_oracle_max_steps,
-            );
-            if pre.hit_step_limit {
+        let oracle_run = match oracle_precheck(&cfg, &words) {
+            OraclePrecheck::Run(oracle_run) => oracle_run,
+            OraclePrecheck::Skip(reason, pre) => {
                 eprintln!(
-                    "[LOOP1][WARN] skip seed: oracle precheck hit step limit (steps={} limit={} words={})",
+                    "[LOOP1][WARN] skip seed: {reason} (steps={} limit={} words={})",
                     pre.steps,
                     cfg.precheck_oracle_max_steps,
                     words.len()
@@ -647,18 +1978,57 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
                 let mut last = LAST_RUN.lock().unwrap();
                 *last = RunStats {
                     eval_id,
-                    skip_reason: Some("oracle_precheck_step_limit".to_string()),
+                    skip_reason: Some(reason.to_string()),
+                    oracle_looped: pre.exit == OracleExit::Looped,
                     ..RunStats::default()
                 };
                 return ExitKind::Ok;
             }
-        }
+        };
 
         backend.clear_direct_injection();
-        let baseline = eval_once(&cfg, timeout, &mut backend, &words);
+        let mut run_timeout = timeout;
+        let prefetched_stats = prefetched.borrow_mut().remove(input.as_ref());
+        let cached =
+            if prefetched_stats.is_none() { result_cache.get(input.as_ref()) } else { None };
+        let is_cached = cached.is_some();
+        if is_cached {
+            eprintln!("[LOOP1] eval_id={eval_id} result cache hit; skipping baseline run");
+        }
+        let mut baseline = prefetched_stats.or(cached).unwrap_or_else(|| {
+            eval_once(&cfg, &caps, run_timeout, &mut *backend, &words, oracle_run.clone())
+        });
+        if let Some(multiplier) = cfg.timeout_retry_multiplier.filter(|m| *m > 1.0) {
+            if baseline.timed_out {
+                let retry_ms = (cfg.timeout_ms as f64 * multiplier) as u64;
+                eprintln!(
+                    "[LOOP1][WARN] eval_id={eval_id} timed out; retrying with timeout_ms={retry_ms}"
+                );
+                backend.set_timeout_ms(retry_ms);
+                let retry_timeout = Duration::from_millis(retry_ms);
+                let retry = eval_once(
+                    &cfg,
+                    &caps,
+                    retry_timeout,
+                    &mut *backend,
+                    &words,
+                    oracle_run.clone(),
+                );
+                if retry.timed_out {
+                    backend.set_timeout_ms(cfg.timeout_ms);
+                } else {
+                    // Keep the larger budget for the injected replays of this slow input.
+                    run_timeout = retry_timeout;
+                    baseline = RunStats { slow: true, ..retry };
+                }
+            }
+        }
+        if !is_cached && !baseline.timed_out && !baseline.slow {
+            result_cache.insert(input.as_ref().to_vec(), baseline.clone());
+        }
         let mut final_stats = baseline.clone();
 
-        if cfg.chain_direct_injection {
+        if cfg.chain_direct_injection && caps.witness_injection {
             // De-duplicate and deterministically order target buckets so replay order is stable.
             let mut target_buckets: Vec<String> = baseline
                 .bucket_hits
@@ -692,16 +2062,27 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
                         continue;
                     };
 
-                    let mut injected = eval_once(&cfg, timeout, &mut backend, &words);
+                    let mut injected = eval_once(
+                        &cfg,
+                        &caps,
+                        run_timeout,
+                        &mut *backend,
+                        &words,
+                        oracle_run.clone(),
+                    );
+                    injected.slow = baseline.slow;
                     injected.has_direct_injection_target = true;
                     injected.injected_phase = true;
                     injected.direct_injection_kind = Some(inject_kind);
                     injected.target_buckets = vec![bucket_id.clone()];
                     injected.baseline_bucket_hits_sig = Some(baseline.bucket_hits_sig.clone());
-                    injected.underconstrained_candidate = baseline.backend_error.is_none()
-                        && baseline.oracle_error.is_none()
-                        && injected.backend_error.is_none()
-                        && injected.oracle_error.is_none();
+                    // A fault the backend compared against a clean run and found silently
+                    // accepted is under-constrained whatever the oracle says.
+                    injected.underconstrained_candidate = injected.injection_caught == Some(false)
+                        || (baseline.backend_error.is_none()
+                            && baseline.oracle_error.is_none()
+                            && injected.backend_error.is_none()
+                            && injected.oracle_error.is_none());
 
                     if injected.underconstrained_candidate {
                         // Mark resolved only for true underconstrained signals.
@@ -737,6 +2118,9 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
             }
         }
         backend.clear_direct_injection();
+        if run_timeout != timeout {
+            backend.set_timeout_ms(cfg.timeout_ms);
+        }
         final_stats.eval_id = eval_id;
 
         let mut last = LAST_RUN.lock().unwrap();
@@ -764,19 +2148,116 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
     )
     .map_err(|e| format!("create executor failed: {e}"))?;
 
-    let mut stages = tuple_list!(StdMutationalStage::new(SeedMutator::new(cfg.max_instructions)));
+    let mut stages = tuple_list!(StdMutationalStage::new(
+        SeedMutator::new(cfg.max_instructions, cfg.min_instructions)
+            .with_supported_opcodes(caps.supported_opcodes.clone())
+            .with_undecodable_words(caps.undecodable_words)
+    ));
 
     let initial_count = state.corpus().count();
+    let mut initial_inputs = Vec::with_capacity(initial_count);
     for idx in 0..initial_count {
-        eprintln!("[LOOP1][initial {}/{}] evaluating seed corpus entry", idx + 1, initial_count);
-        let id = CorpusId::from(idx);
-        let Ok(tc_cell) = state.corpus().get(id) else { continue };
-        let tc = tc_cell.borrow();
-        let Some(input) = tc.input().as_ref().cloned() else { continue };
-        drop(tc);
-        let _ = fuzzer.evaluate_input(&mut state, &mut executor, &mut mgr, &input);
+        let Ok(tc_cell) = state.corpus().get(CorpusId::from(idx)) else { continue };
+        let Some(input) = tc_cell.borrow().input().as_ref().cloned() else { continue };
+        initial_inputs.push(input);
+    }
+    let build_worker = build_worker.filter(|_| cfg.initial_eval_workers > 0);
+    // One hook for the whole pool, installed before any worker exists and removed once the
+    // scope has joined them.
+    let quiet_panic_hook = build_worker.is_some().then(QuietPanicHook::install);
+    let (redundant_seeds, seed_bucket_ids) = std::thread::scope(|scope| {
+        let results = build_worker.map(|build| {
+            eprintln!(
+                "[LOOP1] evaluating {} initial seeds on {} workers",
+                initial_inputs.len(),
+                cfg.initial_eval_workers
+            );
+            spawn_initial_eval_workers(scope, &cfg, &caps, timeout, build, &initial_inputs)
+        });
+        // Feed the feedback strictly in corpus order, waiting for out-of-order worker results.
+        let mut ready = vec![false; initial_inputs.len()];
+        let mut redundant_seeds = 0usize;
+        let mut seed_bucket_ids = Vec::with_capacity(initial_inputs.len());
+        for (idx, input) in initial_inputs.iter().enumerate() {
+            if let Some(results) = results.as_ref() {
+                while !ready[idx] {
+                    // A closed channel means every worker exited; fall back to the harness.
+                    let Ok((done, stats)) = results.recv() else { break };
+                    ready[done] = true;
+                    if let Some(stats) = stats {
+                        prefetched
+                            .borrow_mut()
+                            .insert(initial_inputs[done].as_ref().to_vec(), stats);
+                    }
+                }
+            }
+            eprintln!(
+                "[LOOP1][initial {}/{}] evaluating seed corpus entry",
+                idx + 1,
+                initial_count
+            );
+            let seen_before =
+                (fuzzer.feedback().seen.len(), fuzzer.feedback().seen_bucket_ids.len());
+            let _ = fuzzer.evaluate_input(&mut state, &mut executor, &mut mgr, input);
+            if (fuzzer.feedback().seen.len(), fuzzer.feedback().seen_bucket_ids.len())
+                == seen_before
+            {
+                redundant_seeds += 1;
+            }
+            if cfg.schedule_seeds_by_unique_buckets {
+                let sig = LAST_RUN.lock().unwrap().bucket_hits_sig.clone();
+                seed_bucket_ids.push(
+                    sig.split(';')
+                        .filter(|id| !id.is_empty())
+                        .map(str::to_string)
+                        .collect::<BTreeSet<_>>(),
+                );
+            }
+        }
+        (redundant_seeds, seed_bucket_ids)
+    });
+    drop(quiet_panic_hook);
+    prefetched.borrow_mut().clear();
+    if !initial_inputs.is_empty() {
+        eprintln!(
+            "[LOOP1] initial coverage: {} distinct bucket ids, {} signatures from {} seeds ({} added nothing new)",
+            fuzzer.feedback().seen_bucket_ids.len(),
+            fuzzer.feedback().seen.len(),
+            initial_inputs.len(),
+            redundant_seeds
+        );
+        if cfg.initial_coverage_report {
+            write_initial_coverage(
+                &initial_coverage_path,
+                fuzzer.feedback(),
+                initial_inputs.len(),
+                redundant_seeds,
+            )?;
+            eprintln!("[LOOP1] wrote initial coverage: {}", initial_coverage_path.display());
+        }
+    }
+    if cfg.schedule_seeds_by_unique_buckets && !seed_bucket_ids.is_empty() {
+        reorder_corpus_by_unique_buckets(&mut state, &initial_inputs, &seed_bucket_ids)?;
     }
 
+    if let Some(path) = cfg.resume_from.as_deref() {
+        // Added after the initial evaluation: the earlier campaign already ran these programs.
+        for input in &resumed_inputs {
+            state
+                .corpus_mut()
+                .add(Testcase::new(input.clone()))
+                .map_err(|e| format!("add resumed corpus entry failed: {e}"))?;
+        }
+        eprintln!(
+            "[LOOP1] resumed {} of {} corpus entries from {}",
+            resumed_inputs.len(),
+            resumed.len(),
+            path.display()
+        );
+    }
+
+    let mut last_seen_bucket_ids = fuzzer.feedback().seen_bucket_ids.len();
+    let mut last_progress_iter = 0usize;
     for i in 0..cfg.iters {
         fuzzer
             .fuzz_one(&mut stages, &mut executor, &mut state, &mut mgr)
@@ -805,11 +2286,820 @@ pub fn run_loop1<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loo
             s.timed_out,
             s.bucket_hits_sig
         );
+
+        let seen_bucket_ids = fuzzer.feedback().seen_bucket_ids.len();
+        if seen_bucket_ids > last_seen_bucket_ids {
+            last_seen_bucket_ids = seen_bucket_ids;
+            last_progress_iter = i;
+        } else if cfg.plateau_window > 0 && i - last_progress_iter >= cfg.plateau_window {
+            eprintln!(
+                "[LOOP1][PLATEAU] no new bucket id for {} iters (distinct={})",
+                i - last_progress_iter,
+                seen_bucket_ids
+            );
+            if cfg.stop_on_plateau {
+                break;
+            }
+            bandit::boost_exploration();
+            last_progress_iter = i;
+        }
     }
 
     corpus_writer.flush()?;
     bug_writer.flush()?;
     run_writer.flush()?;
+    write_bucket_histogram(&bucket_histogram_path, &fuzzer.feedback().bucket_hit_counts)?;
+    write_bandit_stats(&bandit_stats_path)?;
+
+    // With `novelty_ttl_iters`, `seen_bucket_ids` may have forgotten ids the campaign did reach.
+    let feedback = fuzzer.feedback();
+    let summary = Loop1RunSummary {
+        bucket_ids: feedback
+            .seen_bucket_ids
+            .iter()
+            .chain(feedback.bucket_hit_counts.keys())
+            .cloned()
+            .collect(),
+        bugs: feedback.written_bug_keys.len(),
+    };
+    let missing_required: Vec<&str> = cfg
+        .required_buckets
+        .iter()
+        .filter(|id| !summary.bucket_ids.contains(*id))
+        .map(String::as_str)
+        .collect();
+    if !missing_required.is_empty() {
+        return Err(format!(
+            "{} of {} required bucket(s) never reached: {}",
+            missing_required.len(),
+            cfg.required_buckets.len(),
+            missing_required.join(", ")
+        ));
+    }
+    Ok((
+        Loop1Outputs {
+            corpus_path,
+            bugs_path,
+            runs_path: Some(runs_path),
+            bucket_histogram_path: Some(bucket_histogram_path),
+            bandit_stats_path: Some(bandit_stats_path),
+        },
+        summary,
+    ))
+}
+
+/// Minimal config for loop tests: reads `{dir}/seeds.jsonl`, writes under `dir`, runs no
+/// mutation iterations.
+#[cfg(test)]
+pub(crate) fn test_config(dir: &Path, output_prefix: &str) -> Loop1Config {
+    Loop1Config {
+        zkvm_tag: "mock".to_string(),
+        zkvm_commit: "0000000000".to_string(),
+        rng_seed: 7,
+        timeout_ms: 60_000,
+        oracle: OracleConfig::default(),
+        ignored_regs: None,
+        seeds_jsonl: dir.join("seeds.jsonl"),
+        out_dir: dir.to_path_buf(),
+        output_prefix: Some(output_prefix.to_string()),
+        output_format: OutputFormat::Jsonl,
+        initial_limit: 0,
+        shuffle_initial_seeds: false,
+        dedup_alpha_equivalent_seeds: false,
+        random_bootstrap_seeds: 0,
+        resume_from: None,
+        max_instructions: 16,
+        min_instructions: 0,
+        iters: 0,
+        chain_direct_injection: false,
+        precheck_oracle_max_steps: 0,
+        skip_oracle_looped: false,
+        keep_boring_rate: 0.0,
+        corpus_freeze_coverage: None,
+        interesting_threshold: None,
+        sort_signature_lexically: false,
+        enrich_metadata: None,
+        timeout_retry_multiplier: None,
+        plateau_window: 0,
+        stop_on_plateau: false,
+        novelty_ttl_iters: None,
+        result_cache_size: 0,
+        initial_eval_workers: 0,
+        initial_coverage_report: false,
+        schedule_seeds_by_unique_buckets: false,
+        known_bug_sigs_path: None,
+        known_bug_reward: 0.0,
+        dedup_bugs_by: BugDedupKey::default(),
+        include_disasm_in_bugs: false,
+        required_buckets: Vec::new(),
+        witness_flips_per_seed: 0,
+        stack_size_bytes: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::{
+        bandit_reward, bug_dedup_program_key, coverage_universe, disassemble_words, eval_once,
+        evaluate_single, interestingness_score, mismatch_regs, parse_program_text, run_loop1,
+        run_loop1_multi, shuffle_seeds, strip_dead_instructions, test_config, unknown_bucket_ids,
+        wait_for_request, BackendCapabilities, BackendEval, BucketNoveltyFeedback, BugDedupKey,
+        Loop1Config, LoopBackend, QuietPanicHook, RequestHandle, RunStats, DEFAULT_IGNORED_REGS,
+        QUIET_PANIC_THREAD,
+    };
+    use crate::fuzz::jsonl::{load_bug_signatures, JsonlWriter};
+    use crate::fuzz::mutators::SEED_MUTATOR_ARM_NAMES;
+    use crate::rv32im::instruction::RV32IMInstruction;
+    use crate::rv32im::oracle::{OracleConfig, RISCVOracle};
+    use crate::rv32im::supported::SupportedOpcodes;
+    use crate::trace::{semantic, BucketHit};
+
+    #[test]
+    fn program_text_only_treats_word_shaped_tokens_as_hex() {
+        let add = RV32IMInstruction::from_asm("add x10, x11, x12").unwrap().word;
+        let text = "0x00500093, 00500093\nadd x10, x11, x12\n";
+        assert_eq!(parse_program_text(text).unwrap(), vec![0x0050_0093, 0x0050_0093, add]);
+        // Every token here is valid hex, but none is word-shaped: it must reach the assembler
+        // rather than silently become four words.
+        let err = parse_program_text("add a0, a1, a2").unwrap_err();
+        assert!(err.starts_with("line 1: cannot parse"), "{err}");
+    }
+
+    #[test]
+    fn default_ignored_regs_skip_only_x0() {
+        let oracle = [0u32; 32];
+        let mut prover = [0u32; 32];
+        prover[0] = 7;
+        prover[5] = 1;
+        assert_eq!(mismatch_regs(&oracle, &prover, DEFAULT_IGNORED_REGS), vec![(5, 0, 1)]);
+        assert_eq!(mismatch_regs(&oracle, &prover, &[]), vec![(0, 0, 7), (5, 0, 1)]);
+    }
+
+    #[test]
+    fn minimized_dedup_key_keeps_only_what_reaches_the_mismatch() {
+        let program = |text: &str| parse_program_text(text).unwrap();
+        let core = program("addi x5, x0, 2\nadd x6, x5, x5");
+        let padded = program(
+            "addi x7, x0, 9\naddi x5, x0, 1\naddi x5, x0, 2\nadd x6, x5, x5\naddi x0, x0, 0",
+        );
+        assert_eq!(strip_dead_instructions(&padded, 1 << 6), core);
+        let key = |words: &[u32]| {
+            bug_dedup_program_key(BugDedupKey::MinimizedPlusRegs, words, &[(6, 4, 5)])
+        };
+        assert_eq!(key(&padded), key(&core));
+
+        // A store reads x7; a branch may reach anything, so everything before it stays.
+        let stored = program("addi x7, x0, 9\nsw x7, 0(x0)\naddi x6, x0, 4");
+        assert_eq!(strip_dead_instructions(&stored, 1 << 6), stored);
+        let branchy = program("addi x7, x0, 9\nbeq x0, x0, 8\naddi x6, x0, 4\naddi x8, x0, 1");
+        assert_eq!(strip_dead_instructions(&branchy, 1 << 6), branchy[..3]);
+    }
+
+    /// Records the memory probe; reports no capabilities.
+    #[derive(Default)]
+    struct ProbeBackend {
+        probed: Option<Vec<u32>>,
+    }
+
+    impl LoopBackend for ProbeBackend {
+        fn set_memory_probe(&mut self, addrs: &[u32]) {
+            self.probed = Some(addrs.to_vec());
+        }
 
-    Ok(Loop1Outputs { corpus_path, bugs_path, runs_path: Some(runs_path) })
+        fn prove_and_read_final_regs(&mut self, _words: &[u32]) -> Result<[u32; 32], String> {
+            Ok([0; 32])
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval::default()
+        }
+    }
+
+    /// `ProbeBackend` that opts into memory readback.
+    #[derive(Default)]
+    struct ReadbackBackend(ProbeBackend);
+
+    impl LoopBackend for ReadbackBackend {
+        fn capabilities(&self) -> BackendCapabilities {
+            BackendCapabilities { memory_readback: true, ..Default::default() }
+        }
+
+        fn set_memory_probe(&mut self, addrs: &[u32]) {
+            self.0.set_memory_probe(addrs);
+        }
+
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            self.0.prove_and_read_final_regs(words)
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            self.0.collect_eval()
+        }
+    }
+
+    #[test]
+    fn undecodable_words_run_only_on_backends_that_opt_in() {
+        let add = parse_program_text("add x1, x2, x3").unwrap()[0];
+        // add with funct7 = 0b0000010, which no RV32IM instruction uses.
+        let program = [add, add | (1 << 26)];
+        assert!(RV32IMInstruction::from_word(program[1]).is_err());
+        assert!(!BackendCapabilities::default().allows_program(&program));
+        let caps = BackendCapabilities { undecodable_words: true, ..Default::default() };
+        assert!(caps.allows_program(&program));
+        let no_alu = BackendCapabilities {
+            supported_opcodes: SupportedOpcodes::parse("", "add").unwrap(),
+            ..caps
+        };
+        assert!(!no_alu.allows_program(&program));
+    }
+
+    #[test]
+    fn backends_without_readback_get_no_memory_probe() {
+        let words = parse_program_text("addi x1, x0, 5\nsw x1, 0(x0)").unwrap();
+        let caps = ProbeBackend::default().capabilities();
+        assert!(!caps.memory_readback && !caps.witness_injection);
+
+        let mut plain = ProbeBackend::default();
+        evaluate_single(&words, &mut plain, OracleConfig::default());
+        assert_eq!(plain.probed, None);
+
+        let mut readback = ReadbackBackend::default();
+        evaluate_single(&words, &mut readback, OracleConfig::default());
+        assert_eq!(readback.0.probed, Some(vec![0, 1, 2, 3]));
+    }
+
+    /// Times out unless its budget is at least 100 ms; logs every budget it is given.
+    struct SlowBackend {
+        timeout_ms: u64,
+        timed_out: bool,
+        budgets: Rc<RefCell<Vec<u64>>>,
+    }
+
+    impl LoopBackend for SlowBackend {
+        fn set_timeout_ms(&mut self, timeout_ms: u64) {
+            self.timeout_ms = timeout_ms;
+            self.budgets.borrow_mut().push(timeout_ms);
+        }
+
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            self.timed_out = self.timeout_ms < 100;
+            if self.timed_out {
+                return Err(format!("timed out after {} ms", self.timeout_ms));
+            }
+            Ok(RISCVOracle::execute(words))
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval {
+                backend_error: self.timed_out.then(|| "backend timed out".to_string()),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn timeout_retry_runs_with_the_larger_budget_and_restores_it() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-retry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, 5`
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+        let cfg = Loop1Config {
+            timeout_ms: 50,
+            timeout_retry_multiplier: Some(4.0),
+            ..test_config(&dir, "retry")
+        };
+        let budgets = Rc::new(RefCell::new(Vec::new()));
+        let backend =
+            SlowBackend { timeout_ms: 50, timed_out: false, budgets: Rc::clone(&budgets) };
+
+        let out = run_loop1(cfg, backend).unwrap();
+        let runs = std::fs::read_to_string(out.runs_path.unwrap()).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(*budgets.borrow(), vec![200, 50]);
+        let record: serde_json::Value = serde_json::from_str(runs.lines().next().unwrap()).unwrap();
+        assert_eq!(record["metadata"]["slow"], serde_json::json!(true));
+        assert_eq!(record["timed_out"], serde_json::json!(false));
+    }
+
+    /// Sleeps through its first warm-up, then fails every later one; runs agree with the oracle.
+    #[derive(Default)]
+    struct ColdStartBackend {
+        warm_ups: usize,
+    }
+
+    impl LoopBackend for ColdStartBackend {
+        fn warm_up(&mut self) -> Result<(), String> {
+            self.warm_ups += 1;
+            if self.warm_ups > 1 {
+                return Err("worker already gone".to_string());
+            }
+            std::thread::sleep(Duration::from_millis(50));
+            Ok(())
+        }
+
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            Ok(RISCVOracle::execute(words))
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval::default()
+        }
+    }
+
+    #[test]
+    fn warm_up_runs_before_the_timeout_starts_and_its_failure_is_not_fatal() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-warm-{}", std::process::id()));
+        let cfg = test_config(&dir, "warm");
+        let words = parse_program_text("addi x1, x0, 5").unwrap();
+        let mut backend = ColdStartBackend::default();
+        let caps = backend.capabilities();
+        let timeout = Duration::from_millis(20);
+
+        let cold = eval_once(&cfg, &caps, timeout, &mut backend, &words, None);
+        assert!(!cold.timed_out);
+        let failed = eval_once(&cfg, &caps, timeout, &mut backend, &words, None);
+        assert_eq!(backend.warm_ups, 2);
+        assert!(failed.mismatch_regs.is_empty() && failed.backend_error.is_none());
+    }
+
+    /// Hits one bucket with a direct injection mapping; the armed fault leaves the outcome
+    /// unchanged, so the backend reports it as not caught.
+    #[derive(Default)]
+    struct AcceptingBackend {
+        armed: bool,
+    }
+
+    impl LoopBackend for AcceptingBackend {
+        fn capabilities(&self) -> BackendCapabilities {
+            BackendCapabilities { witness_injection: true, ..Default::default() }
+        }
+
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            Ok(RISCVOracle::execute(words))
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval {
+                bucket_hits: vec![BucketHit {
+                    bucket_id: "mock.injectable".to_string(),
+                    details: Default::default(),
+                }],
+                semantic_injection_applied: self.armed,
+                injection_caught: self.armed.then_some(false),
+                ..Default::default()
+            }
+        }
+
+        fn bucket_has_direct_injection(&self, bucket_id: &str) -> bool {
+            bucket_id == "mock.injectable"
+        }
+
+        fn clear_direct_injection(&mut self) {
+            self.armed = false;
+        }
+
+        fn arm_direct_injection_from_hits(&mut self, _hits: &[BucketHit]) -> Option<String> {
+            self.armed = true;
+            Some("mock.fault".to_string())
+        }
+    }
+
+    #[test]
+    fn uncaught_injection_is_written_as_a_bug() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-uncaught-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, 5`
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+        let cfg = Loop1Config { chain_direct_injection: true, ..test_config(&dir, "uncaught") };
+
+        let out = run_loop1(cfg, AcceptingBackend::default()).unwrap();
+        let bugs = std::fs::read_to_string(&out.bugs_path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let bug: serde_json::Value = serde_json::from_str(bugs.lines().next().unwrap()).unwrap();
+        assert_eq!(bug["metadata"]["kind"], serde_json::json!("underconstrained_candidate"));
+        assert_eq!(bug["metadata"]["injection_caught"], serde_json::json!(false));
+        assert_eq!(bug["metadata"]["direct_injection_kind"], serde_json::json!("mock.fault"));
+    }
+
+    #[test]
+    fn bug_records_carry_the_disassembly_when_asked() {
+        assert_eq!(
+            disassemble_words(&[0x0050_0093, 0xffff_ffff]),
+            vec!["addi x1, x0, 5".to_string(), ".word 0xffffffff".to_string()]
+        );
+
+        let dir = std::env::temp_dir().join(format!("beak-loop1-disasm-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+        let cfg = Loop1Config {
+            chain_direct_injection: true,
+            include_disasm_in_bugs: true,
+            ..test_config(&dir, "disasm")
+        };
+
+        let out = run_loop1(cfg, AcceptingBackend::default()).unwrap();
+        let bugs = std::fs::read_to_string(&out.bugs_path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let bug: serde_json::Value = serde_json::from_str(bugs.lines().next().unwrap()).unwrap();
+        assert_eq!(bug["metadata"]["disasm"], serde_json::json!(["addi x1, x0, 5"]));
+    }
+
+    /// Agrees with the oracle and hits `buckets[imm]` for a program whose first word is
+    /// `addi x1, x0, imm`.
+    struct ImmBucketBackend {
+        buckets: Vec<semantic::SemanticBucket>,
+        imm: usize,
+    }
+
+    impl LoopBackend for ImmBucketBackend {
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            self.imm = (words[0] >> 20) as usize;
+            Ok(RISCVOracle::execute(words))
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            let bucket = self.buckets[self.imm];
+            BackendEval {
+                bucket_hits: vec![BucketHit::semantic(bucket, Default::default())],
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn quiet_panic_hook_only_lasts_as_long_as_its_guard() {
+        static PROBES: AtomicUsize = AtomicUsize::new(0);
+        const PROBE: &str = "quiet panic hook probe";
+        let panic_on_thread = |quiet: bool| {
+            let worker = std::thread::spawn(move || {
+                QUIET_PANIC_THREAD.with(|q| q.set(quiet));
+                std::panic::panic_any(PROBE);
+            });
+            assert!(worker.join().is_err());
+            PROBES.load(Ordering::SeqCst)
+        };
+        let original = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|info| {
+            if info.payload().downcast_ref::<&str>() == Some(&PROBE) {
+                PROBES.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+
+        let guard = QuietPanicHook::install();
+        assert_eq!(panic_on_thread(true), 0);
+        assert_eq!(panic_on_thread(false), 1);
+        drop(guard);
+        assert_eq!(panic_on_thread(true), 2);
+
+        std::panic::set_hook(original);
+    }
+
+    #[test]
+    fn required_buckets_are_checked_against_the_backend_bucket_list() {
+        let required: Vec<String> = [
+            "openvm.divrem.div_by_zero",
+            semantic::decode::LUI_ADDI_FUSION_SEEN.id,
+            "sem.interaction.kind_seen.not_a_modeled_kind",
+            "sem.no_such_bucket",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        let registry = AcceptingBackend { armed: false }.bucket_ids();
+        assert_eq!(
+            unknown_bucket_ids(&required, &registry),
+            vec!["openvm.divrem.div_by_zero", "sem.no_such_bucket"]
+        );
+        let mut backend_ids = registry;
+        backend_ids.push("openvm.divrem.div_by_zero".to_string());
+        assert_eq!(unknown_bucket_ids(&required, &backend_ids), vec!["sem.no_such_bucket"]);
+    }
+
+    #[test]
+    fn initial_coverage_report_counts_seeds_that_add_nothing_new() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-coverage-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, imm`; the third seed repeats the first one's bucket.
+        let seeds: String = [0u32, 1, 0]
+            .iter()
+            .map(|imm| serde_json::json!({ "instructions": [(imm << 20) | 0x93], "metadata": {} }))
+            .map(|seed| format!("{seed}\n"))
+            .collect();
+        std::fs::write(dir.join("seeds.jsonl"), seeds).unwrap();
+        let buckets = vec![semantic::program::LEN_1, semantic::program::LEN_2_4];
+        let cfg = Loop1Config { initial_coverage_report: true, ..test_config(&dir, "coverage") };
+
+        run_loop1(cfg, ImmBucketBackend { buckets: buckets.clone(), imm: 0 }).unwrap();
+        let report = dir.join("coverage-iter0-initial-coverage.json");
+        let report = std::fs::read_to_string(report).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(report["seeds"], 3);
+        assert_eq!(report["redundant_seeds"], 1);
+        for bucket in &buckets {
+            assert!(report["bucket_ids"].as_array().unwrap().contains(&bucket.id.into()));
+        }
+    }
+
+    #[test]
+    fn multi_seed_runs_write_one_prefix_per_seed_and_a_union_aggregate() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-multi-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, 0` and `addi x1, x0, 1`, hitting one bucket each.
+        let seeds: String = (0..2u32)
+            .map(|imm| serde_json::json!({ "instructions": [(imm << 20) | 0x93], "metadata": {} }))
+            .map(|seed| format!("{seed}\n"))
+            .collect();
+        std::fs::write(dir.join("seeds.jsonl"), seeds).unwrap();
+        let buckets = vec![semantic::program::LEN_1, semantic::program::LEN_2_4];
+        let backend_buckets = buckets.clone();
+
+        let outputs = run_loop1_multi(test_config(&dir, "multi"), &[3, 4], move || {
+            ImmBucketBackend { buckets: backend_buckets, imm: 0 }
+        })
+        .unwrap();
+        let aggregate = std::fs::read_to_string(dir.join("multi-aggregate.json")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(outputs.len(), 2);
+        assert!(outputs[0].corpus_path.to_string_lossy().contains("multi-seed3"));
+        assert!(outputs[1].corpus_path.to_string_lossy().contains("multi-seed4"));
+        let aggregate: serde_json::Value = serde_json::from_str(&aggregate).unwrap();
+        assert_eq!(aggregate["seeds"], serde_json::json!([3, 4]));
+        assert_eq!(aggregate["per_seed"][1]["rng_seed"], 4);
+        assert_eq!(aggregate["total_bugs"], 0);
+        let union = aggregate["union_bucket_ids"].as_array().unwrap();
+        for bucket in &buckets {
+            assert!(union.contains(&bucket.id.into()));
+        }
+    }
+
+    #[test]
+    fn interestingness_rewards_new_ids_and_rare_buckets() {
+        let hit = |id: &str| BucketHit { bucket_id: id.to_string(), details: Default::default() };
+        let stats =
+            RunStats { bucket_hits: vec![hit("a"), hit("b"), hit("b")], ..Default::default() };
+        let seen: HashSet<String> = ["a".to_string()].into();
+        let histogram: HashMap<String, u64> = [("a".to_string(), 9), ("b".to_string(), 1)].into();
+
+        // One new id ("b"), whose single earlier run makes it the rarest bucket.
+        assert_eq!(interestingness_score(&stats, false, &seen, &histogram), 0.5 + 0.5);
+        assert_eq!(interestingness_score(&stats, true, &seen, &histogram), 1.0 + 0.5 + 0.5);
+        let common: HashMap<String, u64> = [("a".to_string(), 9), ("b".to_string(), 9)].into();
+        let all_seen: HashSet<String> = ["a".to_string(), "b".to_string()].into();
+        assert_eq!(interestingness_score(&stats, true, &all_seen, &common), 1.0 + 0.1);
+        assert_eq!(interestingness_score(&RunStats::default(), false, &seen, &histogram), 0.0);
+    }
+
+    #[test]
+    fn resumed_corpus_coverage_is_not_new_again() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-resume-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, imm` for each imm, hitting `buckets[imm]`.
+        let write_seeds = |imms: &[u32]| {
+            let seeds: String = imms
+                .iter()
+                .map(|imm| serde_json::json!({ "instructions": [(imm << 20) | 0x93], "metadata": {} }))
+                .map(|seed| format!("{seed}\n"))
+                .collect();
+            std::fs::write(dir.join("seeds.jsonl"), seeds).unwrap();
+        };
+        let buckets =
+            vec![semantic::program::LEN_1, semantic::program::LEN_2_4, semantic::program::LEN_5_16];
+        let backend = || ImmBucketBackend { buckets: buckets.clone(), imm: 0 };
+
+        write_seeds(&[0, 1]);
+        let first = run_loop1(test_config(&dir, "first"), backend()).unwrap();
+        assert_eq!(std::fs::read_to_string(&first.corpus_path).unwrap().lines().count(), 2);
+
+        write_seeds(&[0, 2]);
+        let cfg = Loop1Config {
+            resume_from: Some(first.corpus_path.clone()),
+            ..test_config(&dir, "resumed")
+        };
+        let resumed = run_loop1(cfg.clone(), backend()).unwrap();
+        let corpus = std::fs::read_to_string(&resumed.corpus_path).unwrap();
+        let kept: Vec<serde_json::Value> =
+            corpus.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0]["instructions"], serde_json::json!([(2 << 20) | 0x93]));
+
+        // A resumed campaign needs no seed file.
+        std::fs::remove_file(dir.join("seeds.jsonl")).unwrap();
+        let only = Loop1Config { output_prefix: Some("only".to_string()), ..cfg };
+        let result = run_loop1(only, backend());
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn novelty_ttl_forgets_entries_unseen_for_the_ttl_on_sweep_evaluations() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-ttl-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let writer = JsonlWriter::open_append(&dir.join("out.jsonl")).unwrap();
+        let mut feedback = BucketNoveltyFeedback::new(
+            writer.clone(),
+            writer.clone(),
+            writer,
+            test_config(&dir, "ttl"),
+            HashSet::new(),
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+        // "old" / "a" were never hit this campaign (e.g. resumed), so count as last hit at 0.
+        feedback.seen = ["old".to_string(), "fresh".to_string()].into();
+        feedback.seen_bucket_ids = ["a".to_string(), "b".to_string()].into();
+        feedback.sig_last_hit = [("fresh".to_string(), 15)].into();
+        feedback.bucket_id_last_hit = [("b".to_string(), 15)].into();
+
+        // ttl 16 sweeps every 2 evaluations; 17 is not a sweep.
+        feedback.evals = 17;
+        feedback.evict_stale_novelty(16);
+        assert_eq!(feedback.seen.len(), 2);
+
+        feedback.evals = 18;
+        feedback.evict_stale_novelty(16);
+        assert_eq!(feedback.seen, ["fresh".to_string()].into());
+        assert_eq!(feedback.seen_bucket_ids, ["b".to_string()].into());
+        assert_eq!(feedback.sig_last_hit.len(), 1);
+
+        feedback.evals = 32;
+        feedback.evict_stale_novelty(16);
+        assert!(feedback.seen.is_empty() && feedback.bucket_id_last_hit.is_empty());
+    }
+
+    #[test]
+    fn bandit_stats_name_every_mutator_arm() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-bandit-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+        let cfg = Loop1Config { iters: 4, ..test_config(&dir, "bandit") };
+
+        let out = run_loop1(cfg, AcceptingBackend::default()).unwrap();
+        let stats = std::fs::read_to_string(out.bandit_stats_path.unwrap()).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // Counts are not checked: the bandit is process-global and other tests share it.
+        let stats: serde_json::Value = serde_json::from_str(&stats).unwrap();
+        let arms = stats["arms"].as_array().unwrap();
+        let names: Vec<&str> = arms.iter().map(|arm| arm["mutator"].as_str().unwrap()).collect();
+        assert_eq!(names, SEED_MUTATOR_ARM_NAMES);
+        assert!(arms.iter().enumerate().all(|(idx, arm)| arm["arm"] == idx));
+    }
+
+    #[test]
+    fn corpus_freezes_once_the_coverage_threshold_is_reached() {
+        let dir = std::env::temp_dir().join(format!("beak-loop1-freeze-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, 0..3`, each hitting a distinct bucket.
+        let seeds: String = (0..3u32)
+            .map(|imm| serde_json::json!({ "instructions": [(imm << 20) | 0x93], "metadata": {} }))
+            .map(|seed| format!("{seed}\n"))
+            .collect();
+        std::fs::write(dir.join("seeds.jsonl"), seeds).unwrap();
+        let buckets =
+            vec![semantic::program::LEN_1, semantic::program::LEN_2_4, semantic::program::LEN_5_16];
+        let backend = || ImmBucketBackend { buckets: buckets.clone(), imm: 0 };
+        let cfg = Loop1Config {
+            required_buckets: buckets[..2].iter().map(|bucket| bucket.id.to_string()).collect(),
+            corpus_freeze_coverage: Some(1.0),
+            ..test_config(&dir, "freeze")
+        };
+
+        let out = run_loop1(cfg.clone(), backend()).unwrap();
+        let corpus = std::fs::read_to_string(&out.corpus_path).unwrap();
+        let runs = std::fs::read_to_string(out.runs_path.as_ref().unwrap()).unwrap();
+        let frozen: Vec<_> = runs
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .map(|run| run["metadata"]["corpus_frozen"].clone())
+            .collect();
+        // The second seed completes the required buckets; it and the novel third are not kept.
+        assert_eq!(corpus.lines().count(), 1);
+        assert_eq!(frozen, vec![false, true, true]);
+
+        for threshold in [0.0, -0.5, 1.5, f64::NAN] {
+            let cfg = Loop1Config { corpus_freeze_coverage: Some(threshold), ..cfg.clone() };
+            assert!(run_loop1(cfg, backend()).is_err(), "{threshold} accepted");
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let default_universe = coverage_universe(&test_config(&dir, "freeze"));
+        assert_eq!(default_universe.len(), semantic::ALL_BUCKETS.len());
+        let bigram = semantic::program::OPCODE_BIGRAMS[0][0].id;
+        assert!(!default_universe.iter().any(|id| id == bigram));
+    }
+
+    /// Answers pending requests after `polls_needed` polls; `None` never answers.
+    struct PendingBackend {
+        polls_needed: Option<usize>,
+        polls: usize,
+    }
+
+    impl LoopBackend for PendingBackend {
+        fn prove_and_read_final_regs(&mut self, _words: &[u32]) -> Result<[u32; 32], String> {
+            unreachable!("requests go through submit/poll")
+        }
+
+        fn submit(&mut self, _words: &[u32]) -> RequestHandle {
+            RequestHandle::Pending(7)
+        }
+
+        fn poll(&mut self, request_id: u64, wait: Duration) -> Option<Result<[u32; 32], String>> {
+            assert_eq!(request_id, 7);
+            self.polls += 1;
+            if self.polls_needed.is_some_and(|needed| self.polls >= needed) {
+                return Some(Ok([1; 32]));
+            }
+            std::thread::sleep(wait);
+            None
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval::default()
+        }
+    }
+
+    #[test]
+    fn pending_requests_resolve_or_fail_once_the_wait_budget_expires() {
+        let budget = Duration::from_millis(50);
+        let mut answering = PendingBackend { polls_needed: Some(3), polls: 0 };
+        let handle = answering.submit(&[]);
+        assert_eq!(wait_for_request(&mut answering, handle, budget), Ok([1; 32]));
+
+        let mut wedged = PendingBackend { polls_needed: None, polls: 0 };
+        let handle = wedged.submit(&[]);
+        let err = wait_for_request(&mut wedged, handle, budget).unwrap_err();
+        assert!(err.contains("no result within 50 ms"), "{err}");
+        assert!(wedged.polls > 1);
+    }
+
+    #[test]
+    fn seed_shuffle_is_a_permutation_fixed_by_the_rng_seed() {
+        let original: Vec<u32> = (0..32).collect();
+        let shuffled = |rng_seed| {
+            let mut items = original.clone();
+            shuffle_seeds(&mut items, rng_seed);
+            items
+        };
+        assert_eq!(shuffled(7), shuffled(7));
+        assert_ne!(shuffled(7), shuffled(8));
+        assert_ne!(shuffled(7), original);
+        let mut sorted = shuffled(7);
+        sorted.sort_unstable();
+        assert_eq!(sorted, original);
+    }
+
+    #[test]
+    fn known_bug_signatures_earn_the_configured_bonus() {
+        let path = std::env::temp_dir()
+            .join(format!("beak-loop1-known-bugs-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let writer = JsonlWriter::open_append(&path).unwrap();
+        for sig in ["a;b", "a;b", ""] {
+            let bug = serde_json::json!({
+                "zkvm_commit": "",
+                "rng_seed": 0,
+                "timeout_ms": 0,
+                "timed_out": false,
+                "bucket_hits_sig": sig,
+                "micro_op_count": 0,
+                "backend_error": null,
+                "oracle_error": null,
+                "bucket_hits": [],
+                "mismatch_regs": [],
+                "instructions": [19],
+            });
+            writer.append_json_line(&bug).unwrap();
+        }
+        let known = load_bug_signatures(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(known.into_iter().collect::<Vec<_>>(), ["a;b"]);
+
+        assert_eq!(bandit_reward(false, 0, 0.0), 0.0);
+        assert_eq!(bandit_reward(true, 2, 0.0), 1.5);
+        assert_eq!(bandit_reward(false, 0, 0.75), 0.75);
+    }
 }
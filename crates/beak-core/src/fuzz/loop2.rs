@@ -1,17 +1,22 @@
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use libafl::inputs::BytesInput;
+use libafl_bolts::rands::{Rand, StdRand};
 use serde_json::json;
 
-use crate::fuzz::jsonl::{BugRecord, CorpusRecord, JsonlWriter};
-use crate::fuzz::loop1::{Loop1Config, Loop1Outputs, LoopBackend};
-use crate::fuzz::seed::FuzzingSeed;
-use crate::rv32im::instruction::RV32IMInstruction;
-use crate::rv32im::oracle::RISCVOracle;
+use crate::fuzz::jsonl::{BugRecord, CorpusRecord, JsonlWriter, RECORD_SCHEMA_VERSION};
+use crate::fuzz::loop1::{
+    canonical_bucket_sig, disassemble_words, mismatch_regs, shuffle_seeds, Loop1Config,
+    Loop1Outputs, LoopBackend, WitnessBitFlip, DEFAULT_IGNORED_REGS,
+};
+use crate::fuzz::seed::{diagnose_seed_line, summarize_dropped_seeds};
+use crate::rv32im::instruction::canonicalize_program;
+use crate::rv32im::oracle::{mismatch_memory, RISCVOracle};
 use crate::trace::{sorted_signatures_from_hits, sorted_signatures_from_signals, BucketHit};
 
 const ANSI_RESET: &str = "\x1b[0m";
@@ -26,9 +31,12 @@ struct DirectRunStats {
     micro_op_count: usize,
     bucket_hits: Vec<BucketHit>,
     mismatch_regs: Vec<(u32, u32, u32)>,
+    mismatch_memory: Vec<(u32, u8, u8)>,
     backend_error: Option<String>,
     oracle_error: Option<String>,
     timed_out: bool,
+    injection_applied: bool,
+    injection_caught: Option<bool>,
 }
 
 fn ansi_enabled() -> bool {
@@ -71,38 +79,47 @@ fn encode_words(words: &[u32]) -> BytesInput {
 fn load_initial_seeds(
     path: &Path,
     max_instructions: usize,
+    dedup_alpha_equivalent: bool,
     is_usable: &dyn Fn(&[u32]) -> bool,
 ) -> Vec<(BytesInput, serde_json::Value)> {
     let f = File::open(path).expect("open initial seeds");
     let r = BufReader::new(f);
     let mut out = Vec::new();
-    for line in r.lines().flatten() {
+    let mut dropped = Vec::new();
+    let mut canonical_seen = HashSet::new();
+    let mut alpha_equivalent = 0usize;
+    for (idx, line) in r.lines().flatten().enumerate() {
         let s = line.trim();
         if s.is_empty() {
             continue;
         }
-        let seed: FuzzingSeed = serde_json::from_str(s).expect("parse seed jsonl");
-        let mut words = seed.instructions;
-        words.truncate(max_instructions);
-        if !is_usable(&words) {
+        let diagnosis = diagnose_seed_line(s, max_instructions, is_usable);
+        if let Some(rejection) = diagnosis.rejection {
+            dropped.push((idx + 1, rejection));
             continue;
         }
-        if words.iter().any(|w| RV32IMInstruction::from_word(*w).is_err()) {
+        let Some(seed) = diagnosis.seed else { continue };
+        if dedup_alpha_equivalent
+            && !canonical_seen.insert(canonicalize_program(&seed.instructions))
+        {
+            alpha_equivalent += 1;
             continue;
         }
-        out.push((encode_words(&words), serde_json::Value::Object(seed.metadata)));
+        out.push((encode_words(&seed.instructions), serde_json::Value::Object(seed.metadata)));
     }
-    out
-}
-
-fn mismatch_regs(oracle: &[u32; 32], prover: &[u32; 32]) -> Vec<(u32, u32, u32)> {
-    let mut out = Vec::new();
-    for i in 0..32u32 {
-        let a = oracle[i as usize];
-        let b = prover[i as usize];
-        if a != b {
-            out.push((i, a, b));
-        }
+    if alpha_equivalent > 0 {
+        eprintln!(
+            "[LOOP2] skipped {alpha_equivalent} seed(s) alpha-equivalent to an earlier seed in {}",
+            path.display()
+        );
+    }
+    if !dropped.is_empty() {
+        eprintln!(
+            "[LOOP2][WARN] dropped {} seed line(s) from {}: {}",
+            dropped.len(),
+            path.display(),
+            summarize_dropped_seeds(&dropped)
+        );
     }
     out
 }
@@ -128,21 +145,6 @@ where
     res
 }
 
-fn canonical_bucket_sig(sigs: &[String]) -> String {
-    let mut seen = HashSet::<&str>::new();
-    let mut out: Vec<&str> = Vec::new();
-    for sig in sigs {
-        let t = sig.trim();
-        if t.is_empty() {
-            continue;
-        }
-        if seen.insert(t) {
-            out.push(t);
-        }
-    }
-    out.join(";")
-}
-
 fn run_single_eval<B: LoopBackend>(
     cfg: &Loop1Config,
     backend: &mut B,
@@ -152,12 +154,15 @@ fn run_single_eval<B: LoopBackend>(
     backend.prepare_for_run(cfg.rng_seed);
 
     let oracle_regs = catch_unwind_nonfatal(std::panic::AssertUnwindSafe(|| {
-        RISCVOracle::execute_with_config(words, cfg.oracle)
+        RISCVOracle::execute_with_memory(words, cfg.oracle)
     }));
     let panic_oracle_error = match oracle_regs.as_ref() {
         Err(p) => Some(panic_payload_to_string(p.as_ref())),
         _ => None,
     };
+    if let Ok((_, memory)) = oracle_regs.as_ref() {
+        backend.set_memory_probe(&memory.keys().copied().collect::<Vec<_>>());
+    }
 
     let backend_regs = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         backend.prove_and_read_final_regs(words)
@@ -172,17 +177,23 @@ fn run_single_eval<B: LoopBackend>(
         Err(_) => None,
     };
     let mismatches = match (oracle_regs.as_ref(), final_regs.as_ref()) {
-        (Ok(oracle), Some(regs)) => mismatch_regs(oracle, regs),
+        (Ok((oracle, _)), Some(regs)) => {
+            mismatch_regs(oracle, regs, cfg.ignored_regs.as_deref().unwrap_or(DEFAULT_IGNORED_REGS))
+        }
         _ => Vec::new(),
     };
 
     let eval = backend.collect_eval();
+    let memory_mismatches = match (oracle_regs.as_ref(), eval.final_memory.as_ref()) {
+        (Ok((_, oracle)), Some(memory)) => mismatch_memory(oracle, memory),
+        _ => Vec::new(),
+    };
     let backend_error = eval.backend_error.clone().or(panic_backend_error);
     let oracle_error = panic_oracle_error.map(|e| format!("oracle {e}"));
     let bucket_sigs = sorted_signatures_from_hits(&eval.bucket_hits);
     let signal_sigs = sorted_signatures_from_signals(&eval.trace_signals);
-    let sig = canonical_bucket_sig(&bucket_sigs);
-    let signal_sig = canonical_bucket_sig(&signal_sigs);
+    let sig = canonical_bucket_sig(&bucket_sigs, cfg.sort_signature_lexically);
+    let signal_sig = canonical_bucket_sig(&signal_sigs, cfg.sort_signature_lexically);
     let backend_timed_out =
         backend_error.as_deref().map(|e| e.contains("timed out")).unwrap_or(false);
     let timed_out = start.elapsed() > Duration::from_millis(cfg.timeout_ms) || backend_timed_out;
@@ -193,9 +204,12 @@ fn run_single_eval<B: LoopBackend>(
         micro_op_count: eval.micro_op_count,
         bucket_hits: eval.bucket_hits,
         mismatch_regs: mismatches,
+        mismatch_memory: memory_mismatches,
         backend_error,
         oracle_error,
         timed_out,
+        injection_applied: eval.semantic_injection_applied,
+        injection_caught: eval.injection_caught,
     }
 }
 
@@ -236,17 +250,24 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
         )
     });
     let prefix = format!("{base_prefix}-iter{}", cfg.iters);
-    let corpus_path = cfg.out_dir.join(format!("{prefix}-corpus.jsonl"));
-    let bugs_path = cfg.out_dir.join(format!("{prefix}-bugs.jsonl"));
-    let corpus_writer = JsonlWriter::open_append(&corpus_path)?;
-    let bug_writer = JsonlWriter::open_append(&bugs_path)?;
+    let ext = cfg.output_format.extension();
+    let corpus_path = cfg.out_dir.join(format!("{prefix}-corpus.{ext}"));
+    let bugs_path = cfg.out_dir.join(format!("{prefix}-bugs.{ext}"));
+    let corpus_writer = JsonlWriter::open_append_as(&corpus_path, cfg.output_format)?;
+    let bug_writer = JsonlWriter::open_append_as(&bugs_path, cfg.output_format)?;
 
-    let seeds = load_initial_seeds(&cfg.seeds_jsonl, cfg.max_instructions, &|words| {
-        backend.is_usable_seed(words)
-    });
+    let mut seeds = load_initial_seeds(
+        &cfg.seeds_jsonl,
+        cfg.max_instructions,
+        cfg.dedup_alpha_equivalent_seeds,
+        &|words| backend.is_usable_seed(words),
+    );
     if seeds.is_empty() {
         return Err(format!("No usable initial seeds loaded from {}", cfg.seeds_jsonl.display()));
     }
+    if cfg.shuffle_initial_seeds {
+        shuffle_seeds(&mut seeds, cfg.rng_seed);
+    }
 
     let mut bug_count = 0usize;
     let mut resolved_direct_buckets: HashSet<String> = HashSet::new();
@@ -280,6 +301,7 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
             if backend.arm_direct_injection_from_hits(&filtered_hits).is_some() {
                 let injected = run_single_eval(&cfg, &mut backend, &words);
                 let injected_found_bug = !injected.mismatch_regs.is_empty()
+                    || !injected.mismatch_memory.is_empty()
                     || injected.backend_error.is_some()
                     || injected.oracle_error.is_some()
                     || injected.timed_out
@@ -294,7 +316,8 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
         }
         backend.clear_direct_injection();
         for (phase_name, is_injected_phase, stats) in phases {
-            let baseline_mismatch = !is_injected_phase && !stats.mismatch_regs.is_empty();
+            let baseline_mismatch = !is_injected_phase
+                && (!stats.mismatch_regs.is_empty() || !stats.mismatch_memory.is_empty());
             let mut metadata = match seed_meta.clone() {
                 serde_json::Value::Object(m) => m,
                 _ => serde_json::Map::new(),
@@ -308,6 +331,7 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
                 json!(has_direct_injection_target),
             );
             let corpus = CorpusRecord {
+                schema_version: RECORD_SCHEMA_VERSION,
                 zkvm_commit: cfg.zkvm_commit.clone(),
                 rng_seed: cfg.rng_seed,
                 timeout_ms: cfg.timeout_ms,
@@ -318,7 +342,7 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
                 instructions: words.clone(),
                 metadata: serde_json::Value::Object(metadata.clone()),
             };
-            corpus_writer.append_json_line(&corpus)?;
+            corpus_writer.append_record(&corpus)?;
 
             let underconstrained_candidate = is_injected_phase
                 && has_direct_injection_target
@@ -339,7 +363,11 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
                     "underconstrained_candidate".to_string(),
                     json!(underconstrained_candidate),
                 );
+                if cfg.include_disasm_in_bugs {
+                    metadata.insert("disasm".to_string(), json!(disassemble_words(&words)));
+                }
                 let bug = BugRecord {
+                    schema_version: RECORD_SCHEMA_VERSION,
                     zkvm_commit: cfg.zkvm_commit.clone(),
                     rng_seed: cfg.rng_seed,
                     timeout_ms: cfg.timeout_ms,
@@ -355,10 +383,15 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
                     } else {
                         Vec::new()
                     },
+                    mismatch_memory: if baseline_mismatch {
+                        stats.mismatch_memory.clone()
+                    } else {
+                        Vec::new()
+                    },
                     instructions: words.clone(),
                     metadata: serde_json::Value::Object(metadata),
                 };
-                bug_writer.append_json_line(&bug)?;
+                bug_writer.append_record(&bug)?;
                 bug_count += 1;
                 let marker = if kind == "underconstrained_candidate" {
                     colorize("[LOOP2][UNDERCONSTRAINED]", ANSI_BOLD_YELLOW)
@@ -383,5 +416,277 @@ pub fn run_direct_bucket_mutate<B: LoopBackend>(
     };
     eprintln!("{summary}");
 
-    Ok(Loop1Outputs { corpus_path, bugs_path, runs_path: None })
+    Ok(Loop1Outputs {
+        corpus_path,
+        bugs_path,
+        runs_path: None,
+        bucket_histogram_path: None,
+        bandit_stats_path: None,
+    })
+}
+
+/// Soundness loop: for every seed program that runs cleanly, flip single witness bits (chosen
+/// from the backend's `witness_flip_sites`) and re-check the constraints. A flip that lands but
+/// is still accepted means that column is under-constrained; it is recorded once per column as
+/// an `under_constrained_column` bug. Tries `cfg.witness_flips_per_seed` flips per seed.
+pub fn run_loop2<B: LoopBackend>(cfg: Loop1Config, mut backend: B) -> Result<Loop1Outputs, String> {
+    std::fs::create_dir_all(&cfg.out_dir)
+        .map_err(|e| format!("create out_dir {} failed: {e}", cfg.out_dir.display()))?;
+
+    let prefix = cfg.output_prefix.clone().unwrap_or_else(|| {
+        format!(
+            "loop2-soundness-{}-{}-seed{}-{}",
+            cfg.zkvm_tag,
+            &cfg.zkvm_commit[..cfg.zkvm_commit.len().min(8)],
+            cfg.rng_seed,
+            now_ts_secs()
+        )
+    });
+    let ext = cfg.output_format.extension();
+    let corpus_path = cfg.out_dir.join(format!("{prefix}-corpus.{ext}"));
+    let bugs_path = cfg.out_dir.join(format!("{prefix}-bugs.{ext}"));
+    let corpus_writer = JsonlWriter::open_append_as(&corpus_path, cfg.output_format)?;
+    let bug_writer = JsonlWriter::open_append_as(&bugs_path, cfg.output_format)?;
+
+    let mut seeds = load_initial_seeds(
+        &cfg.seeds_jsonl,
+        cfg.max_instructions,
+        cfg.dedup_alpha_equivalent_seeds,
+        &|words| backend.is_usable_seed(words),
+    );
+    if seeds.is_empty() {
+        return Err(format!("No usable initial seeds loaded from {}", cfg.seeds_jsonl.display()));
+    }
+    if cfg.shuffle_initial_seeds {
+        shuffle_seeds(&mut seeds, cfg.rng_seed);
+    }
+
+    let mut rand = StdRand::with_seed(cfg.rng_seed);
+    let mut reported_columns: HashSet<String> = HashSet::new();
+    let mut flips_applied = 0usize;
+    let take_n =
+        if cfg.initial_limit == 0 { seeds.len() } else { cfg.initial_limit.min(seeds.len()) };
+    for (seed_idx, (input, seed_meta)) in seeds.into_iter().take(take_n).enumerate() {
+        let words = decode_words_from_input(&input, cfg.max_instructions);
+        if words.is_empty() || !backend.is_usable_seed(&words) {
+            continue;
+        }
+
+        backend.clear_direct_injection();
+        let baseline = run_single_eval(&cfg, &mut backend, &words);
+        let clean = baseline.mismatch_regs.is_empty()
+            && baseline.mismatch_memory.is_empty()
+            && baseline.backend_error.is_none()
+            && baseline.oracle_error.is_none()
+            && !baseline.timed_out;
+        let mut metadata = match seed_meta {
+            serde_json::Value::Object(m) => m,
+            _ => serde_json::Map::new(),
+        };
+        metadata.insert("mode".to_string(), json!("loop2_soundness"));
+        metadata.insert("seed_index".to_string(), json!(seed_idx));
+        metadata.insert("clean_baseline".to_string(), json!(clean));
+        corpus_writer.append_record(&CorpusRecord {
+            schema_version: RECORD_SCHEMA_VERSION,
+            zkvm_commit: cfg.zkvm_commit.clone(),
+            rng_seed: cfg.rng_seed,
+            timeout_ms: cfg.timeout_ms,
+            timed_out: baseline.timed_out,
+            mismatch: !baseline.mismatch_regs.is_empty() || !baseline.mismatch_memory.is_empty(),
+            bucket_hits_sig: baseline.bucket_hits_sig.clone(),
+            signal_sig: baseline.signal_sig.clone(),
+            instructions: words.clone(),
+            metadata: serde_json::Value::Object(metadata.clone()),
+        })?;
+        // A flip is only meaningful against a witness the constraints accept unperturbed.
+        if !clean {
+            continue;
+        }
+
+        let sites: Vec<_> =
+            backend.witness_flip_sites().into_iter().filter(|s| !s.steps.is_empty()).collect();
+        let Some(site_count) = NonZeroUsize::new(sites.len()) else { continue };
+        for _ in 0..cfg.witness_flips_per_seed {
+            let site = &sites[rand.below(site_count)];
+            let step_count = NonZeroUsize::new(site.steps.len()).unwrap();
+            let flip = WitnessBitFlip {
+                column: site.column.clone(),
+                step: site.steps[rand.below(step_count)],
+                bit: rand.below(NonZeroUsize::new(32).unwrap()) as u32,
+            };
+            if let Err(e) = backend.arm_witness_bit_flip(&flip) {
+                eprintln!("[LOOP2][WARN] seed_idx={seed_idx} cannot arm witness bit flip: {e}");
+                break;
+            }
+            let stats = run_single_eval(&cfg, &mut backend, &words);
+            backend.clear_direct_injection();
+            if !stats.injection_applied {
+                continue;
+            }
+            flips_applied += 1;
+            let caught = stats.backend_error.is_some() || stats.injection_caught == Some(true);
+            if caught || !reported_columns.insert(flip.column.clone()) {
+                continue;
+            }
+
+            let mut metadata = metadata.clone();
+            metadata.insert("kind".to_string(), json!("under_constrained_column"));
+            metadata.insert("column".to_string(), json!(flip.column));
+            metadata.insert("step".to_string(), json!(flip.step));
+            metadata.insert("bit".to_string(), json!(flip.bit));
+            if cfg.include_disasm_in_bugs {
+                metadata.insert("disasm".to_string(), json!(disassemble_words(&words)));
+            }
+            bug_writer.append_record(&BugRecord {
+                schema_version: RECORD_SCHEMA_VERSION,
+                zkvm_commit: cfg.zkvm_commit.clone(),
+                rng_seed: cfg.rng_seed,
+                timeout_ms: cfg.timeout_ms,
+                timed_out: stats.timed_out,
+                bucket_hits_sig: stats.bucket_hits_sig.clone(),
+                signal_sig: stats.signal_sig.clone(),
+                micro_op_count: stats.micro_op_count,
+                backend_error: None,
+                oracle_error: stats.oracle_error.clone(),
+                bucket_hits: stats.bucket_hits.clone(),
+                mismatch_regs: stats.mismatch_regs.clone(),
+                mismatch_memory: stats.mismatch_memory.clone(),
+                instructions: words.clone(),
+                metadata: serde_json::Value::Object(metadata),
+            })?;
+            eprintln!(
+                "{} column={} step={} bit={} seed_idx={seed_idx}",
+                colorize("[LOOP2][UNDERCONSTRAINED_COLUMN]", ANSI_BOLD_RED),
+                flip.column,
+                flip.step,
+                flip.bit
+            );
+        }
+    }
+
+    corpus_writer.flush()?;
+    bug_writer.flush()?;
+    let summary = format!(
+        "[LOOP2][DONE] flips_applied={flips_applied} under_constrained_columns={}",
+        reported_columns.len()
+    );
+    let color = if reported_columns.is_empty() { ANSI_BOLD_GREEN } else { ANSI_BOLD_RED };
+    eprintln!("{}", colorize(&summary, color));
+
+    Ok(Loop1Outputs {
+        corpus_path,
+        bugs_path,
+        runs_path: None,
+        bucket_histogram_path: None,
+        bandit_stats_path: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_loop2;
+    use crate::fuzz::jsonl::{read_bincode_records, BugRecord, OutputFormat};
+    use crate::fuzz::loop1::{
+        self, BackendEval, Loop1Config, LoopBackend, WitnessBitFlip, WitnessFlipSite,
+    };
+    use crate::rv32im::oracle::RISCVOracle;
+    use std::cell::RefCell;
+    use std::path::Path;
+    use std::rc::Rc;
+
+    /// Agrees with the oracle; flips on `loose` columns are accepted, on `tight` ones rejected.
+    /// Every run with an armed flip is logged to `flips`.
+    #[derive(Default)]
+    struct FlipBackend {
+        armed: Option<WitnessBitFlip>,
+        flips: Rc<RefCell<Vec<WitnessBitFlip>>>,
+    }
+
+    impl LoopBackend for FlipBackend {
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            self.flips.borrow_mut().extend(self.armed.clone());
+            Ok(RISCVOracle::execute(words))
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            let rejected = self.armed.as_ref().is_some_and(|flip| flip.column == "tight");
+            BackendEval {
+                semantic_injection_applied: self.armed.is_some(),
+                backend_error: rejected.then(|| "constraint failed".to_string()),
+                ..Default::default()
+            }
+        }
+
+        fn clear_direct_injection(&mut self) {
+            self.armed = None;
+        }
+
+        fn witness_flip_sites(&self) -> Vec<WitnessFlipSite> {
+            ["loose", "tight"]
+                .map(|column| WitnessFlipSite { column: column.to_string(), steps: vec![0] })
+                .to_vec()
+        }
+
+        fn arm_witness_bit_flip(&mut self, flip: &WitnessBitFlip) -> Result<(), String> {
+            self.armed = Some(flip.clone());
+            Ok(())
+        }
+    }
+
+    fn test_config(dir: &Path, witness_flips_per_seed: usize) -> Loop1Config {
+        let prefix = format!("flips{witness_flips_per_seed}");
+        Loop1Config { witness_flips_per_seed, ..loop1::test_config(dir, &prefix) }
+    }
+
+    #[test]
+    fn accepted_flips_are_reported_once_per_column() {
+        let dir = std::env::temp_dir().join(format!("beak-loop2-flips-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, 5`
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+
+        let backend = FlipBackend::default();
+        let flips = Rc::clone(&backend.flips);
+        run_loop2(test_config(&dir, 0), backend).unwrap();
+        assert!(flips.borrow().is_empty());
+
+        let backend = FlipBackend::default();
+        let flips = Rc::clone(&backend.flips);
+        let out = run_loop2(test_config(&dir, 16), backend).unwrap();
+        let bugs = std::fs::read_to_string(&out.bugs_path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let flips = flips.borrow();
+        assert_eq!(flips.len(), 16);
+        assert!(flips.iter().all(|flip| flip.step == 0 && flip.bit < 32));
+        assert!(flips.iter().any(|flip| flip.column == "tight"));
+        let columns: Vec<serde_json::Value> = bugs
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .map(|bug| bug["metadata"]["column"].clone())
+            .collect();
+        assert_eq!(columns, vec![serde_json::json!("loose")]);
+    }
+
+    #[test]
+    fn loop2_writes_records_in_the_configured_output_format() {
+        let dir = std::env::temp_dir().join(format!("beak-loop2-format-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let seed = serde_json::json!({ "instructions": [0x0050_0093u32], "metadata": {} });
+        std::fs::write(dir.join("seeds.jsonl"), format!("{seed}\n")).unwrap();
+
+        let cfg = Loop1Config { output_format: OutputFormat::Bincode, ..test_config(&dir, 16) };
+        let out = run_loop2(cfg, FlipBackend::default()).unwrap();
+        let bugs = read_bincode_records::<BugRecord>(&out.bugs_path);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(out.bugs_path.extension().unwrap(), OutputFormat::Bincode.extension());
+        assert_eq!(out.corpus_path.extension().unwrap(), OutputFormat::Bincode.extension());
+        let bugs = bugs.unwrap();
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].metadata["column"], "loose");
+    }
 }
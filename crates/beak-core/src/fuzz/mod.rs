@@ -4,4 +4,8 @@ pub mod jsonl;
 pub mod loop1;
 pub mod loop2;
 pub mod mutators;
+pub mod rebucket;
+pub mod result_cache;
 pub mod seed;
+pub mod worker_diagnostics;
+pub mod worker_protocol;
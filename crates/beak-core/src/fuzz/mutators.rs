@@ -4,7 +4,8 @@ use libafl::prelude::*;
 use libafl_bolts::rands::Rand;
 use libafl_bolts::Named;
 
-use crate::rv32im::instruction::RV32IMInstruction;
+use crate::rv32im::instruction::{RV32IMInstruction, NOP_WORD};
+use crate::rv32im::supported::SupportedOpcodes;
 
 use super::bandit;
 
@@ -51,11 +52,9 @@ fn collect_used_operands(words: &[u32]) -> UsedOperands {
     for &word in words {
         let Ok(insn) = RV32IMInstruction::from_word(word) else { continue };
 
-        for r in [insn.rd, insn.rs1, insn.rs2] {
-            if let Some(r) = r {
-                used.regs.push(r);
-            }
-        }
+        let effects = insn.reg_effects();
+        used.regs.extend_from_slice(&effects.reads);
+        used.regs.extend_from_slice(&effects.writes);
 
         // Track “previously used memory address components” using load/store patterns.
         // We do not simulate execution here; we approximate “address reuse” as reusing the base
@@ -99,17 +98,202 @@ fn pick_from_slice_i32(state: &mut LoopState, xs: &[i32]) -> i32 {
     xs[idx]
 }
 
+fn random_i32_in(rng: &mut impl Rand, lo: i32, hi: i32) -> i32 {
+    lo + rng.below(nz((hi - lo + 1) as usize)) as i32
+}
+
+fn random_mnemonic(rng: &mut impl Rand, xs: &[&'static str]) -> &'static str {
+    xs[rng.below(nz(xs.len()))]
+}
+
+/// Synthesize a program of `len` valid RV32IM words from scratch (no seed needed).
+///
+/// Each slot picks an ALU/MUL/DIV, load/store, branch, LUI/AUIPC or JAL instruction with random
+/// registers and an immediate inside its format's range (12-bit signed, 5-bit shamt, 20-bit
+/// upper). Branch and JAL offsets only jump forward, to a slot of the program or just past its
+/// end, so the result always terminates. JALR, fences, CSR and system instructions are left out.
+/// Backend usability is not checked here; callers filter with `is_usable_seed`.
+pub fn generate_random_program(len: usize, rng: &mut impl Rand) -> Vec<u32> {
+    const R_TYPE: &[&str] = &[
+        "add", "sub", "sll", "slt", "sltu", "xor", "srl", "sra", "or", "and", "mul", "mulh",
+        "mulhsu", "mulhu", "div", "divu", "rem", "remu",
+    ];
+    const I_TYPE: &[&str] = &["addi", "slti", "sltiu", "xori", "ori", "andi"];
+    const SHIFT_IMM: &[&str] = &["slli", "srli", "srai"];
+    const LOADS: &[&str] = &["lb", "lh", "lw", "lbu", "lhu"];
+    const STORES: &[&str] = &["sb", "sh", "sw"];
+    const BRANCHES: &[&str] = &["beq", "bne", "blt", "bge", "bltu", "bgeu"];
+    const UPPER: &[&str] = &["lui", "auipc"];
+
+    let mut words = Vec::with_capacity(len);
+    while words.len() < len {
+        let rd = Some(rng.below(nz(32)) as u32);
+        let rs1 = Some(rng.below(nz(32)) as u32);
+        let rs2 = Some(rng.below(nz(32)) as u32);
+        // Forward jump distance in words: 1 lands on the next slot, `len - idx` just past the end.
+        let max_skip = len - words.len();
+        let insn = match rng.below(nz(8)) {
+            0 | 1 => {
+                RV32IMInstruction::from_parts(random_mnemonic(rng, R_TYPE), rd, rs1, rs2, None)
+            }
+            2 => {
+                let imm = Some(random_i32_in(rng, -2048, 2047));
+                RV32IMInstruction::from_parts(random_mnemonic(rng, I_TYPE), rd, rs1, None, imm)
+            }
+            3 => {
+                let imm = Some(random_i32_in(rng, 0, 31));
+                RV32IMInstruction::from_parts(random_mnemonic(rng, SHIFT_IMM), rd, rs1, None, imm)
+            }
+            4 => {
+                let imm = Some(random_i32_in(rng, -2048, 2047));
+                if rng.coinflip(0.5) {
+                    RV32IMInstruction::from_parts(random_mnemonic(rng, LOADS), rd, rs1, None, imm)
+                } else {
+                    RV32IMInstruction::from_parts(random_mnemonic(rng, STORES), None, rs1, rs2, imm)
+                }
+            }
+            5 => {
+                // B-type offsets are even and within +/-4 KiB.
+                let skip = random_i32_in(rng, 1, max_skip.min(1023) as i32);
+                RV32IMInstruction::from_parts(
+                    random_mnemonic(rng, BRANCHES),
+                    None,
+                    rs1,
+                    rs2,
+                    Some(skip * 4),
+                )
+            }
+            6 => {
+                let imm = Some(random_i32_in(rng, 0, 0xFFFFF));
+                RV32IMInstruction::from_parts(random_mnemonic(rng, UPPER), rd, None, None, imm)
+            }
+            _ => {
+                // J-type offsets are even and within +/-1 MiB.
+                let skip = random_i32_in(rng, 1, max_skip.min((1 << 18) - 1) as i32);
+                RV32IMInstruction::from_parts("jal", rd, None, None, Some(skip * 4))
+            }
+        };
+        if let Ok(insn) = insn {
+            words.push(insn.word);
+        }
+    }
+    words
+}
+
+/// Inclusive byte-offset range of a PC-relative branch or JAL immediate.
+fn control_flow_offset_range(mnemonic: &str) -> Option<(i32, i32)> {
+    match mnemonic {
+        "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" => Some((-4096, 4094)),
+        "jal" => Some((-(1 << 20), (1 << 20) - 2)),
+        _ => None,
+    }
+}
+
+/// Edge-case offsets for the control-flow instruction at `idx` of a `len`-word program: self
+/// loop, +/-2 and +/-4, the encodable extremes, `target` (another instruction of the program) and
+/// the halfword just past it (mid-instruction). Out-of-range values are dropped.
+fn interesting_control_flow_offsets(idx: usize, target: usize, (lo, hi): (i32, i32)) -> Vec<i32> {
+    let to_target = (target as i32 - idx as i32) * 4;
+    [0, 2, -2, 4, -4, hi, lo, to_target, to_target + 2]
+        .into_iter()
+        .filter(|offset| (lo..=hi).contains(offset))
+        .collect()
+}
+
+/// Bit positions of the funct3 (`[14:12]`) and funct7 (`[31:25]`) fields.
+const FUNCT_BITS: [u32; 10] = [12, 13, 14, 25, 26, 27, 28, 29, 30, 31];
+
+/// Random one- or two-bit flips tried before `near_miss_funct_word` gives up on a word.
+const FUNCT_FLIP_ATTEMPTS: usize = 8;
+
+/// One in this many flipped encodings the oracle cannot decode is kept anyway.
+const UNDECODABLE_FLIP_ODDS: usize = 4;
+
+/// Flip one or two funct3/funct7 bits of `word`, returning the first flipped encoding that still
+/// decodes, i.e. a near-miss neighbour of a valid instruction (e.g. an R-type with an unexpected
+/// funct7). An undecodable flip is returned one time in `UNDECODABLE_FLIP_ODDS`, since that is
+/// where the oracle and a backend's transpiler may disagree; the mutator's filter decides whether
+/// the backend runs it. `None` if no attempt is kept.
+pub fn near_miss_funct_word(word: u32, rng: &mut impl Rand) -> Option<u32> {
+    (0..FUNCT_FLIP_ATTEMPTS).find_map(|_| {
+        let mut mask = 1u32 << FUNCT_BITS[rng.below(nz(FUNCT_BITS.len()))];
+        if rng.below(nz(2)) == 0 {
+            mask ^= 1u32 << FUNCT_BITS[rng.below(nz(FUNCT_BITS.len()))];
+        }
+        let candidate = word ^ mask;
+        let keep = RV32IMInstruction::from_word(candidate).is_ok()
+            || rng.below(nz(UNDECODABLE_FLIP_ODDS)) == 0;
+        (candidate != word && keep).then_some(candidate)
+    })
+}
+
 /// Custom mutator implementing the requested strategies on 32-bit word-aligned inputs.
 pub struct SeedMutator {
     max_instructions: usize,
+    /// Mutated inputs shorter than this are padded with `NOP_WORD` (0 disables the floor).
+    min_instructions: usize,
+    /// Mutations that introduce a word outside this subset are discarded.
+    supported: SupportedOpcodes,
+    /// Keep mutants with words `RV32IMInstruction::from_word` rejects.
+    undecodable_words: bool,
     name: std::borrow::Cow<'static, str>,
 }
 
-pub const SEED_MUTATOR_NUM_ARMS: usize = 8;
+pub const SEED_MUTATOR_NUM_ARMS: usize = 10;
+
+/// Mutation strategy behind each bandit arm of `SeedMutator`, in arm index order.
+pub const SEED_MUTATOR_ARM_NAMES: [&str; SEED_MUTATOR_NUM_ARMS] = [
+    "splice_two",
+    "mutate_registers",
+    "mutate_constants",
+    "insert_random_instruction",
+    "delete_one_instruction",
+    "duplicate_one_instruction",
+    "swap_adjacent_instructions",
+    "replace_mnemonic_same_format",
+    "mutate_control_flow_offset",
+    "flip_funct_bits",
+];
 
 impl SeedMutator {
-    pub fn new(max_instructions: usize) -> Self {
-        Self { max_instructions, name: "SeedMutator".into() }
+    pub fn new(max_instructions: usize, min_instructions: usize) -> Self {
+        Self {
+            max_instructions,
+            min_instructions,
+            supported: SupportedOpcodes::default(),
+            undecodable_words: false,
+            name: "SeedMutator".into(),
+        }
+    }
+
+    /// Only keep mutants whose every word is in `supported` (e.g. the backend's
+    /// `BackendCapabilities::supported_opcodes`).
+    pub fn with_supported_opcodes(mut self, supported: SupportedOpcodes) -> Self {
+        self.supported = supported;
+        self
+    }
+
+    /// Also keep mutants with undecodable words, for backends that run them
+    /// (`BackendCapabilities::undecodable_words`).
+    pub fn with_undecodable_words(mut self, undecodable_words: bool) -> Self {
+        self.undecodable_words = undecodable_words;
+        self
+    }
+
+    /// Whether a mutant may contain `word`; mirrors `BackendCapabilities::allows_word`.
+    fn allows_word(&self, word: u32) -> bool {
+        self.supported.allows_word(word)
+            || (self.undecodable_words && RV32IMInstruction::from_word(word).is_err())
+    }
+
+    /// Bring a mutated program within `[min_instructions, max_instructions]`, so the stored
+    /// testcase is exactly what runs instead of being truncated again on every decode.
+    fn normalize_len(&self, words: &mut Vec<u32>) {
+        words.truncate(self.max_instructions);
+        let floor = self.min_instructions.min(self.max_instructions);
+        if words.len() < floor {
+            words.resize(floor, NOP_WORD);
+        }
     }
 
     fn mutate_registers(state: &mut LoopState, words: &mut [u32], used_regs: &[u32]) {
@@ -176,6 +360,52 @@ impl SeedMutator {
         words[idx] = new_insn.word;
     }
 
+    /// Retarget one branch/JAL to an edge-case offset (see `interesting_control_flow_offsets`),
+    /// which uniform constant mutation rarely produces in range.
+    fn mutate_control_flow_offset(state: &mut LoopState, words: &mut [u32]) {
+        let candidates: Vec<(usize, RV32IMInstruction, (i32, i32))> = words
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &word)| {
+                let insn = RV32IMInstruction::from_word(word).ok()?;
+                let range = control_flow_offset_range(&insn.mnemonic)?;
+                Some((idx, insn, range))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        let (idx, insn, range) = &candidates[state.rand_mut().below(nz(candidates.len()))];
+        let target = state.rand_mut().below(nz(words.len()));
+        let offsets = interesting_control_flow_offsets(*idx, target, *range);
+        let new_imm = offsets[state.rand_mut().below(nz(offsets.len()))];
+        if Some(new_imm) == insn.imm {
+            return;
+        }
+        let Ok(new_insn) = RV32IMInstruction::from_parts(
+            &insn.mnemonic,
+            insn.rd,
+            insn.rs1,
+            insn.rs2,
+            Some(new_imm),
+        ) else {
+            return;
+        };
+        words[*idx] = new_insn.word;
+    }
+
+    /// Replace one word with a `near_miss_funct_word` neighbour: operand-level mutators never
+    /// touch the raw funct bits, where decoder/transpiler disagreements tend to sit.
+    fn flip_funct_bits(state: &mut LoopState, words: &mut [u32]) {
+        if words.is_empty() {
+            return;
+        }
+        let idx = state.rand_mut().below(nz(words.len()));
+        if let Some(word) = near_miss_funct_word(words[idx], state.rand_mut()) {
+            words[idx] = word;
+        }
+    }
+
     fn insert_random_instruction(state: &mut LoopState, words: &mut Vec<u32>, used: &UsedOperands) {
         if words.len() >= 2048 {
             return;
@@ -362,10 +592,15 @@ impl Mutator<BytesInput, LoopState> for SeedMutator {
             5 => Self::duplicate_one_instruction(state, &mut words),
             6 => Self::swap_adjacent_instructions(state, &mut words),
             7 => Self::replace_mnemonic_same_format(state, &mut words),
+            8 => Self::mutate_control_flow_offset(state, &mut words),
+            9 => Self::flip_funct_bits(state, &mut words),
             _ => Self::insert_random_instruction(state, &mut words, &used),
         }
 
-        words.truncate(self.max_instructions);
+        self.normalize_len(&mut words);
+        if !words.iter().all(|w| self.allows_word(*w)) {
+            return Ok(MutationResult::Skipped);
+        }
         *input = encode_words(&words);
         Ok(MutationResult::Mutated)
     }
@@ -378,3 +613,88 @@ impl Mutator<BytesInput, LoopState> for SeedMutator {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use libafl_bolts::rands::StdRand;
+
+    use super::{
+        control_flow_offset_range, generate_random_program, interesting_control_flow_offsets,
+        near_miss_funct_word, SeedMutator, FUNCT_BITS, NOP_WORD,
+    };
+    use crate::rv32im::instruction::RV32IMInstruction;
+
+    #[test]
+    fn random_programs_decode_and_only_jump_forward() {
+        let mut rng = StdRand::with_seed(7);
+        for len in [1usize, 2, 16, 64] {
+            let words = generate_random_program(len, &mut rng);
+            assert_eq!(words.len(), len);
+            for (idx, &word) in words.iter().enumerate() {
+                let insn = RV32IMInstruction::from_word(word).expect("decodes");
+                assert_eq!(insn.reencode(), Ok(word), "{}", insn.asm);
+                if matches!(
+                    insn.mnemonic.as_str(),
+                    "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" | "jal"
+                ) {
+                    let offset = insn.imm.unwrap();
+                    assert!(offset > 0 && offset % 4 == 0, "{}", insn.asm);
+                    assert!(idx + (offset / 4) as usize <= len, "{}", insn.asm);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interesting_offsets_encode_for_branches_and_jal() {
+        for mnemonic in ["beq", "bgeu", "jal"] {
+            let range = control_flow_offset_range(mnemonic).unwrap();
+            for (idx, target) in [(0usize, 0usize), (3, 1), (1, 5000)] {
+                let offsets = interesting_control_flow_offsets(idx, target, range);
+                assert!(offsets.contains(&range.0) && offsets.contains(&range.1));
+                for offset in offsets {
+                    let rd = (mnemonic == "jal").then_some(1);
+                    let rs = (mnemonic != "jal").then_some(2);
+                    let insn = RV32IMInstruction::from_parts(mnemonic, rd, rs, rs, Some(offset))
+                        .unwrap_or_else(|e| panic!("{mnemonic} {offset}: {e}"));
+                    assert_eq!(insn.imm, Some(offset), "{}", insn.asm);
+                }
+            }
+        }
+        assert!(control_flow_offset_range("jalr").is_none());
+    }
+
+    #[test]
+    fn normalize_len_truncates_and_pads_with_nops() {
+        let mutator = SeedMutator::new(4, 2);
+        let mut long = vec![1, 2, 3, 4, 5, 6];
+        mutator.normalize_len(&mut long);
+        assert_eq!(long, vec![1, 2, 3, 4]);
+        let mut short = vec![7];
+        mutator.normalize_len(&mut short);
+        assert_eq!(short, vec![7, NOP_WORD]);
+        assert_eq!(RV32IMInstruction::from_word(NOP_WORD).unwrap().mnemonic, "addi");
+    }
+
+    #[test]
+    fn near_miss_funct_words_only_differ_in_funct_bits() {
+        let funct_mask = FUNCT_BITS.iter().fold(0u32, |mask, bit| mask | (1 << bit));
+        let add = RV32IMInstruction::from_parts("add", Some(1), Some(2), Some(3), None).unwrap();
+        let mut rng = StdRand::with_seed(11);
+        let mut mnemonics = std::collections::BTreeSet::new();
+        let mut undecodable = 0;
+        for _ in 0..64 {
+            let Some(word) = near_miss_funct_word(add.word, &mut rng) else { continue };
+            assert_ne!(word, add.word);
+            assert_eq!((word ^ add.word) & !funct_mask, 0);
+            match RV32IMInstruction::from_word(word) {
+                Ok(insn) => {
+                    mnemonics.insert(insn.mnemonic);
+                }
+                Err(_) => undecodable += 1,
+            }
+        }
+        assert!(mnemonics.len() > 2 && !mnemonics.contains("add"), "{mnemonics:?}");
+        assert!(undecodable > 0);
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::fuzz::loop1::canonical_bucket_sig;
+use crate::trace::semantic_matchers::{executed_words, extend_word_derived_hits};
+use crate::trace::{sorted_signatures_from_hits, BucketHit};
+
+/// How one archived input buckets under the updated matcher, next to its recorded signature.
+#[derive(Debug, Clone, Serialize)]
+pub struct RebucketEntry {
+    /// Archive file name (without directory).
+    pub name: String,
+    /// Signature recorded with the trace (`bucket_hits_sig`), if the archive carries one.
+    pub old_sig: Option<String>,
+    pub new_sig: String,
+    /// Bucket ids in `new_sig` but not in `old_sig`.
+    pub added_bucket_ids: Vec<String>,
+    /// Bucket ids in `old_sig` but not in `new_sig`.
+    pub removed_bucket_ids: Vec<String>,
+    /// Replaying the archive in name order, `new_sig` is novel under the updated matcher while
+    /// `old_sig` was not novel (or absent) under the old one.
+    pub newly_interesting: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RebucketReport {
+    pub entries: Vec<RebucketEntry>,
+    /// `(name, error)` for archives that could not be read or that the matcher rejected.
+    pub errors: Vec<(String, String)>,
+    /// Names of the entries with `newly_interesting` set.
+    pub newly_interesting: Vec<String>,
+}
+
+fn sig_ids(sig: &str) -> BTreeSet<&str> {
+    sig.split(';').map(str::trim).filter(|s| !s.is_empty()).collect()
+}
+
+/// Archive object for one recorded trace, in the shape `rebucket_corpus` reads back: the raw
+/// micro-op logs, the signature `hits` bucket to now, and the program that produced them.
+pub fn trace_archive(logs: Vec<Value>, hits: &[BucketHit], words: &[u32]) -> Value {
+    serde_json::json!({
+        "logs": logs,
+        "bucket_hits_sig": canonical_bucket_sig(&sorted_signatures_from_hits(hits), true),
+        "words": words,
+    })
+}
+
+/// Raw micro-op logs, recorded signature and program words of one archived trace.
+type ArchivedTrace = (Vec<Value>, Option<String>, Vec<u32>);
+
+/// Split an archived trace into its raw micro-op logs, recorded signature and program words.
+///
+/// Accepts either a bare JSON array of logs or an object
+/// `{ "logs": [...], "bucket_hits_sig", "words" }`.
+fn parse_archived_trace(value: Value) -> Result<ArchivedTrace, String> {
+    match value {
+        Value::Array(logs) => Ok((logs, None, Vec::new())),
+        Value::Object(mut obj) => {
+            let logs = match obj.remove("logs") {
+                Some(Value::Array(logs)) => logs,
+                _ => return Err("missing \"logs\" array".to_string()),
+            };
+            let old_sig = obj.get("bucket_hits_sig").and_then(Value::as_str).map(str::to_string);
+            let words = obj
+                .get("words")
+                .and_then(Value::as_array)
+                .map(|words| words.iter().filter_map(|w| w.as_u64()).map(|w| w as u32).collect())
+                .unwrap_or_default();
+            Ok((logs, old_sig, words))
+        }
+        _ => Err("archived trace is neither an array nor an object".to_string()),
+    }
+}
+
+/// Re-run `matcher` over every archived `*.json` trace in `traces_dir` (in file name order) and
+/// report how the corpus would bucket under it, without re-executing or re-proving anything.
+///
+/// `matcher` maps the raw micro-op logs to the trace's bucket hits and the executed pcs, e.g.
+/// `OpenVMTrace::from_logs` followed by `bucket_hits()`; the word-derived hits
+/// (`extend_word_derived_hits`) are added from the archived program words as in a live run.
+/// Unreadable archives and matcher errors are collected in `errors`.
+pub fn rebucket_corpus<M>(traces_dir: &Path, mut matcher: M) -> Result<RebucketReport, String>
+where
+    M: FnMut(Vec<Value>) -> Result<(Vec<BucketHit>, Vec<u64>), String>,
+{
+    let mut paths: Vec<_> = std::fs::read_dir(traces_dir)
+        .map_err(|e| format!("read traces dir {} failed: {e}", traces_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut report = RebucketReport::default();
+    let mut old_seen = HashSet::<String>::new();
+    let mut new_seen = HashSet::<String>::new();
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| format!("read failed: {e}"))
+            .and_then(|s| serde_json::from_str(&s).map_err(|e| format!("parse failed: {e}")))
+            .and_then(parse_archived_trace);
+        let (logs, old_sig, words) = match parsed {
+            Ok(v) => v,
+            Err(e) => {
+                report.errors.push((name, e));
+                continue;
+            }
+        };
+        let hits = match matcher(logs) {
+            Ok((mut hits, pcs)) => {
+                extend_word_derived_hits(&mut hits, &executed_words(&words, pcs));
+                hits
+            }
+            Err(e) => {
+                report.errors.push((name, format!("matcher failed: {e}")));
+                continue;
+            }
+        };
+        let new_sig = canonical_bucket_sig(&sorted_signatures_from_hits(&hits), true);
+
+        let old_ids = old_sig.as_deref().map(sig_ids).unwrap_or_default();
+        let new_ids = sig_ids(&new_sig);
+        let added_bucket_ids = new_ids.difference(&old_ids).map(|s| s.to_string()).collect();
+        let removed_bucket_ids = old_ids.difference(&new_ids).map(|s| s.to_string()).collect();
+
+        let was_interesting =
+            old_sig.as_ref().is_some_and(|sig| !sig.is_empty() && old_seen.insert(sig.clone()));
+        let is_interesting = !new_sig.is_empty() && new_seen.insert(new_sig.clone());
+        let newly_interesting = is_interesting && !was_interesting;
+        if newly_interesting {
+            report.newly_interesting.push(name.clone());
+        }
+        report.entries.push(RebucketEntry {
+            name,
+            old_sig,
+            new_sig,
+            added_bucket_ids,
+            removed_bucket_ids,
+            newly_interesting,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_archived_trace, rebucket_corpus, trace_archive};
+    use crate::trace::semantic_matchers::extend_word_derived_hits;
+    use crate::trace::{semantic, BucketHit};
+    use std::collections::HashMap;
+
+    #[test]
+    fn rebucket_flags_inputs_newly_distinguished_by_the_matcher() {
+        let dir = std::env::temp_dir().join(format!("beak-rebucket-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let old_sig = semantic::program::LEN_1.id;
+        for (name, logs) in [("a.json", 1), ("b.json", 2)] {
+            let archive = serde_json::json!({ "logs": vec![0; logs], "bucket_hits_sig": old_sig });
+            std::fs::write(dir.join(name), archive.to_string()).unwrap();
+        }
+        std::fs::write(dir.join("c.json"), "not json").unwrap();
+
+        // The updated matcher additionally buckets two-log traces.
+        let report = rebucket_corpus(&dir, |logs| {
+            let mut hits = vec![BucketHit::semantic(semantic::program::LEN_1, HashMap::new())];
+            if logs.len() == 2 {
+                hits.push(BucketHit::semantic(semantic::program::LEN_2_4, HashMap::new()));
+            }
+            Ok((hits, Vec::new()))
+        })
+        .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.newly_interesting, vec!["b.json".to_string()]);
+        assert_eq!(report.entries[1].added_bucket_ids, vec![semantic::program::LEN_2_4.id]);
+        assert!(report.entries[0].added_bucket_ids.is_empty());
+
+        let hits = [BucketHit::semantic(semantic::program::LEN_1, HashMap::new())];
+        let archive = trace_archive(vec![serde_json::json!({ "op": 1 })], &hits, &[0x13]);
+        let (logs, sig, words) = parse_archived_trace(archive).unwrap();
+        assert_eq!((logs.len(), sig.as_deref(), words), (1, Some(old_sig), vec![0x13]));
+    }
+
+    #[test]
+    fn rebucket_adds_word_derived_hits_from_the_archived_program() {
+        let dir = std::env::temp_dir().join(format!("beak-rebucket-words-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // `addi x1, x0, 5` three times, then `add x2, x1, x1`.
+        let words = [0x0050_0093, 0x0050_0093, 0x0050_0093, 0x0010_8133];
+        let archive = trace_archive(Vec::new(), &[], &words);
+        std::fs::write(dir.join("a.json"), archive.to_string()).unwrap();
+
+        let report = rebucket_corpus(&dir, |_| Ok((Vec::new(), vec![0, 4, 8, 12]))).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut expected = Vec::new();
+        extend_word_derived_hits(&mut expected, &words);
+        assert!(!expected.is_empty());
+        let added = &report.entries[0].added_bucket_ids;
+        for hit in &expected {
+            assert!(added.contains(&hit.bucket_id), "{} missing from {added:?}", hit.bucket_id);
+        }
+        assert!(added.contains(&semantic::program::REPEATED_RUN_3_4.id.to_string()));
+    }
+}
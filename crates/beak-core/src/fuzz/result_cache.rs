@@ -0,0 +1,69 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Bounded least-recently-used cache of backend results keyed by raw input bytes.
+///
+/// Capacity 0 disables caching. Recency is tracked in a queue, so lookups are O(capacity); this
+/// is negligible next to a backend run.
+#[derive(Debug, Clone)]
+pub struct ResultCache<V> {
+    capacity: usize,
+    entries: HashMap<Vec<u8>, V>,
+    order: VecDeque<Vec<u8>>,
+}
+
+impl<V: Clone> ResultCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, entries: HashMap::new(), order: VecDeque::new() }
+    }
+
+    pub fn get(&mut self, key: &[u8]) -> Option<V> {
+        let value = self.entries.get(key)?.clone();
+        self.touch(key);
+        Some(value)
+    }
+
+    pub fn insert(&mut self, key: Vec<u8>, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key.clone(), value).is_some() {
+            self.touch(&key);
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    fn touch(&mut self, key: &[u8]) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResultCache;
+
+    #[test]
+    fn evicts_least_recently_used_entry() {
+        let mut cache = ResultCache::new(2);
+        cache.insert(vec![1], "a");
+        cache.insert(vec![2], "b");
+        assert_eq!(cache.get(&[1]), Some("a"));
+        cache.insert(vec![3], "c");
+        assert_eq!(cache.get(&[2]), None);
+        assert_eq!(cache.get(&[1]), Some("a"));
+        assert_eq!(cache.get(&[3]), Some("c"));
+
+        let mut disabled = ResultCache::new(0);
+        disabled.insert(vec![1], "a");
+        assert_eq!(disabled.get(&[1]), None);
+    }
+}
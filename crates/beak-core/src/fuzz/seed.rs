@@ -1,6 +1,10 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use serde::{Deserialize, Serialize};
 use serde_json::Map;
 
+use crate::rv32im::instruction::RV32IMInstruction;
+
 pub type Metadata = Map<String, serde_json::Value>;
 
 #[derive(Serialize, Deserialize)]
@@ -15,6 +19,141 @@ impl FuzzingSeed {
     }
 }
 
+/// Why `diagnose_seed_line` rejected a seed line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedRejection {
+    /// The line is not a `FuzzingSeed` JSON object.
+    Parse(String),
+    /// The backend's `is_usable` filter refused the (truncated) words.
+    Unusable,
+    /// A word does not decode as RV32IM.
+    UndecodableWord { index: usize, word: u32 },
+}
+
+impl SeedRejection {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Parse(_) => "parse",
+            Self::Unusable => "unusable",
+            Self::UndecodableWord { .. } => "undecodable_word",
+        }
+    }
+}
+
+impl std::fmt::Display for SeedRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "parse error: {e}"),
+            Self::Unusable => f.write_str("rejected by backend is_usable"),
+            Self::UndecodableWord { index, word } => {
+                write!(f, "word[{index}]=0x{word:08x} does not decode")
+            }
+        }
+    }
+}
+
+/// Per-line report of what the initial-seed loaders would do with a seed.
+pub struct SeedDiagnosis {
+    /// The parsed seed with instructions truncated to `max_instructions` (`None` on parse error).
+    pub seed: Option<FuzzingSeed>,
+    /// Instruction count before truncation.
+    pub original_len: usize,
+    /// Whether each kept word decodes, in order.
+    pub decoded: Vec<bool>,
+    pub usable: bool,
+    /// First failing check in loader order (parse, `is_usable`, decode); `None` if accepted.
+    pub rejection: Option<SeedRejection>,
+}
+
+/// Dry-run the seed loader checks on one JSONL line.
+pub fn diagnose_seed_line(
+    line: &str,
+    max_instructions: usize,
+    is_usable: &dyn Fn(&[u32]) -> bool,
+) -> SeedDiagnosis {
+    let mut seed: FuzzingSeed = match serde_json::from_str(line.trim()) {
+        Ok(seed) => seed,
+        Err(e) => {
+            return SeedDiagnosis {
+                seed: None,
+                original_len: 0,
+                decoded: Vec::new(),
+                usable: false,
+                rejection: Some(SeedRejection::Parse(e.to_string())),
+            };
+        }
+    };
+    let original_len = seed.instructions.len();
+    seed.instructions.truncate(max_instructions);
+    let decoded: Vec<bool> =
+        seed.instructions.iter().map(|w| RV32IMInstruction::from_word(*w).is_ok()).collect();
+    let usable = is_usable(&seed.instructions);
+    let rejection = if !usable {
+        Some(SeedRejection::Unusable)
+    } else {
+        decoded
+            .iter()
+            .position(|ok| !ok)
+            .map(|index| SeedRejection::UndecodableWord { index, word: seed.instructions[index] })
+    };
+    SeedDiagnosis { seed: Some(seed), original_len, decoded, usable, rejection }
+}
+
+/// One-line summary of dropped seed lines (`(line_no, reason)`), grouped by reason kind with up to
+/// five example line numbers each.
+pub fn summarize_dropped_seeds(dropped: &[(usize, SeedRejection)]) -> String {
+    let mut by_kind: BTreeMap<&'static str, Vec<usize>> = BTreeMap::new();
+    for (line_no, rejection) in dropped {
+        by_kind.entry(rejection.kind()).or_default().push(*line_no);
+    }
+    by_kind
+        .iter()
+        .map(|(kind, lines)| {
+            let examples: Vec<String> = lines.iter().take(5).map(|l| l.to_string()).collect();
+            let more = if lines.len() > 5 { ", ..." } else { "" };
+            format!("{kind}={} (lines {}{more})", lines.len(), examples.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Greedy set-cover order over per-seed bucket id sets: repeatedly take the seed adding the most
+/// bucket ids not yet covered (ties: more ids no other seed covers, then the earlier seed). Seeds
+/// that add nothing follow in their original order. Returns a permutation of indices.
+pub fn greedy_unique_bucket_order(bucket_ids: &[BTreeSet<String>]) -> Vec<usize> {
+    let mut owners: BTreeMap<&str, usize> = BTreeMap::new();
+    for ids in bucket_ids {
+        for id in ids {
+            *owners.entry(id.as_str()).or_default() += 1;
+        }
+    }
+    let unique: Vec<usize> = bucket_ids
+        .iter()
+        .map(|ids| ids.iter().filter(|id| owners[id.as_str()] == 1).count())
+        .collect();
+
+    let mut covered: BTreeSet<&str> = BTreeSet::new();
+    let mut picked = vec![false; bucket_ids.len()];
+    let mut order = Vec::with_capacity(bucket_ids.len());
+    loop {
+        let best = (0..bucket_ids.len())
+            .filter(|&i| !picked[i])
+            .map(|i| {
+                let gain = bucket_ids[i].iter().filter(|id| !covered.contains(id.as_str())).count();
+                (gain, unique[i], std::cmp::Reverse(i))
+            })
+            .max();
+        let Some((_, _, std::cmp::Reverse(i))) = best.filter(|&(gain, ..)| gain > 0) else {
+            break;
+        };
+        picked[i] = true;
+        order.push(i);
+        covered.extend(bucket_ids[i].iter().map(String::as_str));
+    }
+    order.extend((0..bucket_ids.len()).filter(|&i| !picked[i]));
+    order
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,4 +169,39 @@ mod tests {
         }
         assert_eq!(count, 2172);
     }
+
+    #[test]
+    fn diagnoses_first_rejection_reason() {
+        let ok = diagnose_seed_line(r#"{"instructions":[19,51],"metadata":{}}"#, 1, &|_| true);
+        assert_eq!(ok.rejection, None);
+        assert_eq!((ok.original_len, ok.decoded.clone()), (2, vec![true]));
+        assert_eq!(ok.seed.map(|s| s.instructions), Some(vec![19]));
+
+        let bad_word = diagnose_seed_line(r#"{"instructions":[19,0],"metadata":{}}"#, 8, &|_| true);
+        assert_eq!(bad_word.decoded, vec![true, false]);
+        assert_eq!(bad_word.rejection, Some(SeedRejection::UndecodableWord { index: 1, word: 0 }));
+
+        let unusable = diagnose_seed_line(r#"{"instructions":[0],"metadata":{}}"#, 8, &|_| false);
+        assert_eq!(unusable.rejection, Some(SeedRejection::Unusable));
+
+        let parse = diagnose_seed_line("{not json", 8, &|_| true);
+        assert!(matches!(parse.rejection, Some(SeedRejection::Parse(_))));
+
+        let summary = summarize_dropped_seeds(&[
+            (3, SeedRejection::Unusable),
+            (7, SeedRejection::Parse("eof".to_string())),
+            (9, SeedRejection::Unusable),
+        ]);
+        assert_eq!(summary, "parse=1 (lines 7); unusable=2 (lines 3, 9)");
+    }
+
+    #[test]
+    fn greedy_order_schedules_unique_coverage_first() {
+        let sets: Vec<BTreeSet<String>> = [&["a"][..], &["a", "b", "c"], &["d"], &["b"], &[]]
+            .iter()
+            .map(|ids| ids.iter().map(|s| s.to_string()).collect())
+            .collect();
+        assert_eq!(greedy_unique_bucket_order(&sets), vec![1, 2, 0, 3, 4]);
+        assert!(greedy_unique_bucket_order(&[]).is_empty());
+    }
 }
@@ -0,0 +1,138 @@
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Worker diagnostic verbosity: `none`, `summary` or `full` (unset = `full`).
+pub const WORKER_VERBOSITY_ENV: &str = "BEAK_WORKER_VERBOSITY";
+
+/// Runs aggregated into one line in `summary` mode (unset or 0 = `DEFAULT_SUMMARY_EVERY`).
+pub const WORKER_SUMMARY_EVERY_ENV: &str = "BEAK_WORKER_SUMMARY_EVERY";
+
+pub const DEFAULT_SUMMARY_EVERY: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerVerbosity {
+    /// No per-run diagnostics at all.
+    None,
+    /// Errors, plus one aggregate line every `summary_every` runs.
+    Summary,
+    /// One timing line per run (the historical behavior).
+    Full,
+}
+
+impl WorkerVerbosity {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" | "quiet" | "0" => Ok(Self::None),
+            "summary" => Ok(Self::Summary),
+            "full" | "" => Ok(Self::Full),
+            other => {
+                Err(format!("unknown worker verbosity {other:?} (expected none|summary|full)"))
+            }
+        }
+    }
+}
+
+/// Per-run diagnostic gate and aggregator for a backend worker process.
+#[derive(Debug, Clone)]
+pub struct WorkerDiagnostics {
+    verbosity: WorkerVerbosity,
+    summary_every: u64,
+    runs: u64,
+    errors: u64,
+    total_ms: u128,
+    max_ms: u128,
+}
+
+impl WorkerDiagnostics {
+    pub fn new(verbosity: WorkerVerbosity, summary_every: u64) -> Self {
+        Self {
+            verbosity,
+            summary_every: if summary_every == 0 { DEFAULT_SUMMARY_EVERY } else { summary_every },
+            runs: 0,
+            errors: 0,
+            total_ms: 0,
+            max_ms: 0,
+        }
+    }
+
+    /// Read `WORKER_VERBOSITY_ENV` and `WORKER_SUMMARY_EVERY_ENV`; an unparseable verbosity falls
+    /// back to `Full` with a warning.
+    pub fn from_env() -> Self {
+        let verbosity = match std::env::var(WORKER_VERBOSITY_ENV) {
+            Ok(v) => WorkerVerbosity::parse(&v).unwrap_or_else(|e| {
+                eprintln!("[beak-worker] {e}; using full");
+                WorkerVerbosity::Full
+            }),
+            Err(_) => WorkerVerbosity::Full,
+        };
+        let every =
+            std::env::var(WORKER_SUMMARY_EVERY_ENV).ok().and_then(|v| v.parse().ok()).unwrap_or(0);
+        Self::new(verbosity, every)
+    }
+
+    pub fn verbosity(&self) -> WorkerVerbosity {
+        self.verbosity
+    }
+
+    /// Whether per-run lines (timings, truncation notices) should be printed.
+    pub fn per_run(&self) -> bool {
+        self.verbosity == WorkerVerbosity::Full
+    }
+
+    /// Whether per-run error lines should be printed.
+    pub fn errors(&self) -> bool {
+        self.verbosity != WorkerVerbosity::None
+    }
+
+    /// Account one finished run. In `Summary` mode, returns the aggregate line for the window
+    /// every `summary_every` runs and starts a new window.
+    pub fn record(&mut self, total_ms: u128, failed: bool) -> Option<String> {
+        if self.verbosity != WorkerVerbosity::Summary {
+            return None;
+        }
+        self.runs += 1;
+        self.errors += u64::from(failed);
+        self.total_ms += total_ms;
+        self.max_ms = self.max_ms.max(total_ms);
+        if self.runs < self.summary_every {
+            return None;
+        }
+        let line = format!(
+            "runs={} errors={} mean_ms={} max_ms={}",
+            self.runs,
+            self.errors,
+            self.total_ms / u128::from(self.runs),
+            self.max_ms
+        );
+        *self = Self::new(self.verbosity, self.summary_every);
+        Some(line)
+    }
+}
+
+/// Process-wide diagnostics for the worker, initialized from the environment on first use.
+pub fn worker_diagnostics() -> MutexGuard<'static, WorkerDiagnostics> {
+    static DIAGNOSTICS: OnceLock<Mutex<WorkerDiagnostics>> = OnceLock::new();
+    DIAGNOSTICS
+        .get_or_init(|| Mutex::new(WorkerDiagnostics::from_env()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WorkerDiagnostics, WorkerVerbosity};
+
+    #[test]
+    fn summary_mode_emits_one_line_per_window() {
+        let mut diag = WorkerDiagnostics::new(WorkerVerbosity::Summary, 3);
+        assert!(!diag.per_run() && diag.errors());
+        assert_eq!(diag.record(10, false), None);
+        assert_eq!(diag.record(20, true), None);
+        assert_eq!(diag.record(30, false).unwrap(), "runs=3 errors=1 mean_ms=20 max_ms=30");
+        assert_eq!(diag.record(5, false), None);
+
+        let mut full = WorkerDiagnostics::new(WorkerVerbosity::Full, 1);
+        assert!(full.per_run() && full.record(1, false).is_none());
+        assert_eq!(WorkerVerbosity::parse("Quiet"), Ok(WorkerVerbosity::None));
+        assert!(WorkerVerbosity::parse("loud").is_err());
+    }
+}
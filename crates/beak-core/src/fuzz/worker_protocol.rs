@@ -0,0 +1,263 @@
+use std::backtrace::Backtrace;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Marks protocol lines on the worker pipes; anything else (e.g. prover logging on stdout) is
+/// ignored by `parse_worker_message`.
+pub const WORKER_MESSAGE_PREFIX: &str = "__BEAK_WORKER_JSON__ ";
+
+/// Marks compressed protocol lines: the same envelope JSON, gzipped and base64-encoded.
+pub const WORKER_COMPRESSED_MESSAGE_PREFIX: &str = "__BEAK_WORKER_GZ__ ";
+
+/// Envelope size in bytes from which a worker compresses its messages (unset = never compress).
+/// Readers accept both framings regardless.
+pub const WORKER_COMPRESS_MIN_BYTES_ENV: &str = "BEAK_WORKER_COMPRESS_MIN_BYTES";
+
+/// Set to capture a backtrace in worker panic reports (off by default; capture is slow).
+pub const WORKER_BACKTRACE_ENV: &str = "BEAK_WORKER_PANIC_BACKTRACE";
+
+/// Bump on any incompatible change to `WorkerMessage` or to a backend's request/response types.
+pub const WORKER_PROTOCOL_VERSION: u32 = 1;
+
+/// One newline-delimited message between a backend host and its worker process.
+///
+/// `Req`/`Resp` are the backend's own request and response payloads, which differ per zkVM
+/// snapshot (e.g. only some carry injection fields).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum WorkerMessage<Req, Resp> {
+    Request(Req),
+    Response(Resp),
+    /// Liveness signal while a long request is still running.
+    Heartbeat {
+        request_id: Option<u64>,
+    },
+    /// Protocol-level failure, e.g. an unparseable request. Per-request backend failures are
+    /// still reported inside the backend's `Response`.
+    Error {
+        request_id: Option<u64>,
+        message: String,
+    },
+    Log {
+        message: String,
+    },
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, Req, Resp> {
+    protocol_version: u32,
+    message: &'a WorkerMessage<Req, Resp>,
+}
+
+#[derive(Deserialize)]
+struct RawEnvelope {
+    protocol_version: Option<u32>,
+    message: Option<Value>,
+}
+
+/// Serialize `msg` as a single prefixed protocol line (without the trailing newline).
+pub fn format_worker_message<Req, Resp>(msg: &WorkerMessage<Req, Resp>) -> Result<String, String>
+where
+    Req: Serialize,
+    Resp: Serialize,
+{
+    let envelope = Envelope { protocol_version: WORKER_PROTOCOL_VERSION, message: msg };
+    let json = serde_json::to_string(&envelope)
+        .map_err(|e| format!("serialize worker message failed: {e}"))?;
+    Ok(format!("{WORKER_MESSAGE_PREFIX}{json}"))
+}
+
+/// Like `format_worker_message`, but emits a `WORKER_COMPRESSED_MESSAGE_PREFIX` line when the
+/// envelope JSON is at least `compress_min_bytes` long (`None` = never compress).
+pub fn format_worker_message_compressed<Req, Resp>(
+    msg: &WorkerMessage<Req, Resp>,
+    compress_min_bytes: Option<usize>,
+) -> Result<String, String>
+where
+    Req: Serialize,
+    Resp: Serialize,
+{
+    let envelope = Envelope { protocol_version: WORKER_PROTOCOL_VERSION, message: msg };
+    let json = serde_json::to_string(&envelope)
+        .map_err(|e| format!("serialize worker message failed: {e}"))?;
+    if compress_min_bytes.is_none_or(|min| json.len() < min) {
+        return Ok(format!("{WORKER_MESSAGE_PREFIX}{json}"));
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder
+        .write_all(json.as_bytes())
+        .and_then(|_| encoder.finish())
+        .map(|gz| {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(gz);
+            format!("{WORKER_COMPRESSED_MESSAGE_PREFIX}{encoded}")
+        })
+        .map_err(|e| format!("compress worker message failed: {e}"))
+}
+
+/// `WORKER_COMPRESS_MIN_BYTES_ENV`, read once per process.
+pub fn worker_compress_min_bytes() -> Option<usize> {
+    static MIN_BYTES: OnceLock<Option<usize>> = OnceLock::new();
+    *MIN_BYTES.get_or_init(|| {
+        std::env::var(WORKER_COMPRESS_MIN_BYTES_ENV).ok().and_then(|v| v.trim().parse().ok())
+    })
+}
+
+/// Parse one line read from a worker pipe.
+///
+/// Returns `None` for lines that are not protocol messages. Compressed lines are decoded first. A
+/// peer speaking a different `WORKER_PROTOCOL_VERSION` (or the unversioned legacy framing) is
+/// reported as such instead of surfacing as an opaque payload parse error.
+pub fn parse_worker_message<Req, Resp>(
+    line: &str,
+) -> Option<Result<WorkerMessage<Req, Resp>, String>>
+where
+    Req: DeserializeOwned,
+    Resp: DeserializeOwned,
+{
+    let line = line.trim();
+    if let Some(encoded) = line.strip_prefix(WORKER_COMPRESSED_MESSAGE_PREFIX.trim_end()) {
+        return Some(
+            decompress_payload(encoded.trim_start()).and_then(|json| parse_payload(&json)),
+        );
+    }
+    let payload = line.strip_prefix(WORKER_MESSAGE_PREFIX.trim_end())?.trim_start();
+    Some(parse_payload(payload))
+}
+
+/// Request the worker is currently serving, so the panic hook can address its report.
+static CURRENT_REQUEST_ID: AtomicU64 = AtomicU64::new(0);
+/// Set by the panic hook once it has reported the current request; cleared per request.
+static PANIC_REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Report worker panics (message, location, optional backtrace) through
+/// `report(request_id, message)`, at most once per request started with `begin_worker_request`.
+///
+/// The hook runs before unwinding, so the report also reaches the parent when the panic cannot be
+/// caught (abort, panic on a helper thread) and the worker dies instead of a bare disconnect.
+pub fn install_worker_panic_hook(report: impl Fn(u64, String) + Send + Sync + 'static) {
+    let capture_backtrace = std::env::var_os(WORKER_BACKTRACE_ENV).is_some();
+    let prev_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        prev_hook(info);
+        if PANIC_REPORTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "unknown location".to_string());
+        let payload = info.payload();
+        let payload = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        let mut msg = format!("worker panic at {location}: {payload}");
+        if capture_backtrace {
+            msg.push_str(&format!("\nbacktrace:\n{}", Backtrace::force_capture()));
+        }
+        report(CURRENT_REQUEST_ID.load(Ordering::SeqCst), msg);
+    }));
+}
+
+/// Address panic reports to `request_id` from now on. Call before serving each request.
+pub fn begin_worker_request(request_id: u64) {
+    CURRENT_REQUEST_ID.store(request_id, Ordering::SeqCst);
+    PANIC_REPORTED.store(false, Ordering::SeqCst);
+}
+
+/// Whether the panic hook already reported the current request, so the caller must not send a
+/// second response for it.
+pub fn worker_panic_reported() -> bool {
+    PANIC_REPORTED.load(Ordering::SeqCst)
+}
+
+fn decompress_payload(encoded: &str) -> Result<String, String> {
+    let gz = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| format!("decode compressed worker message failed: {e}"))?;
+    let mut json = String::new();
+    GzDecoder::new(gz.as_slice())
+        .read_to_string(&mut json)
+        .map_err(|e| format!("decompress worker message failed: {e}"))?;
+    Ok(json)
+}
+
+fn parse_payload<Req, Resp>(payload: &str) -> Result<WorkerMessage<Req, Resp>, String>
+where
+    Req: DeserializeOwned,
+    Resp: DeserializeOwned,
+{
+    let raw: RawEnvelope = serde_json::from_str(payload)
+        .map_err(|e| format!("parse worker message envelope failed: {e}"))?;
+    let (version, message) = match (raw.protocol_version, raw.message) {
+        (Some(version), Some(message)) => (version, message),
+        _ => {
+            return Err(format!(
+                "worker protocol version mismatch: peer sent an unversioned message, \
+                 expected version {WORKER_PROTOCOL_VERSION}"
+            ))
+        }
+    };
+    if version != WORKER_PROTOCOL_VERSION {
+        return Err(format!(
+            "worker protocol version mismatch: peer speaks version {version}, \
+             expected version {WORKER_PROTOCOL_VERSION}"
+        ));
+    }
+    serde_json::from_value(message).map_err(|e| format!("parse worker message failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_worker_message, format_worker_message_compressed, parse_worker_message,
+        WorkerMessage, WORKER_COMPRESSED_MESSAGE_PREFIX,
+    };
+
+    type Msg = WorkerMessage<u32, String>;
+
+    #[test]
+    fn worker_messages_round_trip_and_report_version_mismatch() {
+        let msg: Msg = WorkerMessage::Error { request_id: Some(7), message: "boom".into() };
+        let line = format_worker_message(&msg).unwrap();
+        assert_eq!(parse_worker_message::<u32, String>(&line), Some(Ok(msg)));
+
+        let req = format_worker_message(&Msg::Request(3)).unwrap();
+        assert_eq!(
+            parse_worker_message::<u32, String>(&format!("{req}\n")),
+            Some(Ok(Msg::Request(3)))
+        );
+
+        assert_eq!(parse_worker_message::<u32, String>("prover log line"), None);
+
+        let future = line.replace("\"protocol_version\":1", "\"protocol_version\":2");
+        let err = parse_worker_message::<u32, String>(&future).unwrap().unwrap_err();
+        assert!(err.contains("version 2"), "{err}");
+
+        let legacy = parse_worker_message::<u32, String>("__BEAK_WORKER_JSON__ {\"request_id\":1}");
+        assert!(legacy.unwrap().unwrap_err().contains("unversioned"));
+    }
+
+    #[test]
+    fn compressed_messages_round_trip_above_the_threshold() {
+        let msg = WorkerMessage::<u32, String>::Response("hit;".repeat(500));
+        let small = format_worker_message_compressed(&msg, Some(1 << 20)).unwrap();
+        assert_eq!(small, format_worker_message(&msg).unwrap());
+
+        let line = format_worker_message_compressed(&msg, Some(64)).unwrap();
+        assert!(line.starts_with(WORKER_COMPRESSED_MESSAGE_PREFIX) && line.len() < small.len());
+        assert_eq!(parse_worker_message::<u32, String>(&line), Some(Ok(msg)));
+
+        let corrupt = format!("{WORKER_COMPRESSED_MESSAGE_PREFIX}not-base64!");
+        assert!(parse_worker_message::<u32, String>(&corrupt).unwrap().is_err());
+    }
+}
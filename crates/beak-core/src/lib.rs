@@ -1,3 +1,5 @@
 pub mod fuzz;
 pub mod rv32im;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 pub mod trace;
@@ -65,6 +65,27 @@ impl fmt::Display for RV32IMEncodeError {
 
 impl std::error::Error for RV32IMEncodeError {}
 
+/// Whether `RV32IMInstruction::decode_with_mode` accepts encodings with reserved bits set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Reject nonzero reserved fields (shift-immediate `funct7`, `fence` rd/rs1/fm) and R-type
+    /// words whose `funct7` is not the mnemonic's own.
+    Strict,
+    /// Accept whatever `rrs_lib` decodes, like `decode`.
+    #[default]
+    Lenient,
+}
+
+/// `addi x0, x0, 0`, the canonical RV32I NOP.
+pub const NOP_WORD: u32 = 0x0000_0013;
+
+/// Registers an instruction reads and writes, as encoded (`x0` included).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegEffects {
+    pub reads: Vec<u32>,
+    pub writes: Vec<u32>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RV32IMInstruction {
     pub mnemonic: String,
@@ -107,10 +128,31 @@ impl RV32IMInstruction {
         Self::from_parts(&mnemonic, rd, rs1, rs2, imm)
     }
 
+    /// Encode the decoded fields again. A canonical word reproduces itself; a different result
+    /// means the decoder dropped bits or the encoder disagrees with it.
+    pub fn reencode(&self) -> Result<u32, RV32IMEncodeError> {
+        // The decoder reports U-type immediates already shifted into the upper 20 bits, while
+        // the encoder takes the raw 20-bit field.
+        let imm = match mnemonic_spec(&self.mnemonic) {
+            Some(MnemonicSpec { format: RV32IMFormat::U, .. }) => self.imm.map(|imm| imm >> 12),
+            _ => self.imm,
+        };
+        encode_from_parts(&self.mnemonic, self.rd, self.rs1, self.rs2, imm)
+    }
+
     pub fn decode(word: u32) -> Option<Self> {
         Self::decode_with_pc(word, 0)
     }
 
+    /// `decode`, letting the harness choose whether illegal encodings are inputs or rejects.
+    pub fn decode_with_mode(word: u32, mode: DecodeMode) -> Option<Self> {
+        let insn = Self::decode(word)?;
+        match mode {
+            DecodeMode::Lenient => Some(insn),
+            DecodeMode::Strict => reserved_fields_clear(&insn).then_some(insn),
+        }
+    }
+
     pub fn decode_with_pc(word: u32, pc: u32) -> Option<Self> {
         if let Some(system) = decode_system_instruction(word) {
             return Some(system);
@@ -122,6 +164,40 @@ impl RV32IMInstruction {
         process_instruction(&mut builder, word)
     }
 
+    /// Whether this is the canonical NOP (`NOP_WORD`). Other encodings without architectural
+    /// effect, such as `add x0, x1, x2`, are not NOPs here.
+    pub fn is_nop(&self) -> bool {
+        self.word == NOP_WORD
+    }
+
+    /// Which of rd/rs1/rs2 are live for this instruction's format, e.g. a store reads rs1 and rs2
+    /// and writes nothing, and `lui` writes rd and reads nothing. The rs1 field of `csrr*i` is an
+    /// immediate, and fence/SYSTEM instructions touch no registers through their fields.
+    pub fn reg_effects(&self) -> RegEffects {
+        let (reads_rs1, reads_rs2, writes_rd) = match self.mnemonic.as_str() {
+            "fence" | "fence.i" | "ecall" | "ebreak" | "sret" | "mret" | "wfi" | "system" => {
+                (false, false, false)
+            }
+            "sfence.vma" => (true, true, false),
+            "csrrw" | "csrrs" | "csrrc" => (true, false, true),
+            "csrrwi" | "csrrsi" | "csrrci" | "csrr" => (false, false, true),
+            m => match mnemonic_spec(m).map(|spec| spec.format) {
+                Some(RV32IMFormat::R) => (true, true, true),
+                Some(RV32IMFormat::I) => (true, false, true),
+                Some(RV32IMFormat::S) | Some(RV32IMFormat::B) => (true, true, false),
+                Some(RV32IMFormat::U) | Some(RV32IMFormat::J) => (false, false, true),
+                Some(RV32IMFormat::CSR) | None => (false, false, false),
+            },
+        };
+        let mut reads: Vec<u32> = [(reads_rs1, self.rs1), (reads_rs2, self.rs2)]
+            .into_iter()
+            .filter_map(|(live, reg)| reg.filter(|_| live))
+            .collect();
+        reads.dedup();
+        let writes = self.rd.filter(|_| writes_rd).into_iter().collect();
+        RegEffects { reads, writes }
+    }
+
     pub fn new(
         mnemonic: &'static str,
         word: u32,
@@ -156,6 +232,53 @@ impl<'de> Deserialize<'de> for RV32IMInstruction {
     }
 }
 
+/// Rename registers into first-use order (`x1`, `x2`, ... as reads then writes are met in program
+/// order), so inputs that differ only in their choice of temporaries collapse to one word list.
+///
+/// Every register starts at zero, so a consistent renaming of `x1..x31` preserves semantics up to
+/// a permutation of the final registers; `x0` is never renamed. Programs with undecodable words,
+/// `ecall`/`ebreak` (which read ABI registers implicitly) or CSR instructions are returned as-is.
+pub fn canonicalize_program(words: &[u32]) -> Vec<u32> {
+    let mut decoded = Vec::with_capacity(words.len());
+    for &word in words {
+        match RV32IMInstruction::decode(word) {
+            Some(insn)
+                if !matches!(insn.mnemonic.as_str(), "ecall" | "ebreak")
+                    && !insn.mnemonic.starts_with("csr") =>
+            {
+                decoded.push(insn)
+            }
+            _ => return words.to_vec(),
+        }
+    }
+
+    let mut rename = [None::<u32>; 32];
+    rename[0] = Some(0);
+    let mut next = 1;
+    let mut canonical = |reg: u32| {
+        *rename[reg as usize].get_or_insert_with(|| {
+            next += 1;
+            next - 1
+        })
+    };
+    decoded
+        .iter()
+        .map(|insn| {
+            let effects = insn.reg_effects();
+            let mut word = insn.word;
+            let fields = [(insn.rs1, 15, true), (insn.rs2, 20, true), (insn.rd, 7, false)];
+            for (reg, shift, is_read) in fields {
+                let Some(reg) = reg else { continue };
+                let live = if is_read { &effects.reads } else { &effects.writes };
+                if live.contains(&reg) {
+                    word = (word & !(0x1f << shift)) | (canonical(reg) << shift);
+                }
+            }
+            word
+        })
+        .collect()
+}
+
 fn mnemonic_spec(literal: &str) -> Option<MnemonicSpec> {
     match literal {
         "add" => Some(MnemonicSpec {
@@ -501,6 +624,34 @@ fn mnemonic_spec(literal: &str) -> Option<MnemonicSpec> {
             funct3: 0x0,
             funct7: 0x00,
         }),
+        "sret" => Some(MnemonicSpec {
+            literal: "sret",
+            format: RV32IMFormat::I,
+            opcode: 0x73,
+            funct3: 0x0,
+            funct7: 0x08,
+        }),
+        "mret" => Some(MnemonicSpec {
+            literal: "mret",
+            format: RV32IMFormat::I,
+            opcode: 0x73,
+            funct3: 0x0,
+            funct7: 0x18,
+        }),
+        "wfi" => Some(MnemonicSpec {
+            literal: "wfi",
+            format: RV32IMFormat::I,
+            opcode: 0x73,
+            funct3: 0x0,
+            funct7: 0x08,
+        }),
+        "sfence.vma" => Some(MnemonicSpec {
+            literal: "sfence.vma",
+            format: RV32IMFormat::I,
+            opcode: 0x73,
+            funct3: 0x0,
+            funct7: 0x09,
+        }),
         "csrrw" => Some(MnemonicSpec {
             literal: "csrrw",
             format: RV32IMFormat::CSR,
@@ -577,7 +728,41 @@ fn parse_register(token: &str, field: &'static str) -> Result<u32, RV32IMEncodeE
     Ok(value)
 }
 
-fn parse_immediate(token: &str) -> Result<i32, RV32IMEncodeError> {
+/// Field an asm immediate is encoded into; `parse_immediate` rejects values it cannot hold
+/// instead of letting `encode_from_parts` truncate them.
+#[derive(Debug, Clone, Copy)]
+enum ImmediateWidth {
+    /// Two's-complement field of this many bits, e.g. 12 for I/S-type and 13 for branch offsets.
+    Signed(u32),
+    /// Unsigned field of this many bits (shift amounts).
+    Unsigned(u32),
+    /// Field of this many bits written either signed or as its raw bit pattern (U-type).
+    Either(u32),
+}
+
+impl ImmediateWidth {
+    fn range(self) -> (i64, i64) {
+        match self {
+            Self::Signed(bits) => (-(1 << (bits - 1)), (1 << (bits - 1)) - 1),
+            Self::Unsigned(bits) => (0, (1 << bits) - 1),
+            Self::Either(bits) => (-(1 << (bits - 1)), (1 << bits) - 1),
+        }
+    }
+}
+
+fn parse_immediate(token: &str, width: ImmediateWidth) -> Result<i32, RV32IMEncodeError> {
+    let value = parse_immediate_value(token)?;
+    let (min, max) = width.range();
+    if !(min..=max).contains(&i64::from(value)) {
+        return Err(RV32IMEncodeError::InvalidImmediate(format!(
+            "immediate '{}' out of range: expected {min}..={max}",
+            token.trim()
+        )));
+    }
+    Ok(value)
+}
+
+fn parse_immediate_value(token: &str) -> Result<i32, RV32IMEncodeError> {
     let t = token.trim();
     if let Some(hex) = t.strip_prefix("0x") {
         return i32::from_str_radix(hex, 16).map_err(|_| {
@@ -634,6 +819,17 @@ fn parse_operands(
                     found: count,
                 });
             }
+            if let Some(fixed_imm) = rs1_only_imm(spec.literal) {
+                if count != 1 {
+                    return Err(RV32IMEncodeError::InvalidOperandCount {
+                        mnemonic: spec.literal.to_string(),
+                        expected: "rs1",
+                        found: count,
+                    });
+                }
+                let rs1 = parse_register(&operands[0], "rs1")?;
+                return Ok((Some(0), Some(rs1), None, Some(fixed_imm)));
+            }
             if is_load_or_jalr(spec.literal) {
                 if count != 3 {
                     return Err(RV32IMEncodeError::InvalidOperandCount {
@@ -643,7 +839,7 @@ fn parse_operands(
                     });
                 }
                 let rd = parse_register(&operands[0], "rd")?;
-                let imm = parse_immediate(&operands[1])?;
+                let imm = parse_immediate(&operands[1], ImmediateWidth::Signed(12))?;
                 let rs1 = parse_register(&operands[2], "rs1")?;
                 return Ok((Some(rd), Some(rs1), None, Some(imm)));
             }
@@ -656,7 +852,12 @@ fn parse_operands(
             }
             let rd = parse_register(&operands[0], "rd")?;
             let rs1 = parse_register(&operands[1], "rs1")?;
-            let imm = parse_immediate(&operands[2])?;
+            let width = if is_shift_imm(spec.literal) {
+                ImmediateWidth::Unsigned(5)
+            } else {
+                ImmediateWidth::Signed(12)
+            };
+            let imm = parse_immediate(&operands[2], width)?;
             Ok((Some(rd), Some(rs1), None, Some(imm)))
         }
         RV32IMFormat::S => {
@@ -668,7 +869,7 @@ fn parse_operands(
                 });
             }
             let rs2 = parse_register(&operands[0], "rs2")?;
-            let imm = parse_immediate(&operands[1])?;
+            let imm = parse_immediate(&operands[1], ImmediateWidth::Signed(12))?;
             let rs1 = parse_register(&operands[2], "rs1")?;
             Ok((None, Some(rs1), Some(rs2), Some(imm)))
         }
@@ -682,7 +883,7 @@ fn parse_operands(
             }
             let rs1 = parse_register(&operands[0], "rs1")?;
             let rs2 = parse_register(&operands[1], "rs2")?;
-            let imm = parse_immediate(&operands[2])?;
+            let imm = parse_immediate(&operands[2], ImmediateWidth::Signed(13))?;
             Ok((None, Some(rs1), Some(rs2), Some(imm)))
         }
         RV32IMFormat::U => {
@@ -694,7 +895,7 @@ fn parse_operands(
                 });
             }
             let rd = parse_register(&operands[0], "rd")?;
-            let imm = parse_immediate(&operands[1])?;
+            let imm = parse_immediate(&operands[1], ImmediateWidth::Either(20))?;
             Ok((Some(rd), None, None, Some(imm)))
         }
         RV32IMFormat::J => {
@@ -706,7 +907,7 @@ fn parse_operands(
                 });
             }
             let rd = parse_register(&operands[0], "rd")?;
-            let imm = parse_immediate(&operands[1])?;
+            let imm = parse_immediate(&operands[1], ImmediateWidth::Signed(21))?;
             Ok((Some(rd), None, None, Some(imm)))
         }
         RV32IMFormat::CSR => {
@@ -769,6 +970,8 @@ fn encode_from_parts(
     let f7 = spec.funct7;
     let (rd, rs1, imm) = if let Some(default_imm) = no_operand_imm(spec.literal) {
         (Some(0), Some(0), Some(default_imm))
+    } else if let Some(fixed_imm) = rs1_only_imm(spec.literal) {
+        (Some(0), rs1, Some(fixed_imm))
     } else {
         (rd, rs1, imm)
     };
@@ -866,10 +1069,42 @@ fn no_operand_imm(mnemonic: &str) -> Option<i32> {
         "fence" | "fence.i" => Some(0),
         "ecall" => Some(0),
         "ebreak" => Some(1),
+        "sret" => Some(0x102),
+        "mret" => Some(0x302),
+        "wfi" => Some(0x105),
+        _ => None,
+    }
+}
+
+/// Fixed `imm[11:0]` of SYSTEM instructions whose only operand is `rs1` (`rd` is always x0).
+fn rs1_only_imm(mnemonic: &str) -> Option<i32> {
+    match mnemonic {
+        // funct7 = 0b0001001 with rs2 (asid) = x0, the only form the decoder accepts.
+        "sfence.vma" => Some(0x120),
         _ => None,
     }
 }
 
+/// Strict-decode check for bits the lenient decoder ignores. Fields the decoder already pins
+/// (SYSTEM instructions, `fence.i`) need no check here.
+fn reserved_fields_clear(insn: &RV32IMInstruction) -> bool {
+    let word = insn.word;
+    let funct7 = word >> 25;
+    let Some(spec) = mnemonic_spec(&insn.mnemonic) else {
+        return true;
+    };
+    if spec.format == RV32IMFormat::R || is_shift_imm(spec.literal) {
+        return funct7 == spec.funct7;
+    }
+    if spec.literal == "fence" {
+        let rd = (word >> 7) & 0x1f;
+        let rs1 = (word >> 15) & 0x1f;
+        // fm = 0b1000 is `fence.tso`; other fence modes are reserved.
+        return rd == 0 && rs1 == 0 && matches!(word >> 28, 0x0 | 0x8);
+    }
+    true
+}
+
 fn decode_system_instruction(word: u32) -> Option<RV32IMInstruction> {
     let opcode = word & 0x7f;
     let rd = (word >> 7) & 0x1f;
@@ -1258,3 +1493,97 @@ impl InstructionProcessor for InstructionBuilder {
 
     itype!(process_fence, "fence");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        canonicalize_program, DecodeMode, RV32IMEncodeError, RV32IMInstruction, RegEffects,
+    };
+
+    #[test]
+    fn strict_decode_rejects_reserved_fields_that_lenient_accepts() {
+        let add = RV32IMInstruction::from_asm("add x1, x2, x3").unwrap().word;
+        let srai = RV32IMInstruction::from_asm("srai x1, x2, 3").unwrap().word;
+        let fence_iorw = 0x0ff0_000f;
+        for word in [add, srai, fence_iorw] {
+            assert!(RV32IMInstruction::decode_with_mode(word, DecodeMode::Strict).is_some());
+        }
+
+        // slli with shamt[5] set (an RV64 encoding) and fence with a nonzero rd.
+        let slli_wide = RV32IMInstruction::from_asm("slli x1, x2, 3").unwrap().word | (1 << 25);
+        let fence_rd = fence_iorw | (1 << 7);
+        for word in [slli_wide, fence_rd] {
+            let lenient = RV32IMInstruction::decode_with_mode(word, DecodeMode::Lenient);
+            assert!(lenient.is_some_and(|insn| insn == RV32IMInstruction::decode(word).unwrap()));
+            assert!(RV32IMInstruction::decode_with_mode(word, DecodeMode::Strict).is_none());
+        }
+    }
+
+    #[test]
+    fn reg_effects_follow_the_format() {
+        let effects = |asm: &str| RV32IMInstruction::from_asm(asm).unwrap().reg_effects();
+        let fx = |reads: &[u32], writes: &[u32]| RegEffects {
+            reads: reads.to_vec(),
+            writes: writes.to_vec(),
+        };
+        assert_eq!(effects("add x1, x2, x2"), fx(&[2], &[1]));
+        assert_eq!(effects("sw x5, 4(x6)"), fx(&[6, 5], &[]));
+        assert_eq!(effects("beq x1, x2, 8"), fx(&[1, 2], &[]));
+        assert_eq!(effects("lui x7, 0x1"), fx(&[], &[7]));
+        assert_eq!(effects("lw x3, 0(x4)"), fx(&[4], &[3]));
+        assert_eq!(effects("csrrwi x1, 0x300, 5"), fx(&[], &[1]));
+        assert_eq!(effects("ecall"), fx(&[], &[]));
+    }
+
+    #[test]
+    fn privileged_system_instructions_encode_and_round_trip() {
+        let words: Vec<u32> = ["mret", "sret", "wfi", "sfence.vma x5"]
+            .iter()
+            .map(|asm| RV32IMInstruction::from_asm(asm).unwrap().word)
+            .collect();
+        assert_eq!(words[..3], [0x3020_0073, 0x1020_0073, 0x1050_0073]);
+        for word in words {
+            let insn = RV32IMInstruction::from_word(word).unwrap();
+            assert_eq!(insn.reencode().ok(), Some(word), "{}", insn.mnemonic);
+        }
+    }
+
+    #[test]
+    fn only_the_canonical_encoding_is_a_nop() {
+        let nop = |asm: &str| RV32IMInstruction::from_asm(asm).unwrap().is_nop();
+        assert!(nop("addi x0, x0, 0"));
+        assert!(!nop("addi x0, x0, 1") && !nop("add x0, x0, x0") && !nop("addi x1, x1, 0"));
+    }
+
+    #[test]
+    fn out_of_range_immediates_are_rejected_at_parse_time() {
+        let word = |asm: &str| RV32IMInstruction::from_asm(asm).map(|i| i.word);
+        assert_eq!(word("addi x1, x0, -2048"), Ok(0x8000_0093));
+        assert_eq!(word("lui x1, 0xfffff"), Ok(0xffff_f0b7));
+        assert_eq!(word("srai x1, x2, 31"), Ok(0x41f1_5093));
+        assert_eq!(
+            word("addi x1, x0, 2048"),
+            Err(RV32IMEncodeError::InvalidImmediate(
+                "immediate '2048' out of range: expected -2048..=2047".to_string()
+            ))
+        );
+        for asm in ["slli x1, x1, 32", "sw x1, 4096(x2)", "beq x1, x2, 4096", "lui x1, 0x100000"] {
+            assert!(matches!(word(asm), Err(RV32IMEncodeError::InvalidImmediate(_))), "{asm}");
+        }
+    }
+
+    #[test]
+    fn canonicalize_program_collapses_alpha_equivalent_inputs() {
+        let program = |lines: &[&str]| -> Vec<u32> {
+            lines.iter().map(|l| RV32IMInstruction::from_asm(l).unwrap().word).collect()
+        };
+        let a = program(&["add x5, x6, x7", "sw x5, 4(x0)", "sub x7, x5, x5"]);
+        let b = program(&["add x9, x3, x4", "sw x9, 4(x0)", "sub x4, x9, x9"]);
+        let expected = program(&["add x3, x1, x2", "sw x3, 4(x0)", "sub x2, x3, x3"]);
+        assert_eq!(canonicalize_program(&a), expected);
+        assert_eq!(canonicalize_program(&b), expected);
+
+        let with_ecall = program(&["addi x17, x0, 93", "ecall"]);
+        assert_eq!(canonicalize_program(&with_ecall), with_ecall);
+    }
+}
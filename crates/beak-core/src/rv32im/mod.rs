@@ -1,2 +1,3 @@
 pub mod instruction;
 pub mod oracle;
+pub mod supported;
@@ -1,14 +1,88 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
 use rrs_lib::instruction_executor::{InstructionException, InstructionExecutor};
 use rrs_lib::memories::{MemorySpace, VecMemory};
-use rrs_lib::HartState;
+use rrs_lib::{HartState, MemAccessSize, Memory};
+
+use crate::rv32im::supported::{FencePolicy, FENCE_OPCODE};
 
 const MAX_INSTRUCTIONS: u32 = 1000;
 
+/// How an oracle run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OracleExit {
+    /// Ran off the program or stopped on a fetch/decode/access fault.
+    #[default]
+    Completed,
+    /// Reached the step bound without halting.
+    StepLimit,
+    /// A backward jump revisited a `(pc, registers)` state with no store in between, so the
+    /// program can never terminate. Only reported by runs with loop detection enabled.
+    Looped,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct OracleExecution {
     pub regs: [u32; 32],
     pub steps: u32,
     pub hit_step_limit: bool,
+    pub exit: OracleExit,
+}
+
+/// Sparse final memory: byte address -> byte value, for the addresses a program stored to.
+pub type MemorySnapshot = BTreeMap<u32, u8>;
+
+/// Byte-level differences at the oracle's stored addresses as `(addr, oracle, backend)`.
+///
+/// Addresses the backend did not report are skipped rather than treated as zero.
+pub fn mismatch_memory(oracle: &MemorySnapshot, backend: &MemorySnapshot) -> Vec<(u32, u8, u8)> {
+    oracle
+        .iter()
+        .filter_map(|(&addr, &expected)| match backend.get(&addr) {
+            Some(&actual) if actual != expected => Some((addr, expected, actual)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Memory wrapper that records every byte address written by a successful store.
+struct StoreRecordingMemory {
+    inner: MemorySpace,
+    stored: BTreeSet<u32>,
+    /// Successful stores so far; loop detection only compares states between stores.
+    writes: u64,
+}
+
+impl Memory for StoreRecordingMemory {
+    fn read_mem(&mut self, addr: u32, size: MemAccessSize) -> Option<u32> {
+        self.inner.read_mem(addr, size)
+    }
+
+    fn write_mem(&mut self, addr: u32, size: MemAccessSize, store_data: u32) -> bool {
+        let ok = self.inner.write_mem(addr, size, store_data);
+        if ok {
+            self.writes += 1;
+            let len = match size {
+                MemAccessSize::Byte => 1,
+                MemAccessSize::HalfWord => 2,
+                MemAccessSize::Word => 4,
+            };
+            self.stored.extend((0..len).map(|i| addr.wrapping_add(i)));
+        }
+        ok
+    }
+}
+
+impl StoreRecordingMemory {
+    fn snapshot(&mut self) -> MemorySnapshot {
+        let addrs: Vec<u32> = self.stored.iter().copied().collect();
+        addrs
+            .into_iter()
+            .filter_map(|addr| {
+                self.inner.read_mem(addr, MemAccessSize::Byte).map(|b| (addr, b as u8))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,11 +113,18 @@ pub struct OracleConfig {
     pub code_base: u32,
     /// Size of zero-initialized data RAM region mapped at address 0 in split mode.
     pub data_size_bytes: u32,
+    /// Should match the backend's policy so fences cannot cause spurious mismatches.
+    pub fence_policy: FencePolicy,
 }
 
 impl Default for OracleConfig {
     fn default() -> Self {
-        Self { memory_model: OracleMemoryModel::SharedCodeData, code_base: 0, data_size_bytes: 0 }
+        Self {
+            memory_model: OracleMemoryModel::SharedCodeData,
+            code_base: 0,
+            data_size_bytes: 0,
+            fence_policy: FencePolicy::default(),
+        }
     }
 }
 
@@ -61,6 +142,30 @@ impl RISCVOracle {
         Self::execute_with_step_limit(words, cfg, MAX_INSTRUCTIONS).regs
     }
 
+    /// Like [`Self::execute_with_config`], additionally returning the final bytes at every
+    /// address the program stored to, for differential memory comparison.
+    pub fn execute_with_memory(words: &[u32], cfg: OracleConfig) -> ([u32; 32], MemorySnapshot) {
+        let (exec, memory) = Self::run(words, cfg, MAX_INSTRUCTIONS, false);
+        (exec.regs, memory)
+    }
+
+    /// Like [`Self::execute_with_memory`], keeping the step count and [`OracleExit`] status so
+    /// callers can tell a looping program from one that halted.
+    pub fn execute_detailed(words: &[u32], cfg: OracleConfig) -> (OracleExecution, MemorySnapshot) {
+        Self::run(words, cfg, MAX_INSTRUCTIONS, true)
+    }
+
+    /// [`Self::execute_detailed`] with an explicit max-step bound; `detect_loops` decides whether
+    /// a provably non-terminating program stops early with [`OracleExit::Looped`].
+    pub fn execute_detailed_with_step_limit(
+        words: &[u32],
+        cfg: OracleConfig,
+        max_steps: u32,
+        detect_loops: bool,
+    ) -> (OracleExecution, MemorySnapshot) {
+        Self::run(words, cfg, max_steps, detect_loops)
+    }
+
     /// Execute with configurable memory model and an explicit max-step bound.
     /// Returns registers plus execution metadata so callers can reject likely-infinite loops
     /// before invoking expensive backends.
@@ -69,9 +174,26 @@ impl RISCVOracle {
         cfg: OracleConfig,
         max_steps: u32,
     ) -> OracleExecution {
+        Self::run(words, cfg, max_steps, false).0
+    }
+
+    fn run(
+        words: &[u32],
+        cfg: OracleConfig,
+        max_steps: u32,
+        detect_loops: bool,
+    ) -> (OracleExecution, MemorySnapshot) {
         let mut regs = [0u32; 32];
         if words.is_empty() {
-            return OracleExecution { regs, steps: 0, hit_step_limit: false };
+            return (
+                OracleExecution {
+                    regs,
+                    steps: 0,
+                    hit_step_limit: false,
+                    exit: OracleExit::Completed,
+                },
+                MemorySnapshot::new(),
+            );
         }
 
         let code_len_bytes = (words.len() * 4) as u32;
@@ -111,10 +233,27 @@ impl RISCVOracle {
             }
         }
 
-        let mut executor = InstructionExecutor { hart_state: &mut hart, mem: &mut mem_space };
+        let mut mem = StoreRecordingMemory { inner: mem_space, stored: BTreeSet::new(), writes: 0 };
+        let mut executor = InstructionExecutor { hart_state: &mut hart, mem: &mut mem };
 
         let mut steps = 0u32;
+        let mut exit = OracleExit::Completed;
+        // States reached by backward jumps since the last store; any loop must close through one.
+        let mut loop_heads: HashSet<(u32, [u32; 32])> = HashSet::new();
+        let mut writes_at_heads = 0u64;
         while steps < max_steps {
+            let pc = executor.hart_state.pc;
+            if cfg.fence_policy != FencePolicy::Execute {
+                let word = executor.mem.read_mem(pc, MemAccessSize::Word);
+                if word.is_some_and(|w| w & 0x7f == FENCE_OPCODE) {
+                    if cfg.fence_policy == FencePolicy::Reject {
+                        break;
+                    }
+                    executor.hart_state.pc = pc.wrapping_add(4);
+                    steps += 1;
+                    continue;
+                }
+            }
             match executor.step() {
                 Ok(()) => steps += 1,
                 Err(
@@ -125,12 +264,94 @@ impl RISCVOracle {
                     | InstructionException::AlignmentFault(_),
                 ) => break,
             }
+            let next_pc = executor.hart_state.pc;
+            if detect_loops && next_pc <= pc {
+                if executor.mem.writes != writes_at_heads {
+                    loop_heads.clear();
+                    writes_at_heads = executor.mem.writes;
+                }
+                if !loop_heads.insert((next_pc, executor.hart_state.registers)) {
+                    exit = OracleExit::Looped;
+                    break;
+                }
+            }
+        }
+        if steps >= max_steps {
+            exit = OracleExit::StepLimit;
         }
 
         for i in 0..32 {
             regs[i] = hart.registers[i];
         }
         regs[0] = 0; // x0 is always 0
-        OracleExecution { regs, steps, hit_step_limit: steps >= max_steps }
+        let memory = mem.snapshot();
+        (OracleExecution { regs, steps, hit_step_limit: steps >= max_steps, exit }, memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mismatch_memory, OracleConfig, OracleExit, OracleMemoryModel, RISCVOracle};
+    use crate::rv32im::instruction::RV32IMInstruction;
+    use crate::rv32im::supported::FencePolicy;
+
+    #[test]
+    fn memory_snapshot_records_stored_bytes() {
+        let words: Vec<u32> = ["addi x1, x0, 0x123", "sh x1, 16(x0)"]
+            .iter()
+            .map(|asm| RV32IMInstruction::from_asm(asm).unwrap().word)
+            .collect();
+        let cfg = OracleConfig {
+            memory_model: OracleMemoryModel::SplitCodeData,
+            code_base: 0x1000,
+            data_size_bytes: 64,
+            fence_policy: Default::default(),
+        };
+        let (regs, memory) = RISCVOracle::execute_with_memory(&words, cfg);
+        assert_eq!(regs[1], 0x123);
+        assert_eq!(memory.into_iter().collect::<Vec<_>>(), vec![(16, 0x23), (17, 0x01)]);
+
+        let (_, oracle) = RISCVOracle::execute_with_memory(&words, cfg);
+        let mut backend = oracle.clone();
+        backend.insert(17, 0);
+        assert_eq!(mismatch_memory(&oracle, &backend), vec![(17, 0x01, 0)]);
+    }
+
+    #[test]
+    fn detects_loops_that_revisit_the_same_state() {
+        let assemble = |lines: &[&str]| -> Vec<u32> {
+            lines.iter().map(|asm| RV32IMInstruction::from_asm(asm).unwrap().word).collect()
+        };
+        let exit = |lines: &[&str]| {
+            let cfg = OracleConfig::default();
+            RISCVOracle::execute_detailed_with_step_limit(&assemble(lines), cfg, 1000, true).0.exit
+        };
+        assert_eq!(exit(&["addi x1, x0, 1", "beq x0, x0, 0"]), OracleExit::Looped);
+        assert_eq!(exit(&["addi x1, x0, 1", "jal x2, -4"]), OracleExit::Looped);
+        // The counter changes every iteration, so no state repeats before the step bound.
+        assert_eq!(exit(&["addi x1, x1, 1", "jal x0, -4"]), OracleExit::StepLimit);
+        assert_eq!(exit(&["addi x1, x0, 1", "addi x2, x1, 1"]), OracleExit::Completed);
+        // Plain differential runs do not pay for detection and just hit the bound.
+        let words = assemble(&["addi x1, x0, 1", "beq x0, x0, 0"]);
+        let plain = RISCVOracle::execute_with_step_limit(&words, OracleConfig::default(), 1000);
+        assert_eq!(plain.exit, OracleExit::StepLimit);
+    }
+
+    #[test]
+    fn fence_policy_decides_whether_the_oracle_steps_over_fences() {
+        let words: Vec<u32> = ["addi x1, x0, 1", "fence.i", "addi x2, x0, 2"]
+            .iter()
+            .map(|asm| RV32IMInstruction::from_asm(asm).unwrap().word)
+            .collect();
+        let run = |fence_policy| {
+            RISCVOracle::execute_with_config(
+                &words,
+                OracleConfig { fence_policy, ..Default::default() },
+            )
+        };
+        // rrs-lib has no `fence.i`, so executing it faults like any illegal instruction.
+        assert_eq!(run(FencePolicy::Execute)[2], 0);
+        assert_eq!(run(FencePolicy::Reject)[2], 0);
+        assert_eq!(run(FencePolicy::TreatAsNop)[2], 2);
     }
 }
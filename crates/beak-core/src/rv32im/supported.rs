@@ -0,0 +1,123 @@
+use crate::rv32im::instruction::RV32IMInstruction;
+
+/// Backend-specific instruction subset, matched by major opcode bits (`word & 0x7f`) or by
+/// decoded mnemonic.
+///
+/// An empty allowlist admits every decodable word; the denylist is applied afterwards. Words that
+/// `RV32IMInstruction::from_word` rejects are never supported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupportedOpcodes {
+    pub allow_opcodes: Vec<u32>,
+    pub allow_mnemonics: Vec<String>,
+    pub deny_opcodes: Vec<u32>,
+    pub deny_mnemonics: Vec<String>,
+}
+
+fn parse_entries(spec: &str) -> Result<(Vec<u32>, Vec<String>), String> {
+    let mut opcodes = Vec::new();
+    let mut mnemonics = Vec::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        if let Some(hex) = entry.strip_prefix("0x").or_else(|| entry.strip_prefix("0X")) {
+            let opcode = u32::from_str_radix(hex, 16)
+                .map_err(|e| format!("invalid opcode entry {entry:?}: {e}"))?;
+            if opcode > 0x7f {
+                return Err(format!("opcode entry {entry:?} exceeds 7 bits"));
+            }
+            opcodes.push(opcode);
+        } else {
+            mnemonics.push(entry.to_ascii_lowercase());
+        }
+    }
+    Ok((opcodes, mnemonics))
+}
+
+/// How fences (the MISC-MEM major opcode `0x0f`: `fence`, `fence.tso`, `fence.i`) are handled.
+///
+/// The backend filters seeds with `allows_word`; the oracle mirrors the same choice through
+/// `OracleConfig::fence_policy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FencePolicy {
+    /// Fences are unsupported: the backend rejects them and the oracle stops on one.
+    Reject,
+    /// Fences reach the backend, and the oracle steps over every one of them.
+    TreatAsNop,
+    /// Fences reach the backend and the oracle executes them with its own semantics.
+    #[default]
+    Execute,
+}
+
+pub const FENCE_OPCODE: u32 = 0x0f;
+
+impl FencePolicy {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "nop" | "treat-as-nop" => Ok(Self::TreatAsNop),
+            "execute" => Ok(Self::Execute),
+            other => Err(format!(
+                "invalid fence policy '{other}', expected one of: reject, treat-as-nop, execute"
+            )),
+        }
+    }
+
+    pub fn allows_word(self, word: u32) -> bool {
+        self != Self::Reject || word & 0x7f != FENCE_OPCODE
+    }
+}
+
+impl SupportedOpcodes {
+    /// Parse comma-separated allow/deny lists; entries are `0x`-prefixed major opcodes or
+    /// mnemonics, e.g. `allow = ""`, `deny = "fence,0x73"`.
+    pub fn parse(allow: &str, deny: &str) -> Result<Self, String> {
+        let (allow_opcodes, allow_mnemonics) = parse_entries(allow)?;
+        let (deny_opcodes, deny_mnemonics) = parse_entries(deny)?;
+        Ok(Self { allow_opcodes, allow_mnemonics, deny_opcodes, deny_mnemonics })
+    }
+
+    pub fn allows_word(&self, word: u32) -> bool {
+        let Ok(insn) = RV32IMInstruction::from_word(word) else {
+            return false;
+        };
+        let opcode = word & 0x7f;
+        let matches = |opcodes: &[u32], mnemonics: &[String]| {
+            opcodes.contains(&opcode) || mnemonics.contains(&insn.mnemonic)
+        };
+        let allowed = (self.allow_opcodes.is_empty() && self.allow_mnemonics.is_empty())
+            || matches(&self.allow_opcodes, &self.allow_mnemonics);
+        allowed && !matches(&self.deny_opcodes, &self.deny_mnemonics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FencePolicy, SupportedOpcodes};
+    use crate::rv32im::instruction::RV32IMInstruction;
+
+    #[test]
+    fn allow_and_deny_lists_compose_with_decode() {
+        let add = RV32IMInstruction::from_asm("add x1, x2, x3").unwrap().word;
+        let lw = RV32IMInstruction::from_asm("lw x1, 0(x2)").unwrap().word;
+
+        let all = SupportedOpcodes::default();
+        assert!(all.allows_word(add) && all.allows_word(lw));
+        assert!(!all.allows_word(0xffff_ffff));
+
+        let no_loads = SupportedOpcodes::parse("", "0x03").unwrap();
+        assert!(no_loads.allows_word(add) && !no_loads.allows_word(lw));
+
+        let only_add = SupportedOpcodes::parse("ADD", "").unwrap();
+        assert!(only_add.allows_word(add) && !only_add.allows_word(lw));
+
+        assert!(SupportedOpcodes::parse("0x80", "").is_err());
+    }
+
+    #[test]
+    fn fence_policy_only_rejects_fences_when_asked() {
+        let fence = RV32IMInstruction::from_asm("fence").unwrap().word;
+        let add = RV32IMInstruction::from_asm("add x1, x2, x3").unwrap().word;
+        assert!(!FencePolicy::Reject.allows_word(fence) && FencePolicy::Reject.allows_word(add));
+        assert!(FencePolicy::TreatAsNop.allows_word(fence));
+        assert_eq!(FencePolicy::parse("NOP"), Ok(FencePolicy::TreatAsNop));
+        assert!(FencePolicy::parse("skip").is_err());
+    }
+}
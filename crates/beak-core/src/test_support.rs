@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+
+use crate::fuzz::benchmark::BenchmarkBackend;
+
+/// Run `words` through `backend` once and return the distinct bucket ids it reports.
+///
+/// A backend error (or a failed warm-up) is returned as-is, so golden tests fail on it instead of
+/// comparing an empty bucket set.
+pub fn collect_bucket_ids<B: BenchmarkBackend + ?Sized>(
+    backend: &mut B,
+    words: &[u32],
+) -> Result<BTreeSet<String>, String> {
+    backend.warm_up()?;
+    backend.prepare_for_run(0);
+    let regs = backend.prove_and_read_final_regs(words);
+    let eval = backend.collect_eval();
+    if let Some(err) = eval.backend_error {
+        return Err(err);
+    }
+    regs?;
+    Ok(eval.bucket_hits.into_iter().map(|hit| hit.bucket_id).collect())
+}
+
+/// Describe how `actual` differs from `expected` (order-independent), or `None` if they match.
+pub fn golden_bucket_diff(actual: &BTreeSet<String>, expected: &[&str]) -> Option<String> {
+    let expected: BTreeSet<&str> = expected.iter().copied().collect();
+    let missing: Vec<&str> = expected.iter().copied().filter(|id| !actual.contains(*id)).collect();
+    let unexpected: Vec<&str> =
+        actual.iter().map(String::as_str).filter(|id| !expected.contains(id)).collect();
+    if missing.is_empty() && unexpected.is_empty() {
+        return None;
+    }
+    let mut out = String::from("bucket ids differ from golden set");
+    for id in missing {
+        out.push_str(&format!("\n  - {id}"));
+    }
+    for id in unexpected {
+        out.push_str(&format!("\n  + {id}"));
+    }
+    Some(out)
+}
+
+/// Assert that `words` run through `backend` hits exactly `expected_buckets` (order-independent,
+/// duplicates ignored). On mismatch, panics with the missing (`-`) and unexpected (`+`) ids.
+#[track_caller]
+pub fn assert_trace_matches_golden<B: BenchmarkBackend + ?Sized>(
+    backend: &mut B,
+    words: &[u32],
+    expected_buckets: &[&str],
+) {
+    let actual = collect_bucket_ids(backend, words)
+        .unwrap_or_else(|e| panic!("golden program {words:08x?} failed to run: {e}"));
+    if let Some(diff) = golden_bucket_diff(&actual, expected_buckets) {
+        panic!("golden program {words:08x?}: {diff}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_trace_matches_golden, golden_bucket_diff};
+    use crate::fuzz::benchmark::BenchmarkBackend;
+    use crate::fuzz::loop1::BackendEval;
+    use crate::trace::semantic;
+    use crate::trace::semantic_matchers::match_program_length_semantic_hits;
+
+    /// Reports only the program length bucket of the last program it ran.
+    struct LengthBackend(usize);
+
+    impl BenchmarkBackend for LengthBackend {
+        fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+            self.0 = words.len();
+            Ok([0; 32])
+        }
+
+        fn collect_eval(&mut self) -> BackendEval {
+            BackendEval {
+                bucket_hits: match_program_length_semantic_hits(self.0, None),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn golden_buckets_compare_as_sets_and_diff_on_mismatch() {
+        let mut backend = LengthBackend(0);
+        assert_trace_matches_golden(&mut backend, &[0x13; 3], &[semantic::program::LEN_2_4.id]);
+
+        let actual = [semantic::program::LEN_1.id.to_string()].into();
+        let diff = golden_bucket_diff(&actual, &[semantic::program::LEN_2_4.id]).unwrap();
+        assert!(diff.contains("- sem.program.len_2_4") && diff.contains("+ sem.program.len_1"));
+    }
+}
@@ -0,0 +1,102 @@
+use super::{BucketHit, semantic};
+
+/// Fixed, sorted set of bucket ids that assigns each id a stable bit index.
+///
+/// Bitmaps produced against the same registry are comparable across runs; adding or removing a
+/// bucket shifts the indices of every id sorted after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BucketRegistry {
+    ids: Vec<String>,
+}
+
+impl BucketRegistry {
+    pub fn new<I, S>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut ids: Vec<String> = ids.into_iter().map(Into::into).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        Self { ids }
+    }
+
+    /// Registry over every bucket in `semantic::all_buckets`.
+    pub fn semantic() -> Self {
+        Self::new(semantic::all_buckets().map(|bucket| bucket.id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn index_of(&self, bucket_id: &str) -> Option<usize> {
+        self.ids.binary_search_by(|id| id.as_str().cmp(bucket_id)).ok()
+    }
+
+    pub fn id_at(&self, index: usize) -> Option<&str> {
+        self.ids.get(index).map(String::as_str)
+    }
+
+    /// Bitmap width in bytes (`ceil(len / 8)`).
+    pub fn bitmap_len(&self) -> usize {
+        self.ids.len().div_ceil(8)
+    }
+}
+
+/// Encode hits as a little-endian bitmap: bit `i` lives in byte `i / 8` at position `i % 8`.
+///
+/// Hits whose id is not in the registry are ignored.
+pub fn bucket_hits_to_bitmap(hits: &[BucketHit], registry: &BucketRegistry) -> Vec<u8> {
+    let mut bitmap = vec![0u8; registry.bitmap_len()];
+    for hit in hits {
+        if let Some(index) = registry.index_of(&hit.bucket_id) {
+            bitmap[index / 8] |= 1 << (index % 8);
+        }
+    }
+    bitmap
+}
+
+/// Decode a bitmap produced by `bucket_hits_to_bitmap` back into sorted bucket ids.
+///
+/// Set bits beyond the registry size are ignored.
+pub fn bitmap_to_bucket_ids(bitmap: &[u8], registry: &BucketRegistry) -> Vec<String> {
+    (0..registry.len())
+        .filter(|index| bitmap.get(index / 8).is_some_and(|byte| byte & (1 << (index % 8)) != 0))
+        .filter_map(|index| registry.id_at(index).map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn bitmap_round_trips_registered_hits() {
+        let registry = BucketRegistry::semantic();
+        assert_eq!(registry.len(), semantic::all_buckets().count());
+
+        let hits = vec![
+            BucketHit::semantic(semantic::time::CONNECTOR_CHAIN_BREAK, HashMap::new()),
+            BucketHit::semantic(semantic::alu::IMMEDIATE_LIMB_CONSISTENCY, HashMap::new()),
+            BucketHit::semantic(semantic::time::CONNECTOR_CHAIN_BREAK, HashMap::new()),
+            BucketHit { bucket_id: "sem.unregistered".to_string(), details: HashMap::new() },
+        ];
+        let bitmap = bucket_hits_to_bitmap(&hits, &registry);
+        assert_eq!(bitmap.len(), registry.bitmap_len());
+        assert_eq!(bitmap.iter().map(|byte| byte.count_ones()).sum::<u32>(), 2);
+        assert_eq!(
+            bitmap_to_bucket_ids(&bitmap, &registry),
+            vec![
+                semantic::alu::IMMEDIATE_LIMB_CONSISTENCY.id.to_string(),
+                semantic::time::CONNECTOR_CHAIN_BREAK.id.to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::ops::Range;
+
+use serde_json::{Map, Value};
+
+/// Micro-op envelope types emitted by the backend `fuzzer_utils` crates.
+pub const MICRO_OP_TYPES: &[&str] = &["instruction", "chip_row", "interaction"];
+
+fn object_at<'a>(value: &'a Value, path: &str) -> Result<&'a Map<String, Value>, String> {
+    value.as_object().ok_or_else(|| format!("{path}: expected object"))
+}
+
+fn field_at<'a>(obj: &'a Map<String, Value>, path: &str, key: &str) -> Result<&'a Value, String> {
+    obj.get(key).ok_or_else(|| format!("{path}.{key}: missing"))
+}
+
+fn require_u64(obj: &Map<String, Value>, path: &str, key: &str) -> Result<(), String> {
+    field_at(obj, path, key)?
+        .as_u64()
+        .map(|_| ())
+        .ok_or_else(|| format!("{path}.{key}: expected unsigned integer"))
+}
+
+fn require_str<'a>(obj: &'a Map<String, Value>, path: &str, key: &str) -> Result<&'a str, String> {
+    field_at(obj, path, key)?.as_str().ok_or_else(|| format!("{path}.{key}: expected string"))
+}
+
+/// A tagged payload (`{ "type": ..., "data": {...} }`) must be tagged with the row/bus kind.
+fn check_payload_kind(data: &Map<String, Value>, path: &str, kind: &str) -> Result<(), String> {
+    let payload = field_at(data, path, "payload")?;
+    let payload_path = format!("{path}.payload");
+    let payload = object_at(payload, &payload_path)?;
+    if let Some(tag) = payload.get("type") {
+        let tag = tag.as_str().ok_or_else(|| format!("{payload_path}.type: expected string"))?;
+        if tag != kind {
+            return Err(format!("{payload_path}.type: \"{tag}\" does not match kind \"{kind}\""));
+        }
+        object_at(field_at(payload, &payload_path, "data")?, &format!("{payload_path}.data"))?;
+    }
+    Ok(())
+}
+
+/// Check the shape of one emitted micro-op envelope before typed parsing.
+///
+/// Validates the `type` tag, the required base fields (`seq`, `step_idx`, `op_idx`, and for
+/// interactions `row_id`/`direction`/`kind`), and that a tagged payload matches the declared kind.
+/// Errors name the offending JSON path, e.g. `$.data.base.step_idx: missing`.
+pub fn validate_micro_op_envelope(value: &Value) -> Result<(), String> {
+    let root = object_at(value, "$")?;
+    let ty = require_str(root, "$", "type")?;
+    let data = object_at(field_at(root, "$", "data")?, "$.data")?;
+
+    match ty {
+        "instruction" => {
+            require_u64(data, "$.data", "seq")?;
+            require_u64(data, "$.data", "step_idx")?;
+            require_u64(data, "$.data", "pc")?;
+            require_u64(data, "$.data", "opcode")?;
+        }
+        "chip_row" => {
+            let base = object_at(field_at(data, "$.data", "base")?, "$.data.base")?;
+            require_u64(base, "$.data.base", "seq")?;
+            require_u64(base, "$.data.base", "step_idx")?;
+            require_u64(base, "$.data.base", "op_idx")?;
+            let kind = require_str(data, "$.data", "kind")?;
+            check_payload_kind(data, "$.data", kind)?;
+        }
+        "interaction" => {
+            let base = object_at(field_at(data, "$.data", "base")?, "$.data.base")?;
+            require_u64(base, "$.data.base", "seq")?;
+            require_u64(base, "$.data.base", "step_idx")?;
+            require_u64(base, "$.data.base", "op_idx")?;
+            require_str(base, "$.data.base", "row_id")?;
+            let direction = require_str(base, "$.data.base", "direction")?;
+            if direction != "send" && direction != "receive" {
+                return Err(format!(
+                    "$.data.base.direction: expected \"send\" or \"receive\", got \"{direction}\""
+                ));
+            }
+            let kind = require_str(base, "$.data.base", "kind")?;
+            check_payload_kind(data, "$.data", kind)?;
+        }
+        other => {
+            return Err(format!(
+                "$.type: unknown micro-op type \"{other}\" (expected one of {MICRO_OP_TYPES:?})"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The `seq` of a micro-op envelope (`$.data.seq` for instructions, `$.data.base.seq` otherwise).
+pub fn micro_op_seq(value: &Value) -> Option<u64> {
+    let data = value.get("data")?;
+    let seq = match value.get("type")?.as_str()? {
+        "instruction" => data.get("seq")?,
+        _ => data.get("base")?.get("seq")?,
+    };
+    seq.as_u64()
+}
+
+/// The `step_idx` of a micro-op envelope, read from the same place as `micro_op_seq`.
+pub fn micro_op_step_idx(value: &Value) -> Option<u64> {
+    let data = value.get("data")?;
+    let step_idx = match value.get("type")?.as_str()? {
+        "instruction" => data.get("step_idx")?,
+        _ => data.get("base")?.get("step_idx")?,
+    };
+    step_idx.as_u64()
+}
+
+/// Group an emitted micro-op stream into `op_spans`: `spans[step]` is the index range of the
+/// micro-ops tagged with that `step_idx`, and steps without micro-ops get an empty range.
+///
+/// Fails when an envelope has no readable `step_idx` or a step's micro-ops are not contiguous
+/// (a step reappears after a later one began), since such a stream has no span representation.
+pub fn build_op_spans_from_step_idx(logs: &[Value]) -> Result<Vec<Range<usize>>, String> {
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    for (idx, log) in logs.iter().enumerate() {
+        let step =
+            micro_op_step_idx(log).ok_or_else(|| format!("log[{idx}]: missing step_idx"))? as usize;
+        if step + 1 < spans.len() {
+            return Err(format!(
+                "log[{idx}]: step_idx={step} follows step_idx={}",
+                spans.len() - 1
+            ));
+        }
+        if step + 1 == spans.len() {
+            spans[step].end = idx + 1;
+        } else {
+            spans.resize(step, idx..idx);
+            spans.push(idx..idx + 1);
+        }
+    }
+    Ok(spans)
+}
+
+/// Check that `seq` strictly increases across the emitted micro-op stream.
+///
+/// `fuzzer_utils` bumps `seq` on every emission, so a repeated or decreasing value means a
+/// double emission or a misplaced per-run reset. Envelopes without a readable `seq` are left to
+/// `validate_micro_op_envelope`.
+pub fn validate_seq_monotonic(logs: &[Value]) -> Result<(), String> {
+    let mut prev: Option<u64> = None;
+    for (idx, log) in logs.iter().enumerate() {
+        let Some(seq) = micro_op_seq(log) else { continue };
+        if let Some(prev) = prev.filter(|&prev| seq <= prev) {
+            return Err(format!("log[{idx}]: SeqNonMonotonic: seq={seq} follows seq={prev}"));
+        }
+        prev = Some(seq);
+    }
+    Ok(())
+}
+
+/// A structural inconsistency in a parsed trace, reported by a backend's invariant check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceInvariantViolation {
+    /// Name of the violated invariant, e.g. `SeqNonMonotonic`.
+    pub invariant: &'static str,
+    pub detail: String,
+}
+
+impl std::fmt::Display for TraceInvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.invariant, self.detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{build_op_spans_from_step_idx, validate_micro_op_envelope, validate_seq_monotonic};
+
+    #[test]
+    fn accepts_well_formed_envelopes() {
+        let insn = json!({
+            "type": "instruction",
+            "data": { "seq": 0, "step_idx": 0, "pc": 0, "opcode": 512, "operands": [0, 0, 0, 0, 0, 0, 0] }
+        });
+        let row = json!({
+            "type": "chip_row",
+            "data": {
+                "base": { "seq": 1, "step_idx": 0, "op_idx": 0, "is_valid": true },
+                "kind": "base_alu",
+                "payload": { "type": "base_alu", "data": { "op": 0 } }
+            }
+        });
+        let ia = json!({
+            "type": "interaction",
+            "data": {
+                "base": {
+                    "seq": 2, "step_idx": 0, "op_idx": 0, "row_id": "step0_row0",
+                    "direction": "send", "kind": "execution"
+                },
+                "payload": { "type": "execution", "data": { "pc": 0, "timestamp": 1 } }
+            }
+        });
+        for env in [insn, row, ia] {
+            assert_eq!(validate_micro_op_envelope(&env), Ok(()));
+        }
+    }
+
+    #[test]
+    fn reports_path_of_first_problem() {
+        let missing_step = json!({
+            "type": "chip_row",
+            "data": {
+                "base": { "seq": 1, "op_idx": 0 },
+                "kind": "base_alu",
+                "payload": { "type": "base_alu", "data": {} }
+            }
+        });
+        assert_eq!(
+            validate_micro_op_envelope(&missing_step),
+            Err("$.data.base.step_idx: missing".to_string())
+        );
+
+        let wrong_payload = json!({
+            "type": "chip_row",
+            "data": {
+                "base": { "seq": 1, "step_idx": 0, "op_idx": 0 },
+                "kind": "base_alu",
+                "payload": { "type": "shift", "data": {} }
+            }
+        });
+        let err = validate_micro_op_envelope(&wrong_payload).unwrap_err();
+        assert!(err.starts_with("$.data.payload.type:"), "{err}");
+
+        let unknown = json!({ "type": "bogus", "data": {} });
+        assert!(validate_micro_op_envelope(&unknown).unwrap_err().starts_with("$.type:"));
+    }
+
+    #[test]
+    fn rejects_repeated_or_decreasing_seq() {
+        let insn = |seq: u64| json!({ "type": "instruction", "data": { "seq": seq } });
+        let row = |seq: u64| json!({ "type": "chip_row", "data": { "base": { "seq": seq } } });
+        assert_eq!(validate_seq_monotonic(&[insn(0), row(1), insn(2)]), Ok(()));
+        assert_eq!(
+            validate_seq_monotonic(&[insn(0), row(1), row(1)]),
+            Err("log[2]: SeqNonMonotonic: seq=1 follows seq=1".to_string())
+        );
+        assert!(validate_seq_monotonic(&[insn(3), insn(0)]).is_err());
+    }
+
+    #[test]
+    fn op_spans_group_micro_ops_by_step() {
+        let insn = |step: u64| json!({ "type": "instruction", "data": { "step_idx": step } });
+        let row =
+            |step: u64| json!({ "type": "chip_row", "data": { "base": { "step_idx": step } } });
+        let spans = build_op_spans_from_step_idx(&[insn(0), row(0), insn(2), row(2)]).unwrap();
+        assert_eq!(spans, vec![0..2, 2..2, 2..4]);
+        assert!(build_op_spans_from_step_idx(&[insn(1), insn(0)]).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+/// Order in which a backend emits value limbs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// Limb 0 is the least significant (OpenVM).
+    #[default]
+    Little,
+    /// Limb 0 is the most significant.
+    Big,
+}
+
+/// Compose limbs of `limb_bits` bits each, ordered per `endianness`, into a `u64`.
+///
+/// Returns `None` (and logs why) when `limb_bits` is 0 or > 8, the total width exceeds 64 bits,
+/// or a limb does not fit in `limb_bits`.
+pub fn limbs_to_u64(limbs: &[u8], limb_bits: usize, endianness: Endianness) -> Option<u64> {
+    if limb_bits == 0 || limb_bits > 8 {
+        eprintln!("[trace][limbs] invalid limb_bits={limb_bits}");
+        return None;
+    }
+    if limbs.len() * limb_bits > 64 {
+        eprintln!("[trace][limbs] {} limbs x {limb_bits} bits exceed 64 bits", limbs.len());
+        return None;
+    }
+    let mut value = 0u64;
+    for (i, &limb) in limbs.iter().enumerate() {
+        if u32::from(limb) >> limb_bits != 0 {
+            eprintln!("[trace][limbs] limb[{i}]={limb:#x} does not fit in {limb_bits} bits");
+            return None;
+        }
+        let position = match endianness {
+            Endianness::Little => i,
+            Endianness::Big => limbs.len() - 1 - i,
+        };
+        value |= u64::from(limb) << (position * limb_bits);
+    }
+    Some(value)
+}
+
+/// Checked 32-bit variant of [`limbs_to_u64`]: the limbs must cover exactly 32 bits.
+pub fn limbs_to_u32(limbs: &[u8], limb_bits: usize, endianness: Endianness) -> Option<u32> {
+    if limbs.len() * limb_bits != 32 {
+        eprintln!("[trace][limbs] {} limbs x {limb_bits} bits is not a 32-bit value", limbs.len());
+        return None;
+    }
+    limbs_to_u64(limbs, limb_bits, endianness).map(|v| v as u32)
+}
+
+/// Final RV32 registers from a register address space where `x{i}` occupies the little-endian
+/// bytes at offsets `4 * i .. 4 * i + 4`.
+///
+/// `read_word_bytes(offset)` returns the four bytes at `offset`. Backends differ only in how they
+/// fetch those bytes, so the byte order lives here and nowhere else.
+pub fn read_final_regs_from_memory(mut read_word_bytes: impl FnMut(u32) -> [u8; 4]) -> [u32; 32] {
+    std::array::from_fn(|i| u32::from_le_bytes(read_word_bytes(i as u32 * 4)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Endianness, limbs_to_u32, limbs_to_u64, read_final_regs_from_memory};
+
+    #[test]
+    fn composes_little_endian_and_rejects_bad_limbs() {
+        let le = Endianness::Little;
+        assert_eq!(limbs_to_u32(&[0x78, 0x56, 0x34, 0x12], 8, le), Some(0x1234_5678));
+        assert_eq!(limbs_to_u64(&[0b11, 0b01], 2, le), Some(0b0111));
+        assert_eq!(limbs_to_u32(&[0x78, 0x56, 0x34], 8, le), None);
+        assert_eq!(limbs_to_u64(&[0b100], 2, le), None);
+        assert_eq!(limbs_to_u64(&[0; 9], 8, le), None);
+    }
+
+    #[test]
+    fn big_endian_reverses_limb_significance() {
+        let limbs = [0x12, 0x34, 0x56, 0x78];
+        assert_eq!(limbs_to_u32(&limbs, 8, Endianness::Big), Some(0x1234_5678));
+        assert_eq!(limbs_to_u32(&limbs, 8, Endianness::Little), Some(0x7856_3412));
+    }
+
+    #[test]
+    fn reads_registers_as_little_endian_words() {
+        let mut memory = [0u8; 128];
+        memory[4..8].copy_from_slice(&[0x78, 0x56, 0x34, 0x12]);
+        memory[124..128].copy_from_slice(&[0xff, 0, 0, 0x80]);
+        let regs = read_final_regs_from_memory(|offset| {
+            let offset = offset as usize;
+            memory[offset..offset + 4].try_into().unwrap()
+        });
+        assert_eq!(regs[0], 0);
+        assert_eq!(regs[1], 0x1234_5678);
+        assert_eq!(regs[31], 0x8000_00ff);
+    }
+}
@@ -1,14 +1,25 @@
+pub mod coverage;
+pub mod envelope;
+pub mod limbs;
 pub mod observations;
 pub mod semantic;
 pub mod semantic_matchers;
 
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 const EMPTY_TRACE_SIGNALS: [TraceSignal; 0] = [];
 
+/// Hits per bucket id whose details a trace retains (unset = unbounded); see
+/// `BucketHitCollector`.
+pub const MAX_DETAILS_PER_BUCKET_ENV: &str = "BEAK_MAX_DETAILS_PER_BUCKET";
+
+/// Details key on the last retained hit of a capped bucket: how many further hits were dropped.
+pub const SUPPRESSED_HITS_KEY: &str = "suppressed_hits";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TraceSignal {
     HasLoad,
@@ -88,6 +99,122 @@ pub fn sorted_signatures_from_hits(hits: &[BucketHit]) -> Vec<String> {
     ordered.into_iter().map(|h| h.signature().to_string()).collect()
 }
 
+/// Collects bucket hits under a per-bucket details cap that is applied as hits are produced.
+///
+/// Once a bucket has `max_per_bucket` retained hits (at least one), further hits of it only bump
+/// a counter and their details are never built; `into_hits` reports the count under
+/// `SUPPRESSED_HITS_KEY` on the bucket's last retained hit. Every bucket stays present for the
+/// signature while memory stays bounded on adversarial inputs.
+#[derive(Debug, Default)]
+pub struct BucketHitCollector {
+    hits: Vec<BucketHit>,
+    max_per_bucket: Option<usize>,
+    /// Per bucket id: index of its last retained hit, retained count, suppressed count.
+    buckets: HashMap<String, (usize, usize, u64)>,
+}
+
+impl BucketHitCollector {
+    /// `None` retains every hit.
+    pub fn new(max_per_bucket: Option<usize>) -> Self {
+        Self { max_per_bucket: max_per_bucket.map(|max| max.max(1)), ..Self::default() }
+    }
+
+    /// A collector capped at `max_details_per_bucket()`.
+    pub fn bounded() -> Self {
+        Self::new(max_details_per_bucket())
+    }
+
+    /// Whether the next hit of `bucket_id` is retained; if not, it and the `suppressed` hits it
+    /// stands for are counted against the bucket.
+    fn admit(&mut self, bucket_id: &str, suppressed: u64) -> bool {
+        let Some(max) = self.max_per_bucket else {
+            return true;
+        };
+        match self.buckets.get_mut(bucket_id) {
+            Some((_, retained, dropped)) if *retained >= max => {
+                *dropped += 1 + suppressed;
+                false
+            }
+            _ => true,
+        }
+    }
+
+    fn retain(&mut self, hit: BucketHit, suppressed: u64) {
+        if self.max_per_bucket.is_some() {
+            let idx = self.hits.len();
+            let (last, retained, dropped) =
+                self.buckets.entry(hit.bucket_id.clone()).or_insert((idx, 0, 0));
+            *last = idx;
+            *retained += 1;
+            *dropped += suppressed;
+        }
+        self.hits.push(hit);
+    }
+
+    /// Record a hit of `bucket`, building its details only if the cap retains it.
+    pub fn push_with(
+        &mut self,
+        bucket: semantic::SemanticBucket,
+        details: impl FnOnce() -> HashMap<String, Value>,
+    ) {
+        if self.admit(bucket.id, 0) {
+            self.retain(BucketHit::semantic(bucket, details()), 0);
+        }
+    }
+
+    /// Record an already built hit. A `SUPPRESSED_HITS_KEY` count it carries (from an earlier
+    /// collector) is folded into its bucket's count.
+    pub fn push(&mut self, mut hit: BucketHit) {
+        if self.max_per_bucket.is_none() {
+            self.hits.push(hit);
+            return;
+        }
+        let suppressed =
+            hit.details.remove(SUPPRESSED_HITS_KEY).and_then(|v| v.as_u64()).unwrap_or(0);
+        if self.admit(&hit.bucket_id, suppressed) {
+            self.retain(hit, suppressed);
+        }
+    }
+
+    pub fn into_hits(mut self) -> Vec<BucketHit> {
+        for (last, _, suppressed) in self.buckets.into_values() {
+            if suppressed > 0 {
+                self.hits[last]
+                    .details
+                    .insert(SUPPRESSED_HITS_KEY.to_string(), Value::from(suppressed));
+            }
+        }
+        self.hits
+    }
+}
+
+impl Extend<BucketHit> for BucketHitCollector {
+    fn extend<I: IntoIterator<Item = BucketHit>>(&mut self, hits: I) {
+        for hit in hits {
+            self.push(hit);
+        }
+    }
+}
+
+/// Apply `BucketHitCollector`'s cap to hits gathered from several matchers, each of which
+/// already bounded its own output. `None` leaves `hits` untouched.
+pub fn bound_bucket_hit_details(hits: &mut Vec<BucketHit>, max_per_bucket: Option<usize>) {
+    if max_per_bucket.is_none() {
+        return;
+    }
+    let mut collector = BucketHitCollector::new(max_per_bucket);
+    collector.extend(hits.drain(..));
+    *hits = collector.into_hits();
+}
+
+/// `MAX_DETAILS_PER_BUCKET_ENV`, read once per process.
+pub fn max_details_per_bucket() -> Option<usize> {
+    static MAX: OnceLock<Option<usize>> = OnceLock::new();
+    *MAX.get_or_init(|| {
+        std::env::var(MAX_DETAILS_PER_BUCKET_ENV).ok().and_then(|v| v.trim().parse().ok())
+    })
+}
+
 pub fn sorted_signatures_from_signals(signals: &[TraceSignal]) -> Vec<String> {
     let mut ordered: Vec<String> = signals.iter().map(|signal| signal.id().to_string()).collect();
     ordered.sort_unstable();
@@ -107,3 +234,59 @@ pub trait Trace {
         &EMPTY_TRACE_SIGNALS
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BucketHit, BucketHitCollector, SUPPRESSED_HITS_KEY, bound_bucket_hit_details, semantic,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn bounded_details_keep_every_bucket_and_count_the_rest() {
+        let hit = |bucket, step: u64| {
+            BucketHit::semantic(bucket, HashMap::from([("step_idx".to_string(), step.into())]))
+        };
+        let mut hits = vec![
+            hit(semantic::program::LEN_1, 0),
+            hit(semantic::program::LEN_2_4, 1),
+            hit(semantic::program::LEN_1, 2),
+            hit(semantic::program::LEN_1, 3),
+        ];
+        let unbounded = hits.len();
+        bound_bucket_hit_details(&mut hits, None);
+        assert_eq!(hits.len(), unbounded);
+
+        bound_bucket_hit_details(&mut hits, Some(0));
+        let ids: Vec<_> = hits.iter().map(BucketHit::signature).collect();
+        assert_eq!(ids, vec![semantic::program::LEN_1.id, semantic::program::LEN_2_4.id]);
+        assert_eq!(hits[0].details["step_idx"], 0);
+        assert_eq!(hits[0].details[SUPPRESSED_HITS_KEY], 2);
+        assert!(!hits[1].details.contains_key(SUPPRESSED_HITS_KEY));
+    }
+
+    #[test]
+    fn collector_builds_no_details_past_the_cap_and_merges_earlier_counts() {
+        let mut built = 0;
+        let mut hits = BucketHitCollector::new(Some(2));
+        for step in 0..5u64 {
+            hits.push_with(semantic::program::LEN_1, || {
+                built += 1;
+                HashMap::from([("step_idx".to_string(), step.into())])
+            });
+        }
+        assert_eq!(built, 2);
+
+        // A hit already capped elsewhere counts itself plus the hits it stood for.
+        let earlier = HashMap::from([(SUPPRESSED_HITS_KEY.to_string(), 4.into())]);
+        hits.push(BucketHit::semantic(semantic::program::LEN_1, earlier));
+        hits.push(BucketHit::semantic(semantic::program::LEN_2_4, HashMap::new()));
+
+        let hits = hits.into_hits();
+        assert_eq!(hits.len(), 3);
+        assert!(!hits[0].details.contains_key(SUPPRESSED_HITS_KEY));
+        assert_eq!(hits[1].details["step_idx"], 1);
+        assert_eq!(hits[1].details[SUPPRESSED_HITS_KEY], 3 + 5);
+        assert!(!hits[2].details.contains_key(SUPPRESSED_HITS_KEY));
+    }
+}
@@ -54,6 +54,15 @@ pub struct XorMultiplicityObservation {
     pub rhs: u32,
 }
 
+/// One range-check lookup `(value, max_bits)` on the variable range checker bus.
+#[derive(Debug, Clone)]
+pub struct RangeCheckObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub value: u32,
+    pub max_bits: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct AuipcPcLimbObservation {
     pub step_idx: u64,
@@ -85,6 +94,18 @@ pub struct MemoryImmediateSignObservation {
     pub needs_write: bool,
 }
 
+#[derive(Debug, Clone)]
+pub struct EffectivePtrObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub chip_name: String,
+    pub op: u32,
+    pub rs1_val: u32,
+    /// Sign-extended immediate.
+    pub imm: i32,
+    pub effective_ptr: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct MemoryAddressSpaceObservation {
     pub step_idx: u64,
@@ -105,6 +126,41 @@ pub struct BoundaryOriginObservation {
     pub is_terminate: bool,
 }
 
+#[derive(Debug, Clone)]
+pub struct ConnectorObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub chip_name: String,
+    pub from_pc: u32,
+    pub to_pc: u32,
+    pub from_timestamp: Option<u32>,
+    pub to_timestamp: Option<u32>,
+    pub is_terminate: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct JalLuiObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub chip_name: String,
+    pub is_jal: bool,
+    pub needs_write: bool,
+    pub from_pc: u32,
+    pub imm: u32,
+    /// Value written to `rd`, recomposed from the row's `rd_data` limbs.
+    pub rd_value: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgramRowObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub row_id: String,
+    pub execution_frequency: u32,
+    /// Number of interactions anchored to this row via its row id.
+    pub anchored_interactions: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct TimestampedLoadPathObservation {
     pub step_idx: u64,
@@ -132,12 +188,25 @@ pub struct ArithmeticSpecialCaseObservation {
     pub rs2: u32,
 }
 
+/// One `mul`/`mulh`/`mulhsu`/`mulhu` row with its operand and result values.
+#[derive(Debug, Clone)]
+pub struct MulProductObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub mnemonic: String,
+    pub rs1: u32,
+    pub rs2: u32,
+    pub result: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct ZeroRegisterWriteObservation {
     pub op_idx: u64,
     pub pc: u64,
     pub raw_word: u32,
     pub mnemonic: String,
+    /// Value the row wrote for rd, when the trace records it.
+    pub rd_data: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -167,3 +236,60 @@ pub struct EcallInsnObservation {
     pub raw_word: u32,
     pub mnemonic: String,
 }
+
+/// A store row's data limbs: `write_data` should be `prev_data` with the low `size_bytes` of
+/// `read_data` (the stored register) merged in at the byte offset `effective_ptr % 4`.
+#[derive(Debug, Clone)]
+pub struct StoreMergeObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub chip_name: String,
+    pub size_bytes: u8,
+    pub effective_ptr: u32,
+    pub read_data: Vec<u8>,
+    pub prev_data: Vec<u32>,
+    pub write_data: Vec<u32>,
+}
+
+/// A sign-extending load row (`loadb`/`loadh`): the width flags select the loaded limbs from
+/// `shifted_read_data`, and `data_most_sig_bit` is the sign bit extended into the upper limbs.
+#[derive(Debug, Clone)]
+pub struct LoadSignExtendObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub chip_name: String,
+    pub rd_ptr: u32,
+    pub effective_ptr: u32,
+    pub needs_write: bool,
+    pub shifted_read_data: Vec<u8>,
+    pub data_most_sig_bit: bool,
+    pub shift_most_sig_bit: bool,
+    pub opcode_loadh_flag: bool,
+    pub opcode_loadb_flag1: bool,
+    pub opcode_loadb_flag0: bool,
+}
+
+/// A timestamped interaction next to the timestamps of the execution chip row it is anchored to
+/// (`row_id`). `step_next_timestamp` is the row's instruction's end timestamp, when known.
+#[derive(Debug, Clone)]
+pub struct InteractionTimestampObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub kind: String,
+    pub row_id: String,
+    pub timestamp: u32,
+    pub row_timestamp: u32,
+    pub step_next_timestamp: Option<u32>,
+}
+
+/// An execution chip row next to the chip kind its step's opcode should be dispatched to. Kinds
+/// are the backend's own row-kind names (e.g. `base_alu`).
+#[derive(Debug, Clone)]
+pub struct OpcodeDispatchObservation {
+    pub step_idx: u64,
+    pub op_idx: u64,
+    pub chip_name: String,
+    pub opcode: u64,
+    pub expected_kind: String,
+    pub row_kind: String,
+}
@@ -7,6 +7,7 @@ pub enum SemanticBucketCategory {
     Interaction,
     Lookup,
     Memory,
+    Program,
     Row,
     Time,
 }
@@ -36,6 +37,26 @@ pub mod alu {
         "semantic.alu.immediate_limb_consistency",
         SemanticBucketCategory::Alu,
     );
+    pub const IMM_VALUE_BOUNDARY_12BIT_MAX: SemanticBucket = SemanticBucket::new(
+        "sem.alu.imm_value_boundary_12bit_max",
+        "semantic.alu.imm_value_boundary_12bit_max",
+        SemanticBucketCategory::Alu,
+    );
+    pub const IMM_VALUE_BOUNDARY_12BIT_MIN: SemanticBucket = SemanticBucket::new(
+        "sem.alu.imm_value_boundary_12bit_min",
+        "semantic.alu.imm_value_boundary_12bit_min",
+        SemanticBucketCategory::Alu,
+    );
+    pub const IMM_VALUE_BOUNDARY_POW2: SemanticBucket = SemanticBucket::new(
+        "sem.alu.imm_value_boundary_pow2",
+        "semantic.alu.imm_value_boundary_pow2",
+        SemanticBucketCategory::Alu,
+    );
+    pub const IMM_VALUE_BOUNDARY_SIGN_EDGE: SemanticBucket = SemanticBucket::new(
+        "sem.alu.imm_value_boundary_sign_edge",
+        "semantic.alu.imm_value_boundary_sign_edge",
+        SemanticBucketCategory::Alu,
+    );
 }
 
 pub mod arithmetic {
@@ -47,6 +68,14 @@ pub mod arithmetic {
         SemanticBucketCategory::Arithmetic,
     );
 
+    /// A `mul` row and a `mulh*` row on the same operands whose low and high words do not form
+    /// the 64-bit product.
+    pub const MUL_MULH_PRODUCT_INCONSISTENT: SemanticBucket = SemanticBucket::new(
+        "sem.arithmetic.mul_mulh_product_inconsistent",
+        "semantic.arithmetic.mul_mulh_product_inconsistent",
+        SemanticBucketCategory::Arithmetic,
+    );
+
     pub const SPECIAL_CASE_CONSISTENCY: SemanticBucket = SemanticBucket::new(
         "sem.arithmetic.special_case_consistency",
         "semantic.arithmetic.special_case_consistency",
@@ -74,17 +103,83 @@ pub mod control {
         "semantic.control.ecall_argument_decomposition",
         SemanticBucketCategory::Control,
     );
+
+    pub const JAL_LUI_RD_MISMATCH: SemanticBucket = SemanticBucket::new(
+        "sem.control.jal_lui_rd_mismatch",
+        "semantic.control.jal_lui_rd_mismatch",
+        SemanticBucketCategory::Control,
+    );
+
+    /// Phantom discriminants of the `PHANTOM_SUB_KIND` family: OpenVM's `SysPhantom` (0..=3)
+    /// and `Rv32Phantom` (0x20..=0x23). `PHANTOM_SUB_KINDS[i]` names `PHANTOM_DISCRIMINANTS[i]`.
+    pub const PHANTOM_DISCRIMINANTS: [u32; 8] = [0x00, 0x01, 0x02, 0x03, 0x20, 0x21, 0x22, 0x23];
+
+    macro_rules! phantom_sub_kinds {
+        ($($kind:literal),* $(,)?) => {
+            /// Sub-kind names of the `PHANTOM_SUB_KIND` family, in index order. A discriminant
+            /// not in `PHANTOM_DISCRIMINANTS` maps to the trailing `other`.
+            pub const PHANTOM_SUB_KINDS: [&str; 9] = [$($kind),*];
+
+            /// `PHANTOM_SUB_KIND[i]`: the trace executes a phantom of sub-kind
+            /// `PHANTOM_SUB_KINDS[i]`, so hint and debug phantoms are told apart.
+            pub const PHANTOM_SUB_KIND: [SemanticBucket; 9] = [$(SemanticBucket::new(
+                concat!("sem.control.phantom_sub_kind.", $kind),
+                "semantic.control.phantom_sub_kind",
+                SemanticBucketCategory::Control,
+            )),*];
+        };
+    }
+
+    phantom_sub_kinds!(
+        "nop",
+        "debug_panic",
+        "ct_start",
+        "ct_end",
+        "hint_input",
+        "print_str",
+        "hint_random",
+        "hint_load_by_key",
+        "other",
+    );
 }
 
 pub mod decode {
     use super::{SemanticBucket, SemanticBucketCategory};
 
+    /// `lui rd, hi` immediately followed by `addi rd, rd, lo`, the usual two-instruction
+    /// constant materialization that backends may fuse or constrain specially.
+    pub const LUI_ADDI_FUSION_SEEN: SemanticBucket = SemanticBucket::new(
+        "sem.decode.lui_addi_fusion_seen",
+        "semantic.decode.lui_addi_fusion_seen",
+        SemanticBucketCategory::Decode,
+    );
+
+    /// An execution chip row whose kind is not the chip its step's opcode dispatches to (e.g. a
+    /// `mul` step proved by the base ALU chip).
+    pub const OPCODE_CHIP_KIND_MISMATCH: SemanticBucket = SemanticBucket::new(
+        "sem.decode.opcode_chip_kind_mismatch",
+        "semantic.decode.opcode_chip_dispatch",
+        SemanticBucketCategory::Decode,
+    );
+
     pub const OPERAND_INDEX_ROUTING: SemanticBucket = SemanticBucket::new(
         "sem.decode.operand_index_routing",
         "semantic.decode.operand_index_routing",
         SemanticBucketCategory::Decode,
     );
 
+    pub const OPERAND_NON_CANONICAL: SemanticBucket = SemanticBucket::new(
+        "sem.decode.operand_non_canonical",
+        "semantic.decode.operand_non_canonical",
+        SemanticBucketCategory::Decode,
+    );
+
+    pub const OPERAND_OUT_OF_FIELD: SemanticBucket = SemanticBucket::new(
+        "sem.decode.operand_out_of_field",
+        "semantic.decode.operand_out_of_field",
+        SemanticBucketCategory::Decode,
+    );
+
     pub const RD_BIT_DECOMPOSITION: SemanticBucket = SemanticBucket::new(
         "sem.decode.rd_bit_decomposition",
         "semantic.decode.rd_bit_decomposition",
@@ -102,16 +197,73 @@ pub mod decode {
         "semantic.decode.zero_register_immutability",
         SemanticBucketCategory::Decode,
     );
+
+    /// A write to x0 that left a nonzero value (row `rd_data` or final x0), unlike the mostly
+    /// benign no-op writes counted by `ZERO_REGISTER_IMMUTABILITY`.
+    pub const ZERO_REGISTER_WRITE_EFFECTFUL: SemanticBucket = SemanticBucket::new(
+        "sem.decode.zero_register_write_effectful",
+        "semantic.decode.zero_register_write_effectful",
+        SemanticBucketCategory::Decode,
+    );
 }
 
 pub mod interaction {
     use super::{SemanticBucket, SemanticBucketCategory};
 
+    /// A bus with both sends and receives whose signed multiplicities do not sum to zero.
+    pub const BUS_IMBALANCE: SemanticBucket = SemanticBucket::new(
+        "sem.interaction.bus_imbalance",
+        "semantic.interaction.bus_balance",
+        SemanticBucketCategory::Interaction,
+    );
+
     pub const DIGEST_KIND_ROUTE: SemanticBucket = SemanticBucket::new(
         "sem.interaction.digest_kind_route",
         "semantic.interaction.digest_kind_route",
         SemanticBucketCategory::Interaction,
     );
+
+    /// Interaction kinds (buses) of the `KIND_SEEN` family, in index order. A kind a backend
+    /// emits that is not listed maps to the trailing `other`.
+    pub const INTERACTION_KINDS: [&str; 11] = [
+        "execution",
+        "program",
+        "memory",
+        "range_check",
+        "bitwise",
+        "lookup",
+        "poseidon2",
+        "keccak",
+        "sha256",
+        "syscall",
+        "other",
+    ];
+
+    macro_rules! kind_seen {
+        ($($kind:literal),*) => {
+            [$(SemanticBucket::new(
+                concat!("sem.interaction.kind_seen.", $kind),
+                "semantic.interaction.kind_seen",
+                SemanticBucketCategory::Interaction,
+            )),*]
+        };
+    }
+
+    /// `KIND_SEEN[i]`: the trace has an interaction of kind `INTERACTION_KINDS[i]`. Coverage for
+    /// every bus, including those without bespoke buckets yet.
+    pub const KIND_SEEN: [SemanticBucket; 11] = kind_seen!(
+        "execution",
+        "program",
+        "memory",
+        "range_check",
+        "bitwise",
+        "lookup",
+        "poseidon2",
+        "keccak",
+        "sha256",
+        "syscall",
+        "other"
+    );
 }
 
 pub mod lookup {
@@ -123,6 +275,22 @@ pub mod lookup {
         SemanticBucketCategory::Lookup,
     );
 
+    /// A `(value, max_bits)` range-check lookup with `max_bits == 32` and the value's high bit
+    /// set: trivially in range, but where a backend may under-range a value meant for a narrower
+    /// check.
+    pub const RANGE_CHECK_FULL_WIDTH_HIGH_BIT: SemanticBucket = SemanticBucket::new(
+        "sem.lookup.range_check_full_width_high_bit",
+        "semantic.lookup.range_check_bound",
+        SemanticBucketCategory::Lookup,
+    );
+
+    /// A `(value, max_bits)` range-check lookup with `max_bits < 32` and `value >= 1 << max_bits`.
+    pub const RANGE_CHECK_VALUE_OUT_OF_RANGE: SemanticBucket = SemanticBucket::new(
+        "sem.lookup.range_check_value_out_of_range",
+        "semantic.lookup.range_check_bound",
+        SemanticBucketCategory::Lookup,
+    );
+
     pub const XOR_MULTIPLICITY_CONSISTENCY: SemanticBucket = SemanticBucket::new(
         "sem.lookup.xor_multiplicity_consistency",
         "semantic.lookup.multiplicity_consistency",
@@ -139,6 +307,13 @@ pub mod memory {
         SemanticBucketCategory::Memory,
     );
 
+    /// A load/store row whose `effective_ptr` is not `rs1_val + sign_extend(imm)`.
+    pub const EFFECTIVE_PTR_MISMATCH: SemanticBucket = SemanticBucket::new(
+        "sem.memory.effective_ptr_mismatch",
+        "semantic.memory.effective_ptr_consistency",
+        SemanticBucketCategory::Memory,
+    );
+
     pub const IMMEDIATE_SIGN_CONSISTENCY: SemanticBucket = SemanticBucket::new(
         "sem.memory.immediate_sign_consistency",
         "semantic.memory.immediate_sign_consistency",
@@ -151,12 +326,26 @@ pub mod memory {
         SemanticBucketCategory::Memory,
     );
 
+    /// A `loadb`/`loadh` row whose width flags, sign bit or shift selection contradict the
+    /// sign-extended result its `shifted_read_data` implies.
+    pub const LOAD_SIGN_EXTEND_RESULT_MISMATCH: SemanticBucket = SemanticBucket::new(
+        "sem.memory.load_sign_extend_result_mismatch",
+        "semantic.memory.load_sign_extend_consistency",
+        SemanticBucketCategory::Memory,
+    );
+
     pub const STORE_LOAD_PAYLOAD_FLOW: SemanticBucket = SemanticBucket::new(
         "sem.memory.store_load_payload_flow",
         "semantic.memory.write_payload_flow_consistency",
         SemanticBucketCategory::Memory,
     );
 
+    pub const STORE_WRITE_DATA_INCONSISTENT: SemanticBucket = SemanticBucket::new(
+        "sem.memory.store_write_data_inconsistent",
+        "semantic.memory.store_write_merge_consistency",
+        SemanticBucketCategory::Memory,
+    );
+
     pub const TIMESTAMPED_LOAD_PATH: SemanticBucket = SemanticBucket::new(
         "sem.memory.timestamped_load_path",
         "semantic.memory.timestamped_load_path_consistency",
@@ -176,6 +365,109 @@ pub mod memory {
     );
 }
 
+pub mod program {
+    use super::{SemanticBucket, SemanticBucketCategory};
+
+    /// Coarse opcode classes used by the `OPCODE_BIGRAMS` family, in index order.
+    pub const OPCODE_CLASSES: [&str; 8] =
+        ["alu", "branch", "load", "store", "mul", "div", "jump", "system"];
+
+    macro_rules! opcode_bigram_row {
+        ($prev:literal) => {
+            opcode_bigram_row!(
+                $prev; "alu", "branch", "load", "store", "mul", "div", "jump", "system"
+            )
+        };
+        ($prev:literal; $($cur:literal),*) => {
+            [$(SemanticBucket::new(
+                concat!("sem.program.opcode_bigram.", $prev, ".", $cur),
+                "semantic.program.opcode_bigram",
+                SemanticBucketCategory::Program,
+            )),*]
+        };
+    }
+
+    /// `OPCODE_BIGRAMS[prev][cur]`: consecutive executed instructions of classes
+    /// `OPCODE_CLASSES[prev]` then `OPCODE_CLASSES[cur]`.
+    pub const OPCODE_BIGRAMS: [[SemanticBucket; 8]; 8] = [
+        opcode_bigram_row!("alu"),
+        opcode_bigram_row!("branch"),
+        opcode_bigram_row!("load"),
+        opcode_bigram_row!("store"),
+        opcode_bigram_row!("mul"),
+        opcode_bigram_row!("div"),
+        opcode_bigram_row!("jump"),
+        opcode_bigram_row!("system"),
+    ];
+
+    pub const LEN_1: SemanticBucket = SemanticBucket::new(
+        "sem.program.len_1",
+        "semantic.program.instruction_count",
+        SemanticBucketCategory::Program,
+    );
+
+    pub const LEN_2_4: SemanticBucket = SemanticBucket::new(
+        "sem.program.len_2_4",
+        "semantic.program.instruction_count",
+        SemanticBucketCategory::Program,
+    );
+
+    pub const LEN_5_16: SemanticBucket = SemanticBucket::new(
+        "sem.program.len_5_16",
+        "semantic.program.instruction_count",
+        SemanticBucketCategory::Program,
+    );
+
+    pub const LEN_17_64: SemanticBucket = SemanticBucket::new(
+        "sem.program.len_17_64",
+        "semantic.program.instruction_count",
+        SemanticBucketCategory::Program,
+    );
+
+    pub const LEN_65_PLUS: SemanticBucket = SemanticBucket::new(
+        "sem.program.len_65_plus",
+        "semantic.program.instruction_count",
+        SemanticBucketCategory::Program,
+    );
+    /// Longest run of identical consecutive instruction words, a typical mutation artifact.
+    /// Low value for coverage; tracked so the corpus share of such inputs can be measured.
+    pub const REPEATED_RUN_3_4: SemanticBucket = SemanticBucket::new(
+        "sem.program.repeated_run_3_4",
+        "semantic.program.repeated_instruction_run",
+        SemanticBucketCategory::Program,
+    );
+    pub const REPEATED_RUN_5_16: SemanticBucket = SemanticBucket::new(
+        "sem.program.repeated_run_5_16",
+        "semantic.program.repeated_instruction_run",
+        SemanticBucketCategory::Program,
+    );
+    pub const REPEATED_RUN_17_PLUS: SemanticBucket = SemanticBucket::new(
+        "sem.program.repeated_run_17_plus",
+        "semantic.program.repeated_instruction_run",
+        SemanticBucketCategory::Program,
+    );
+    pub const SEGMENTS_1: SemanticBucket = SemanticBucket::new(
+        "sem.program.segments_1",
+        "semantic.program.segment_count",
+        SemanticBucketCategory::Program,
+    );
+    pub const SEGMENTS_2: SemanticBucket = SemanticBucket::new(
+        "sem.program.segments_2",
+        "semantic.program.segment_count",
+        SemanticBucketCategory::Program,
+    );
+    pub const SEGMENTS_3_PLUS: SemanticBucket = SemanticBucket::new(
+        "sem.program.segments_3_plus",
+        "semantic.program.segment_count",
+        SemanticBucketCategory::Program,
+    );
+    pub const ZERO_FREQUENCY_ACTIVE_ROW: SemanticBucket = SemanticBucket::new(
+        "sem.program.zero_frequency_active_row",
+        "semantic.program.zero_frequency_active_row",
+        SemanticBucketCategory::Program,
+    );
+}
+
 pub mod row {
     use super::{SemanticBucket, SemanticBucketCategory};
 
@@ -194,33 +486,105 @@ pub mod time {
         "semantic.time.boundary_origin_consistency",
         SemanticBucketCategory::Time,
     );
+    pub const CONNECTOR_CHAIN_BREAK: SemanticBucket = SemanticBucket::new(
+        "sem.time.connector_chain_break",
+        "semantic.time.connector_chain_break",
+        SemanticBucketCategory::Time,
+    );
+    /// A timestamped interaction falls outside the time window of the execution chip row it is
+    /// anchored to: before the row's timestamp, or after its instruction's next timestamp.
+    pub const INTERACTION_ROW_TIMESTAMP_MISMATCH: SemanticBucket = SemanticBucket::new(
+        "sem.time.interaction_row_timestamp_mismatch",
+        "semantic.time.interaction_row_timestamp",
+        SemanticBucketCategory::Time,
+    );
 }
 
 pub const ALL_BUCKETS: &[SemanticBucket] = &[
     alu::IMMEDIATE_LIMB_CONSISTENCY,
+    alu::IMM_VALUE_BOUNDARY_12BIT_MAX,
+    alu::IMM_VALUE_BOUNDARY_12BIT_MIN,
+    alu::IMM_VALUE_BOUNDARY_POW2,
+    alu::IMM_VALUE_BOUNDARY_SIGN_EDGE,
     arithmetic::DIVISION_REMAINDER_BOUND,
+    arithmetic::MUL_MULH_PRODUCT_INCONSISTENT,
     arithmetic::SPECIAL_CASE_CONSISTENCY,
     control::AUIPC_PC_LIMB_CONSISTENCY,
     control::ECALL_ARGUMENT_DECOMPOSITION,
     control::ECALL_NEXT_PC,
+    control::JAL_LUI_RD_MISMATCH,
+    decode::LUI_ADDI_FUSION_SEEN,
+    decode::OPCODE_CHIP_KIND_MISMATCH,
     decode::OPERAND_INDEX_ROUTING,
+    decode::OPERAND_NON_CANONICAL,
+    decode::OPERAND_OUT_OF_FIELD,
     decode::RD_BIT_DECOMPOSITION,
     decode::UPPER_IMMEDIATE_MATERIALIZATION,
     decode::ZERO_REGISTER_IMMUTABILITY,
+    decode::ZERO_REGISTER_WRITE_EFFECTFUL,
+    interaction::BUS_IMBALANCE,
     interaction::DIGEST_KIND_ROUTE,
     lookup::BOOLEAN_MULTIPLICITY,
+    lookup::RANGE_CHECK_FULL_WIDTH_HIGH_BIT,
+    lookup::RANGE_CHECK_VALUE_OUT_OF_RANGE,
     lookup::XOR_MULTIPLICITY_CONSISTENCY,
     memory::ADDRESS_SPACE_CONSISTENCY,
+    memory::EFFECTIVE_PTR_MISMATCH,
     memory::IMMEDIATE_SIGN_CONSISTENCY,
     memory::KIND_SELECTOR_CONSISTENCY,
+    memory::LOAD_SIGN_EXTEND_RESULT_MISMATCH,
     memory::STORE_LOAD_PAYLOAD_FLOW,
+    memory::STORE_WRITE_DATA_INCONSISTENT,
     memory::TIMESTAMPED_LOAD_PATH,
     memory::VOLATILE_BOUNDARY_RANGE,
     memory::WRITE_PAYLOAD_CONSISTENCY,
+    program::LEN_1,
+    program::LEN_17_64,
+    program::LEN_2_4,
+    program::LEN_5_16,
+    program::LEN_65_PLUS,
+    program::REPEATED_RUN_17_PLUS,
+    program::REPEATED_RUN_3_4,
+    program::REPEATED_RUN_5_16,
+    program::SEGMENTS_1,
+    program::SEGMENTS_2,
+    program::SEGMENTS_3_PLUS,
+    program::ZERO_FREQUENCY_ACTIVE_ROW,
     row::PADDING_INTERACTION_SEND,
     time::BOUNDARY_ORIGIN_CONSISTENCY,
+    time::CONNECTOR_CHAIN_BREAK,
+    time::INTERACTION_ROW_TIMESTAMP_MISMATCH,
+];
+
+/// Every registered bucket: `ALL_BUCKETS` followed by the generated `program::OPCODE_BIGRAMS`,
+/// `interaction::KIND_SEEN` and `control::PHANTOM_SUB_KIND`.
+pub fn all_buckets() -> impl Iterator<Item = SemanticBucket> {
+    ALL_BUCKETS
+        .iter()
+        .chain(program::OPCODE_BIGRAMS.iter().flatten())
+        .chain(interaction::KIND_SEEN.iter())
+        .chain(control::PHANTOM_SUB_KIND.iter())
+        .copied()
+}
+
+/// Id templates of the generated bucket families, with `{param}` placeholders for the parts
+/// that vary (e.g. `OPCODE_CLASSES` entries).
+pub const BUCKET_FAMILY_TEMPLATES: &[&str] = &[
+    "sem.program.opcode_bigram.{prev}.{cur}",
+    "sem.interaction.kind_seen.{kind}",
+    "sem.control.phantom_sub_kind.{kind}",
 ];
 
+/// Every id a backend can emit, sorted, followed by `BUCKET_FAMILY_TEMPLATES`.
+///
+/// Lets tooling document the bucket space and check that observed ids are a subset of it.
+pub fn all_bucket_ids() -> Vec<String> {
+    let mut ids: Vec<String> = all_buckets().map(|bucket| bucket.id.to_string()).collect();
+    ids.sort_unstable();
+    ids.extend(BUCKET_FAMILY_TEMPLATES.iter().map(|template| template.to_string()));
+    ids
+}
+
 pub fn by_id(id: &str) -> Option<SemanticBucket> {
-    ALL_BUCKETS.iter().copied().find(|bucket| bucket.id == id)
+    all_buckets().find(|bucket| bucket.id == id)
 }
@@ -1,16 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::OnceLock;
 
 use serde_json::{Value, json};
 
+use crate::rv32im::instruction::RV32IMInstruction;
 use crate::trace::observations::{
     ArithmeticSpecialCaseObservation, AuipcPcLimbObservation, BoundaryOriginObservation,
-    DivisionInsnObservation, EcallInsnObservation, ImmediateLimbObservation,
-    MemoryAddressSpaceObservation, MemoryImmediateSignObservation, MemoryWriteObservation,
-    RdBitDecompositionObservation, SequenceInsnObservation, SequenceSemanticMatcherProfile,
+    ConnectorObservation, DivisionInsnObservation, EcallInsnObservation, EffectivePtrObservation,
+    ImmediateLimbObservation, InteractionTimestampObservation, JalLuiObservation,
+    LoadSignExtendObservation, MemoryAddressSpaceObservation, MemoryImmediateSignObservation,
+    MemoryWriteObservation, MulProductObservation, OpcodeDispatchObservation,
+    ProgramRowObservation, RangeCheckObservation, RdBitDecompositionObservation,
+    SequenceInsnObservation, SequenceSemanticMatcherProfile, StoreMergeObservation,
     TimestampedLoadPathObservation, UpperImmediateInsnObservation, VolatileBoundaryObservation,
     XorMultiplicityObservation, ZeroRegisterWriteObservation,
 };
-use crate::trace::{BucketHit, TraceSignal, semantic};
+use crate::trace::{BucketHit, BucketHitCollector, TraceSignal, semantic};
 
 fn details_kv(kvs: &[(&str, Value)]) -> HashMap<String, Value> {
     let mut out = HashMap::new();
@@ -168,31 +173,93 @@ pub fn match_sequence_semantic_hits(
 pub fn match_upper_immediate_semantic_hits(
     instructions: &[UpperImmediateInsnObservation],
 ) -> Vec<BucketHit> {
-    instructions
-        .iter()
-        .map(|insn| {
-            BucketHit::semantic(
-                semantic::decode::UPPER_IMMEDIATE_MATERIALIZATION,
-                details_kv(&[
-                    ("op_idx", json!(insn.op_idx)),
-                    ("pc", json!(insn.pc)),
-                    ("raw_word", json!(insn.raw_word)),
-                    ("rd", json!((insn.raw_word >> 7) & 0x1f)),
-                    ("u_imm20", json!((insn.raw_word >> 12) & 0x000f_ffff)),
-                    ("semantic_family", json!("upper_immediate")),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for insn in instructions {
+        hits.push_with(semantic::decode::UPPER_IMMEDIATE_MATERIALIZATION, || {
+            details_kv(&[
+                ("op_idx", json!(insn.op_idx)),
+                ("pc", json!(insn.pc)),
+                ("raw_word", json!(insn.raw_word)),
+                ("rd", json!((insn.raw_word >> 7) & 0x1f)),
+                ("u_imm20", json!((insn.raw_word >> 12) & 0x000f_ffff)),
+                ("semantic_family", json!("upper_immediate")),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
-pub fn match_memory_write_semantic_hits(observations: &[MemoryWriteObservation]) -> Vec<BucketHit> {
+/// A `lui rd, hi` immediately followed by `addi rd, rd, lo` (with `rd != x0`) in the executed
+/// `(step_idx, word)` stream. Reports the materialized constant so a later check can compare it
+/// against the oracle.
+pub fn match_lui_addi_fusion_semantic_hits(
+    instructions: impl IntoIterator<Item = (u64, u32)>,
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    let mut prev = None;
+    for (step_idx, addi) in instructions {
+        let Some((lui_step_idx, lui)) = prev.replace((step_idx, addi)) else {
+            continue;
+        };
+        let lui_rd = (lui >> 7) & 0x1f;
+        let is_lui = lui & 0x7f == 0x37 && lui_rd != 0;
+        let is_addi = addi & 0x7f == 0x13 && (addi >> 12) & 0x7 == 0;
+        if !is_lui || !is_addi || (addi >> 7) & 0x1f != lui_rd || (addi >> 15) & 0x1f != lui_rd {
+            continue;
+        }
+        let hi = lui & 0xffff_f000;
+        let lo = (addi as i32) >> 20;
+        let constant = hi.wrapping_add(lo as u32);
+        hits.push_with(semantic::decode::LUI_ADDI_FUSION_SEEN, || {
+            details_kv(&[
+                ("step_idx", json!(lui_step_idx)),
+                ("rd", json!(lui_rd)),
+                ("lui_word", json!(format!("0x{lui:08x}"))),
+                ("addi_word", json!(format!("0x{addi:08x}"))),
+                ("constant", json!(format!("0x{constant:08x}"))),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Range-check lookups whose value does not fit the claimed width (`max_bits < 32`), and the
+/// full-width boundary (`max_bits >= 32` with bit 31 set). Each bucket fires at most once.
+pub fn match_range_check_semantic_hits(observations: &[RangeCheckObservation]) -> Vec<BucketHit> {
     let mut hits = Vec::new();
+    let mut seen = HashSet::<&'static str>::new();
+    for obs in observations {
+        let bucket = if obs.max_bits >= 32 {
+            if obs.value & 0x8000_0000 == 0 {
+                continue;
+            }
+            semantic::lookup::RANGE_CHECK_FULL_WIDTH_HIGH_BIT
+        } else if obs.value >> obs.max_bits != 0 {
+            semantic::lookup::RANGE_CHECK_VALUE_OUT_OF_RANGE
+        } else {
+            continue;
+        };
+        push_semantic_once(
+            &mut hits,
+            &mut seen,
+            bucket,
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("value", json!(obs.value)),
+                ("max_bits", json!(obs.max_bits)),
+            ]),
+        );
+    }
+    hits
+}
+
+pub fn match_memory_write_semantic_hits(observations: &[MemoryWriteObservation]) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
 
     for obs in observations {
         if obs.has_followup_load {
-            hits.push(BucketHit::semantic(
-                semantic::memory::STORE_LOAD_PAYLOAD_FLOW,
+            hits.push_with(semantic::memory::STORE_LOAD_PAYLOAD_FLOW, || {
                 details_kv(&[
                     ("op_idx", json!(obs.op_idx)),
                     ("pc", json!(obs.pc)),
@@ -201,12 +268,11 @@ pub fn match_memory_write_semantic_hits(observations: &[MemoryWriteObservation])
                     ("value", json!(obs.value)),
                     ("prev_value", json!(obs.prev_value)),
                     ("semantic_family", json!("store_to_load_payload_flow")),
-                ]),
-            ));
+                ])
+            });
         }
 
-        hits.push(BucketHit::semantic(
-            semantic::memory::WRITE_PAYLOAD_CONSISTENCY,
+        hits.push_with(semantic::memory::WRITE_PAYLOAD_CONSISTENCY, || {
             details_kv(&[
                 ("op_idx", json!(obs.op_idx)),
                 ("pc", json!(obs.pc)),
@@ -216,255 +282,695 @@ pub fn match_memory_write_semantic_hits(observations: &[MemoryWriteObservation])
                 ("prev_value", json!(obs.prev_value)),
                 ("value_low_bits", json!(obs.value & 0xff)),
                 ("semantic_family", json!("memory_write_payload")),
-            ]),
-        ));
+            ])
+        });
+    }
+
+    hits.into_hits()
+}
+
+/// Store rows whose `write_data` is not `prev_data` with the stored bytes merged in at the offset
+/// the row's `effective_ptr` selects; rows with malformed limb vectors are skipped.
+pub fn match_store_write_data_semantic_hits(
+    observations: &[StoreMergeObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in
+        observations.iter().filter(|obs| !store_write_data_is_consistent(obs).unwrap_or(true))
+    {
+        hits.push_with(semantic::memory::STORE_WRITE_DATA_INCONSISTENT, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("chip_name", json!(obs.chip_name)),
+                ("size_bytes", json!(obs.size_bytes)),
+                ("effective_ptr", json!(obs.effective_ptr)),
+                ("read_data", json!(obs.read_data)),
+                ("prev_data", json!(obs.prev_data)),
+                ("write_data", json!(obs.write_data)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// `None` if the row cannot be checked (unexpected size, limb count, or a misaligned pointer).
+fn store_write_data_is_consistent(obs: &StoreMergeObservation) -> Option<bool> {
+    let size = usize::from(obs.size_bytes);
+    let shift = (obs.effective_ptr % 4) as usize;
+    if !matches!(size, 1 | 2 | 4)
+        || !shift.is_multiple_of(size)
+        || obs.read_data.len() != 4
+        || obs.prev_data.len() != 4
+        || obs.write_data.len() != 4
+    {
+        return None;
+    }
+    Some((0..4).all(|i| {
+        let expected = if (shift..shift + size).contains(&i) {
+            u32::from(obs.read_data[i - shift])
+        } else {
+            obs.prev_data[i]
+        };
+        obs.write_data[i] == expected
+    }))
+}
+
+/// Execution chip rows whose kind differs from the kind their step's opcode dispatches to, one hit
+/// per mismatched row.
+pub fn match_opcode_chip_kind_semantic_hits(
+    observations: &[OpcodeDispatchObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations.iter().filter(|obs| obs.row_kind != obs.expected_kind) {
+        hits.push_with(semantic::decode::OPCODE_CHIP_KIND_MISMATCH, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("chip_name", json!(obs.chip_name)),
+                ("opcode", json!(obs.opcode)),
+                ("expected_kind", json!(obs.expected_kind)),
+                ("row_kind", json!(obs.row_kind)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Sign-extending load rows whose own columns contradict the result they should write to rd.
+///
+/// The expected rd value is rebuilt from `shifted_read_data` and the width flags; the row is
+/// flagged when the flags are not one-hot, `data_most_sig_bit` is not the loaded value's sign
+/// bit, the shift selection disagrees with `effective_ptr`, or `needs_write` disagrees with rd
+/// being `x0`. Rows with malformed limb vectors are skipped.
+pub fn match_load_sign_extend_semantic_hits(
+    observations: &[LoadSignExtendObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        let Some((reason, expected_rd)) = load_sign_extend_mismatch(obs) else {
+            continue;
+        };
+        hits.push_with(semantic::memory::LOAD_SIGN_EXTEND_RESULT_MISMATCH, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("chip_name", json!(obs.chip_name)),
+                ("reason", json!(reason)),
+                ("expected_rd", json!(expected_rd)),
+                ("rd_ptr", json!(obs.rd_ptr)),
+                ("effective_ptr", json!(obs.effective_ptr)),
+                ("shifted_read_data", json!(obs.shifted_read_data)),
+                ("data_most_sig_bit", json!(obs.data_most_sig_bit)),
+            ])
+        });
     }
+    hits.into_hits()
+}
+
+/// `(reason, expected rd value)` for an inconsistent row; the expected value is `None` when the
+/// width flags do not pick a load width.
+fn load_sign_extend_mismatch(
+    obs: &LoadSignExtendObservation,
+) -> Option<(&'static str, Option<u32>)> {
+    let &[b0, b1, _, _] = obs.shifted_read_data.as_slice() else { return None };
+    let (loaded, bits) =
+        match (obs.opcode_loadh_flag, obs.opcode_loadb_flag0, obs.opcode_loadb_flag1) {
+            (true, false, false) => (u32::from(b0) | (u32::from(b1) << 8), 16),
+            (false, true, false) => (u32::from(b0), 8),
+            (false, false, true) => (u32::from(b1), 8),
+            _ => return Some(("width_flags_not_one_hot", None)),
+        };
+    let sign = (loaded >> (bits - 1)) & 1 == 1;
+    let expected = if sign { loaded | (u32::MAX << bits) } else { loaded };
+    let shift = obs.effective_ptr & 3;
+    let reason = if sign != obs.data_most_sig_bit {
+        "sign_bit_mismatch"
+    } else if obs.shift_most_sig_bit != (shift & 2 != 0)
+        || obs.opcode_loadb_flag1 != (shift & 1 != 0)
+    {
+        "shift_selection_mismatch"
+    } else if obs.needs_write != (obs.rd_ptr != 0) {
+        "needs_write_mismatch"
+    } else {
+        return None;
+    };
+    Some((reason, Some(expected)))
+}
+
+/// Largest `k` for which `±2^k` immediates get the power-of-two boundary bucket.
+const IMM_POW2_MAX_EXP: u32 = 11;
+
+/// Classify an ALU immediate that sits on a carry/borrow or sign-extension boundary.
+///
+/// Checked in order: `±2047`, `±2048`, `±2^k` (`1 <= k <= IMM_POW2_MAX_EXP`), then any value
+/// whose low 12 bits are `0x7ff`/`0x800`, the sign-extension edge of the 12-bit immediate field.
+pub fn classify_imm_value(imm: i32) -> Option<semantic::SemanticBucket> {
+    let magnitude = imm.unsigned_abs();
+    if magnitude == 2047 {
+        return Some(semantic::alu::IMM_VALUE_BOUNDARY_12BIT_MAX);
+    }
+    if magnitude == 2048 {
+        return Some(semantic::alu::IMM_VALUE_BOUNDARY_12BIT_MIN);
+    }
+    if magnitude.is_power_of_two() && (1..=IMM_POW2_MAX_EXP).contains(&magnitude.trailing_zeros()) {
+        return Some(semantic::alu::IMM_VALUE_BOUNDARY_POW2);
+    }
+    if matches!(imm & 0xfff, 0x7ff | 0x800) {
+        return Some(semantic::alu::IMM_VALUE_BOUNDARY_SIGN_EDGE);
+    }
+    None
+}
+
+/// Index into `semantic::program::OPCODE_CLASSES` for an RV32IM mnemonic.
+fn opcode_class(mnemonic: &str) -> Option<usize> {
+    let class = match mnemonic {
+        "add" | "addi" | "sub" | "sll" | "slli" | "slt" | "slti" | "sltu" | "sltiu" | "xor"
+        | "xori" | "srl" | "srli" | "sra" | "srai" | "or" | "ori" | "and" | "andi" | "lui"
+        | "auipc" => "alu",
+        "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" => "branch",
+        "lb" | "lh" | "lw" | "lbu" | "lhu" => "load",
+        "sb" | "sh" | "sw" => "store",
+        "mul" | "mulh" | "mulhsu" | "mulhu" => "mul",
+        "div" | "divu" | "rem" | "remu" => "div",
+        "jal" | "jalr" => "jump",
+        "ecall" | "ebreak" | "fence" | "fence.i" | "mret" | "wfi" => "system",
+        m if m.starts_with("csr") => "system",
+        _ => return None,
+    };
+    semantic::program::OPCODE_CLASSES.iter().position(|c| *c == class)
+}
 
+/// One hit per distinct pair of consecutive opcode classes in execution order.
+///
+/// Mnemonics without a class break the chain rather than pairing across the gap.
+pub fn match_opcode_bigram_semantic_hits<'a>(
+    mnemonics: impl IntoIterator<Item = &'a str>,
+) -> Vec<BucketHit> {
+    let mut hits = Vec::new();
+    let mut seen = HashSet::<&'static str>::new();
+    let mut prev: Option<usize> = None;
+    for (idx, mnemonic) in mnemonics.into_iter().enumerate() {
+        let cur = opcode_class(mnemonic);
+        if let (Some(p), Some(c)) = (prev, cur) {
+            push_semantic_once(
+                &mut hits,
+                &mut seen,
+                semantic::program::OPCODE_BIGRAMS[p][c],
+                details_kv(&[
+                    ("prev_class", json!(semantic::program::OPCODE_CLASSES[p])),
+                    ("cur_class", json!(semantic::program::OPCODE_CLASSES[c])),
+                    ("first_insn_idx", json!(idx)),
+                ]),
+            );
+        }
+        prev = cur;
+    }
     hits
 }
 
-pub fn match_immediate_limb_semantic_hits(
-    observations: &[ImmediateLimbObservation],
+/// One `interaction::KIND_SEEN` hit per distinct interaction kind, so buses without bespoke
+/// buckets still register novelty. Kinds outside `INTERACTION_KINDS` count as `other`.
+pub fn match_interaction_kind_semantic_hits<'a>(
+    kinds: impl IntoIterator<Item = &'a str>,
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
+    let kinds_table = &semantic::interaction::INTERACTION_KINDS;
+    let mut hits = Vec::new();
+    let mut seen = HashSet::<&'static str>::new();
+    for (idx, kind) in kinds.into_iter().enumerate() {
+        let slot = kinds_table.iter().position(|k| *k == kind).unwrap_or(kinds_table.len() - 1);
+        push_semantic_once(
+            &mut hits,
+            &mut seen,
+            semantic::interaction::KIND_SEEN[slot],
+            details_kv(&[
+                ("interaction_kind", json!(kind)),
+                ("first_interaction_idx", json!(idx)),
+                ("semantic_family", json!("interaction_kind_seen")),
+            ]),
+        );
+    }
+    hits
+}
+
+/// One `interaction::BUS_IMBALANCE` hit per bus whose signed multiplicities (send positive,
+/// receive negative) do not cancel. Buses seen in only one direction are skipped, since their
+/// other side (e.g. the program table) is not emitted.
+pub fn match_bus_balance_semantic_hits<'a>(
+    interactions: impl IntoIterator<Item = (&'a str, i64)>,
+) -> Vec<BucketHit> {
+    let mut buses = BTreeMap::<&str, (i64, u64, u64)>::new();
+    for (kind, signed_multiplicity) in interactions {
+        let (net, sends, receives) = buses.entry(kind).or_default();
+        *net += signed_multiplicity;
+        if signed_multiplicity >= 0 {
+            *sends += 1;
+        } else {
+            *receives += 1;
+        }
+    }
+    buses
+        .into_iter()
+        .filter(|(_, (net, sends, receives))| *net != 0 && *sends > 0 && *receives > 0)
+        .map(|(kind, (net, sends, receives))| {
             BucketHit::semantic(
-                semantic::alu::IMMEDIATE_LIMB_CONSISTENCY,
+                semantic::interaction::BUS_IMBALANCE,
                 details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("imm", json!(obs.imm)),
+                    ("interaction_kind", json!(kind)),
+                    ("net_multiplicity", json!(net)),
+                    ("sends", json!(sends)),
+                    ("receives", json!(receives)),
                 ]),
             )
         })
         .collect()
 }
 
+pub fn match_immediate_limb_semantic_hits(
+    observations: &[ImmediateLimbObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        let details = || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("imm", json!(obs.imm)),
+            ])
+        };
+        if let Some(boundary) = classify_imm_value(obs.imm) {
+            hits.push_with(boundary, details);
+        }
+        hits.push_with(semantic::alu::IMMEDIATE_LIMB_CONSISTENCY, details);
+    }
+    hits.into_hits()
+}
+
 pub fn match_xor_multiplicity_semantic_hits(
     observations: &[XorMultiplicityObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::lookup::XOR_MULTIPLICITY_CONSISTENCY,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("lhs", json!(obs.lhs)),
-                    ("rhs", json!(obs.rhs)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::lookup::XOR_MULTIPLICITY_CONSISTENCY, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("lhs", json!(obs.lhs)),
+                ("rhs", json!(obs.rhs)),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_auipc_pc_limb_semantic_hits(
     observations: &[AuipcPcLimbObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::control::AUIPC_PC_LIMB_CONSISTENCY,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("from_pc", json!(obs.from_pc)),
-                    ("imm", json!(obs.imm)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::control::AUIPC_PC_LIMB_CONSISTENCY, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("from_pc", json!(obs.from_pc)),
+                ("imm", json!(obs.imm)),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_memory_immediate_sign_semantic_hits(
     observations: &[MemoryImmediateSignObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::memory::IMMEDIATE_SIGN_CONSISTENCY,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("op", json!(obs.op)),
-                    ("imm", json!(obs.imm)),
-                    ("imm_sign", json!(obs.imm_sign)),
-                    ("rs1_ptr", json!(obs.rs1_ptr)),
-                    ("rd_rs2_ptr", json!(obs.rd_rs2_ptr)),
-                    ("mem_as", json!(obs.mem_as)),
-                    ("effective_ptr", json!(obs.effective_ptr)),
-                    ("alt_effective_ptr", json!(obs.alt_effective_ptr)),
-                    ("alt_ptr_delta", json!(obs.alt_ptr_delta)),
-                    ("alt_ptr_in_range_29", json!(obs.alt_ptr_in_range_29)),
-                    ("is_load", json!(obs.is_load)),
-                    ("is_store", json!(obs.is_store)),
-                    ("needs_write", json!(obs.needs_write)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::memory::IMMEDIATE_SIGN_CONSISTENCY, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("op", json!(obs.op)),
+                ("imm", json!(obs.imm)),
+                ("imm_sign", json!(obs.imm_sign)),
+                ("rs1_ptr", json!(obs.rs1_ptr)),
+                ("rd_rs2_ptr", json!(obs.rd_rs2_ptr)),
+                ("mem_as", json!(obs.mem_as)),
+                ("effective_ptr", json!(obs.effective_ptr)),
+                ("alt_effective_ptr", json!(obs.alt_effective_ptr)),
+                ("alt_ptr_delta", json!(obs.alt_ptr_delta)),
+                ("alt_ptr_in_range_29", json!(obs.alt_ptr_in_range_29)),
+                ("is_load", json!(obs.is_load)),
+                ("is_store", json!(obs.is_store)),
+                ("needs_write", json!(obs.needs_write)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Recompute `rs1_val + sign_extend(imm)` for each load/store row and fire
+/// `EFFECTIVE_PTR_MISMATCH` once per trace on the first row whose `effective_ptr` disagrees.
+pub fn match_effective_ptr_semantic_hits(
+    observations: &[EffectivePtrObservation],
+) -> Vec<BucketHit> {
+    let mut hits = Vec::new();
+    let mut seen = HashSet::new();
+    for obs in observations {
+        let expected = obs.rs1_val.wrapping_add(obs.imm as u32);
+        if expected == obs.effective_ptr {
+            continue;
+        }
+        push_semantic_once(
+            &mut hits,
+            &mut seen,
+            semantic::memory::EFFECTIVE_PTR_MISMATCH,
+            details_kv(&[
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("op", json!(obs.op)),
+                ("rs1_val", json!(obs.rs1_val)),
+                ("imm", json!(obs.imm)),
+                ("effective_ptr", json!(obs.effective_ptr)),
+                ("expected_ptr", json!(expected)),
+            ]),
+        );
+    }
+    hits
 }
 
 pub fn match_memory_address_space_semantic_hits(
     observations: &[MemoryAddressSpaceObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::memory::ADDRESS_SPACE_CONSISTENCY,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("mem_as", json!(obs.mem_as)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::memory::ADDRESS_SPACE_CONSISTENCY, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("mem_as", json!(obs.mem_as)),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_boundary_origin_semantic_hits(
     observations: &[BoundaryOriginObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::time::BOUNDARY_ORIGIN_CONSISTENCY,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("from_timestamp", json!(obs.from_timestamp)),
-                    ("to_timestamp", json!(obs.to_timestamp)),
-                    ("is_terminate", json!(obs.is_terminate)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::time::BOUNDARY_ORIGIN_CONSISTENCY, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("from_timestamp", json!(obs.from_timestamp)),
+                ("to_timestamp", json!(obs.to_timestamp)),
+                ("is_terminate", json!(obs.is_terminate)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Consecutive connector rows must chain across segments: each row's `from_pc`/`from_timestamp`
+/// should equal the previous row's `to_pc`/`to_timestamp`.
+pub fn match_connector_chain_break_semantic_hits(
+    observations: &[ConnectorObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for pair in observations.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        let pc_break = next.from_pc != prev.to_pc;
+        let ts_break = matches!(
+            (prev.to_timestamp, next.from_timestamp),
+            (Some(to), Some(from)) if to != from
+        );
+        if !pc_break && !ts_break {
+            continue;
+        }
+        hits.push_with(semantic::time::CONNECTOR_CHAIN_BREAK, || {
+            details_kv(&[
+                ("chip_name", json!(next.chip_name)),
+                ("step_idx", json!(next.step_idx)),
+                ("op_idx", json!(next.op_idx)),
+                ("prev_to_pc", json!(prev.to_pc)),
+                ("prev_to_timestamp", json!(prev.to_timestamp)),
+                ("from_pc", json!(next.from_pc)),
+                ("from_timestamp", json!(next.from_timestamp)),
+                ("pc_break", json!(pc_break)),
+                ("timestamp_break", json!(ts_break)),
+                ("is_terminate", json!(next.is_terminate)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Interactions timestamped outside their anchor row's window. An instruction's accesses happen
+/// at or after its row's timestamp and no later than its next timestamp, so only timestamps
+/// before the row, or past the step's end when known, are flagged.
+pub fn match_interaction_row_timestamp_semantic_hits(
+    observations: &[InteractionTimestampObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        let reason = if obs.timestamp < obs.row_timestamp {
+            "before_row"
+        } else if obs.step_next_timestamp.is_some_and(|next| obs.timestamp > next) {
+            "after_step_end"
+        } else {
+            continue;
+        };
+        hits.push_with(semantic::time::INTERACTION_ROW_TIMESTAMP_MISMATCH, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("kind", json!(obs.kind)),
+                ("row_id", json!(obs.row_id)),
+                ("timestamp", json!(obs.timestamp)),
+                ("row_timestamp", json!(obs.row_timestamp)),
+                ("step_next_timestamp", json!(obs.step_next_timestamp)),
+                ("reason", json!(reason)),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_timestamped_load_path_semantic_hits(
     observations: &[TimestampedLoadPathObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::memory::TIMESTAMPED_LOAD_PATH,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("timestamp", json!(obs.timestamp)),
-                    ("is_load", json!(obs.is_load)),
-                    ("is_store", json!(obs.is_store)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::memory::TIMESTAMPED_LOAD_PATH, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("timestamp", json!(obs.timestamp)),
+                ("is_load", json!(obs.is_load)),
+                ("is_store", json!(obs.is_store)),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_volatile_boundary_semantic_hits(
     observations: &[VolatileBoundaryObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::memory::VOLATILE_BOUNDARY_RANGE,
-                details_kv(&[
-                    ("kind", json!(obs.kind)),
-                    ("chip_name", json!(obs.chip_name)),
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::memory::VOLATILE_BOUNDARY_RANGE, || {
+            details_kv(&[
+                ("kind", json!(obs.kind)),
+                ("chip_name", json!(obs.chip_name)),
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_arithmetic_special_case_semantic_hits(
     observations: &[ArithmeticSpecialCaseObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::arithmetic::SPECIAL_CASE_CONSISTENCY,
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::arithmetic::SPECIAL_CASE_CONSISTENCY, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("rs1", json!(obs.rs1)),
+                ("rs2", json!(obs.rs2)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Full 64-bit product of `rs1 * rs2` under the signedness of a `mulh*` mnemonic.
+fn mulh_wide_product(mnemonic: &str, rs1: u32, rs2: u32) -> Option<u64> {
+    let product = match mnemonic {
+        "mulh" => (rs1 as i32 as i64).wrapping_mul(rs2 as i32 as i64) as u64,
+        "mulhsu" => (rs1 as i32 as i64).wrapping_mul(rs2 as i64) as u64,
+        "mulhu" => (rs1 as u64) * (rs2 as u64),
+        _ => return None,
+    };
+    Some(product)
+}
+
+/// Pair every `mulh*` row with the `mul` rows on the same operand values (in either order, since
+/// the low word is commutative) and fire `MUL_MULH_PRODUCT_INCONSISTENT` once per trace when the
+/// `mul` low word and `mulh*` high word disagree with the 64-bit product.
+pub fn match_mul_mulh_product_semantic_hits(
+    observations: &[MulProductObservation],
+) -> Vec<BucketHit> {
+    let mut hits = Vec::new();
+    let mut seen = HashSet::new();
+    for high in observations {
+        let Some(expected) = mulh_wide_product(&high.mnemonic, high.rs1, high.rs2) else {
+            continue;
+        };
+        let lows = observations.iter().filter(|low| {
+            low.mnemonic == "mul"
+                && ((low.rs1, low.rs2) == (high.rs1, high.rs2)
+                    || (low.rs1, low.rs2) == (high.rs2, high.rs1))
+        });
+        for low in lows {
+            let combined = ((high.result as u64) << 32) | low.result as u64;
+            if combined == expected {
+                continue;
+            }
+            push_semantic_once(
+                &mut hits,
+                &mut seen,
+                semantic::arithmetic::MUL_MULH_PRODUCT_INCONSISTENT,
                 details_kv(&[
-                    ("step_idx", json!(obs.step_idx)),
-                    ("op_idx", json!(obs.op_idx)),
-                    ("rs1", json!(obs.rs1)),
-                    ("rs2", json!(obs.rs2)),
+                    ("mul_step_idx", json!(low.step_idx)),
+                    ("mulh_step_idx", json!(high.step_idx)),
+                    ("mulh_mnemonic", json!(high.mnemonic)),
+                    ("rs1", json!(high.rs1)),
+                    ("rs2", json!(high.rs2)),
+                    ("mul_result", json!(low.result)),
+                    ("mulh_result", json!(high.result)),
+                    ("expected_product", json!(expected)),
                 ]),
-            )
-        })
-        .collect()
+            );
+        }
+    }
+    hits
 }
 
+/// One `control::PHANTOM_SUB_KIND` hit per distinct phantom sub-kind, from `(step_idx,
+/// discriminant)` pairs. Discriminants outside `PHANTOM_DISCRIMINANTS` count as `other`.
+pub fn match_phantom_sub_kind_semantic_hits(
+    phantoms: impl IntoIterator<Item = (u64, u32)>,
+) -> Vec<BucketHit> {
+    let discriminants = &semantic::control::PHANTOM_DISCRIMINANTS;
+    let mut hits = Vec::new();
+    let mut seen = HashSet::<&'static str>::new();
+    for (step_idx, discriminant) in phantoms {
+        let slot =
+            discriminants.iter().position(|d| *d == discriminant).unwrap_or(discriminants.len());
+        push_semantic_once(
+            &mut hits,
+            &mut seen,
+            semantic::control::PHANTOM_SUB_KIND[slot],
+            details_kv(&[
+                ("phantom_sub_kind", json!(semantic::control::PHANTOM_SUB_KINDS[slot])),
+                ("discriminant", json!(discriminant)),
+                ("first_step_idx", json!(step_idx)),
+            ]),
+        );
+    }
+    hits
+}
+
+/// Every x0 write hits `ZERO_REGISTER_IMMUTABILITY` as coverage; one whose recorded `rd_data`
+/// is nonzero also hits `ZERO_REGISTER_WRITE_EFFECTFUL`.
 pub fn match_zero_register_semantic_hits(
     observations: &[ZeroRegisterWriteObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::decode::ZERO_REGISTER_IMMUTABILITY,
+    let mut out = BucketHitCollector::bounded();
+    for obs in observations {
+        out.push_with(semantic::decode::ZERO_REGISTER_IMMUTABILITY, || {
+            details_kv(&[
+                ("op_idx", json!(obs.op_idx)),
+                ("pc", json!(obs.pc)),
+                ("raw_word", json!(obs.raw_word)),
+                ("mnemonic", json!(obs.mnemonic)),
+                ("semantic_family", json!("zero_register_write")),
+            ])
+        });
+        if let Some(rd_data) = obs.rd_data.filter(|&v| v != 0) {
+            out.push_with(semantic::decode::ZERO_REGISTER_WRITE_EFFECTFUL, || {
                 details_kv(&[
                     ("op_idx", json!(obs.op_idx)),
                     ("pc", json!(obs.pc)),
                     ("raw_word", json!(obs.raw_word)),
                     ("mnemonic", json!(obs.mnemonic)),
+                    ("rd_data", json!(rd_data)),
                     ("semantic_family", json!("zero_register_write")),
-                ]),
-            )
-        })
-        .collect()
+                ])
+            });
+        }
+    }
+    out.into_hits()
+}
+
+/// `ZERO_REGISTER_WRITE_EFFECTFUL` for a run whose final x0 is nonzero.
+pub fn match_final_zero_register_semantic_hits(final_x0: u32) -> Vec<BucketHit> {
+    if final_x0 == 0 {
+        return Vec::new();
+    }
+    vec![BucketHit::semantic(
+        semantic::decode::ZERO_REGISTER_WRITE_EFFECTFUL,
+        details_kv(&[
+            ("final_x0", json!(final_x0)),
+            ("semantic_family", json!("zero_register_write")),
+        ]),
+    )]
 }
 
 pub fn match_rd_bit_semantic_hits(
     observations: &[RdBitDecompositionObservation],
 ) -> Vec<BucketHit> {
-    observations
-        .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::decode::RD_BIT_DECOMPOSITION,
-                details_kv(&[
-                    ("op_idx", json!(obs.op_idx)),
-                    ("pc", json!(obs.pc)),
-                    ("raw_word", json!(obs.raw_word)),
-                    ("rd", json!(obs.rd)),
-                    ("mnemonic", json!(obs.mnemonic)),
-                    ("semantic_family", json!("rd_bit_decomposition")),
-                ]),
-            )
-        })
-        .collect()
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::decode::RD_BIT_DECOMPOSITION, || {
+            details_kv(&[
+                ("op_idx", json!(obs.op_idx)),
+                ("pc", json!(obs.pc)),
+                ("raw_word", json!(obs.raw_word)),
+                ("rd", json!(obs.rd)),
+                ("mnemonic", json!(obs.mnemonic)),
+                ("semantic_family", json!("rd_bit_decomposition")),
+            ])
+        });
+    }
+    hits.into_hits()
 }
 
 pub fn match_division_semantic_hits(observations: &[DivisionInsnObservation]) -> Vec<BucketHit> {
-    let mut hits = Vec::new();
+    let mut hits = BucketHitCollector::bounded();
     for obs in observations {
-        hits.push(BucketHit::semantic(
-            semantic::decode::OPERAND_INDEX_ROUTING,
+        hits.push_with(semantic::decode::OPERAND_INDEX_ROUTING, || {
             details_kv(&[
                 ("op_idx", json!(obs.op_idx)),
                 ("pc", json!(obs.pc)),
@@ -474,10 +980,9 @@ pub fn match_division_semantic_hits(observations: &[DivisionInsnObservation]) ->
                 ("rs1", json!(obs.rs1)),
                 ("rs2", json!(obs.rs2)),
                 ("semantic_family", json!("division_operand_routing")),
-            ]),
-        ));
-        hits.push(BucketHit::semantic(
-            semantic::arithmetic::DIVISION_REMAINDER_BOUND,
+            ])
+        });
+        hits.push_with(semantic::arithmetic::DIVISION_REMAINDER_BOUND, || {
             details_kv(&[
                 ("op_idx", json!(obs.op_idx)),
                 ("pc", json!(obs.pc)),
@@ -487,34 +992,280 @@ pub fn match_division_semantic_hits(observations: &[DivisionInsnObservation]) ->
                 ("rs1", json!(obs.rs1)),
                 ("rs2", json!(obs.rs2)),
                 ("semantic_family", json!("division_remainder_bound")),
-            ]),
-        ));
+            ])
+        });
     }
-    hits
+    hits.into_hits()
 }
 
 pub fn match_ecall_semantic_hits(observations: &[EcallInsnObservation]) -> Vec<BucketHit> {
-    observations
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations {
+        hits.push_with(semantic::control::ECALL_ARGUMENT_DECOMPOSITION, || {
+            details_kv(&[
+                ("op_idx", json!(obs.op_idx)),
+                ("pc", json!(obs.pc)),
+                ("raw_word", json!(obs.raw_word)),
+                ("mnemonic", json!(obs.mnemonic)),
+                ("semantic_family", json!("ecall_argument_decomposition")),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Instructions whose emitter flagged operands at or above the field modulus, as
+/// `(step_idx, operand indices)`. No real execution emits such an instruction, so the hit marks a
+/// trace produced by a malformed (e.g. mutated) program; one hit per trace.
+pub fn match_operand_out_of_field_semantic_hits<'a>(
+    flagged: impl IntoIterator<Item = (u64, &'a [usize])>,
+) -> Vec<BucketHit> {
+    let mut flagged = flagged.into_iter().filter(|(_, operands)| !operands.is_empty());
+    let Some((step_idx, operands)) = flagged.next() else {
+        return Vec::new();
+    };
+    vec![BucketHit::semantic(
+        semantic::decode::OPERAND_OUT_OF_FIELD,
+        details_kv(&[
+            ("step_idx", json!(step_idx)),
+            ("operands", json!(operands)),
+            ("flagged_steps", json!(1 + flagged.count())),
+        ]),
+    )]
+}
+
+/// Instruction operands recorded at or above `field_order`, checked on the trace side
+/// independently of any emitter flag. Honest execution only produces canonical field elements,
+/// so this is a cheap tripwire for mutation artifacts or backends accepting non-canonical
+/// encodings; one hit per trace.
+pub fn match_operand_non_canonical_semantic_hits<'a>(
+    instructions: impl IntoIterator<Item = (u64, &'a [u32])>,
+    field_order: u32,
+) -> Vec<BucketHit> {
+    let mut flagged = instructions.into_iter().filter_map(|(step_idx, operands)| {
+        let indices: Vec<usize> = operands
+            .iter()
+            .enumerate()
+            .filter(|(_, &operand)| operand >= field_order)
+            .map(|(i, _)| i)
+            .collect();
+        (!indices.is_empty()).then_some((step_idx, indices))
+    });
+    let Some((step_idx, indices)) = flagged.next() else {
+        return Vec::new();
+    };
+    vec![BucketHit::semantic(
+        semantic::decode::OPERAND_NON_CANONICAL,
+        details_kv(&[
+            ("step_idx", json!(step_idx)),
+            ("operands", json!(indices)),
+            ("field_order", json!(field_order)),
+            ("flagged_steps", json!(1 + flagged.count())),
+        ]),
+    )]
+}
+
+/// Set to `1` to bucket program length by non-NOP instruction count.
+pub const LENGTH_EXCLUDES_NOPS_ENV: &str = "BEAK_LENGTH_EXCLUDES_NOPS";
+
+/// `LENGTH_EXCLUDES_NOPS_ENV`, read once per process.
+pub fn length_excludes_nops() -> bool {
+    static EXCLUDES: OnceLock<bool> = OnceLock::new();
+    *EXCLUDES.get_or_init(|| {
+        std::env::var(LENGTH_EXCLUDES_NOPS_ENV).is_ok_and(|v| matches!(v.trim(), "1" | "true"))
+    })
+}
+
+/// Whether `hit` is one of the `match_program_length_semantic_hits` length regimes.
+pub fn is_program_length_hit(hit: &BucketHit) -> bool {
+    semantic::by_id(&hit.bucket_id)
+        .is_some_and(|bucket| bucket.semantic_class == semantic::program::LEN_1.semantic_class)
+}
+
+/// Coarse program-length bucket: one hit per trace, keyed by instruction-count regime.
+/// With `non_nop_count`, the regime follows the non-NOP count instead (an all-NOP program counts
+/// as length 1), so NOP padding does not move an input into a longer length regime.
+pub fn match_program_length_semantic_hits(
+    instruction_count: usize,
+    non_nop_count: Option<usize>,
+) -> Vec<BucketHit> {
+    if instruction_count == 0 {
+        return Vec::new();
+    }
+    let bucket = match non_nop_count.map_or(instruction_count, |n| n.max(1)) {
+        1 => semantic::program::LEN_1,
+        2..=4 => semantic::program::LEN_2_4,
+        5..=16 => semantic::program::LEN_5_16,
+        17..=64 => semantic::program::LEN_17_64,
+        _ => semantic::program::LEN_65_PLUS,
+    };
+    let mut details = details_kv(&[
+        ("instruction_count", json!(instruction_count)),
+        ("semantic_family", json!("program_length")),
+    ]);
+    if let Some(non_nop_count) = non_nop_count {
+        details.insert("non_nop_count".to_string(), json!(non_nop_count));
+    }
+    vec![BucketHit::semantic(bucket, details)]
+}
+
+/// Shortest run of identical consecutive words that `match_repeated_instruction_run_semantic_hits`
+/// reports.
+pub const REPEATED_RUN_MIN_LEN: usize = 3;
+
+/// Bucket the longest run of identical consecutive instruction words, if it reaches `min_len`
+/// (clamped to at least `REPEATED_RUN_MIN_LEN`).
+pub fn match_repeated_instruction_run_semantic_hits(
+    words: impl IntoIterator<Item = u32>,
+    min_len: usize,
+) -> Vec<BucketHit> {
+    let mut longest = (0usize, 0u32);
+    let mut current = (0usize, None::<u32>);
+    for word in words {
+        current =
+            if current.1 == Some(word) { (current.0 + 1, current.1) } else { (1, Some(word)) };
+        if current.0 > longest.0 {
+            longest = (current.0, word);
+        }
+    }
+    let (run_len, word) = longest;
+    if run_len < min_len.max(REPEATED_RUN_MIN_LEN) {
+        return Vec::new();
+    }
+    let bucket = match run_len {
+        0..=4 => semantic::program::REPEATED_RUN_3_4,
+        5..=16 => semantic::program::REPEATED_RUN_5_16,
+        _ => semantic::program::REPEATED_RUN_17_PLUS,
+    };
+    vec![BucketHit::semantic(
+        bucket,
+        details_kv(&[
+            ("run_len", json!(run_len)),
+            ("word", json!(format!("0x{word:08x}"))),
+            ("semantic_family", json!("repeated_instruction_run")),
+        ]),
+    )]
+}
+
+/// Program words in execution order: the word at each executed `pc` of a program loaded at 0.
+pub fn executed_words(words: &[u32], pcs: impl IntoIterator<Item = u64>) -> Vec<u32> {
+    pcs.into_iter().filter_map(|pc| words.get((pc / 4) as usize).copied()).collect()
+}
+
+/// Add the hits that depend only on the executed instruction words: opcode bigrams, LUI+ADDI
+/// fusion, the longest repeated run and, under `length_excludes_nops`, the NOP-aware length regime in place of the
+/// trace's own length hit. Both the backends and `rebucket_corpus` go through here, so a replayed
+/// archive buckets like the live run did.
+pub fn extend_word_derived_hits(hits: &mut Vec<BucketHit>, executed_words: &[u32]) {
+    if executed_words.is_empty() {
+        return;
+    }
+    let decoded: Vec<_> =
+        executed_words.iter().map(|w| RV32IMInstruction::from_word(*w).ok()).collect();
+    if length_excludes_nops() {
+        let non_nop_count =
+            decoded.iter().filter(|i| !i.as_ref().is_some_and(|i| i.is_nop())).count();
+        hits.retain(|hit| !is_program_length_hit(hit));
+        hits.extend(match_program_length_semantic_hits(executed_words.len(), Some(non_nop_count)));
+    }
+    // An undecodable word has no class, so it breaks the bigram chain instead of being skipped.
+    hits.extend(match_opcode_bigram_semantic_hits(
+        decoded.iter().map(|i| i.as_ref().map_or("", |i| i.mnemonic.as_str())),
+    ));
+    hits.extend(match_lui_addi_fusion_semantic_hits((0u64..).zip(executed_words.iter().copied())));
+    hits.extend(match_repeated_instruction_run_semantic_hits(
+        executed_words.iter().copied(),
+        REPEATED_RUN_MIN_LEN,
+    ));
+}
+
+/// A writing JAL must store the return address `from_pc + 4` and a writing LUI `imm << 12`; any
+/// other `rd` value means the row's write constraint admitted a wrong result.
+pub fn match_jal_lui_rd_semantic_hits(observations: &[JalLuiObservation]) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations.iter().filter(|obs| obs.needs_write) {
+        let expected =
+            if obs.is_jal { obs.from_pc.wrapping_add(4) } else { obs.imm.wrapping_shl(12) };
+        if expected == obs.rd_value {
+            continue;
+        }
+        hits.push_with(semantic::control::JAL_LUI_RD_MISMATCH, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("chip_name", json!(obs.chip_name)),
+                ("variant", json!(if obs.is_jal { "jal" } else { "lui" })),
+                ("expected", json!(expected)),
+                ("actual", json!(obs.rd_value)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// A program row that was never executed (`execution_frequency == 0`) should not balance any
+/// interactions; one that does probes the program-ROM multiplicity constraint.
+pub fn match_program_row_frequency_semantic_hits(
+    observations: &[ProgramRowObservation],
+) -> Vec<BucketHit> {
+    let mut hits = BucketHitCollector::bounded();
+    for obs in observations
         .iter()
-        .map(|obs| {
-            BucketHit::semantic(
-                semantic::control::ECALL_ARGUMENT_DECOMPOSITION,
-                details_kv(&[
-                    ("op_idx", json!(obs.op_idx)),
-                    ("pc", json!(obs.pc)),
-                    ("raw_word", json!(obs.raw_word)),
-                    ("mnemonic", json!(obs.mnemonic)),
-                    ("semantic_family", json!("ecall_argument_decomposition")),
-                ]),
-            )
-        })
-        .collect()
+        .filter(|obs| obs.execution_frequency == 0 && obs.anchored_interactions > 0)
+    {
+        hits.push_with(semantic::program::ZERO_FREQUENCY_ACTIVE_ROW, || {
+            details_kv(&[
+                ("step_idx", json!(obs.step_idx)),
+                ("op_idx", json!(obs.op_idx)),
+                ("row_id", json!(obs.row_id)),
+                ("execution_frequency", json!(obs.execution_frequency)),
+                ("anchored_interactions", json!(obs.anchored_interactions)),
+            ])
+        });
+    }
+    hits.into_hits()
+}
+
+/// Bucket the number of continuation segments so inputs crossing segment boundaries are rewarded.
+pub fn match_segment_count_semantic_hits(segment_count: usize) -> Vec<BucketHit> {
+    let bucket = match segment_count {
+        0 => return Vec::new(),
+        1 => semantic::program::SEGMENTS_1,
+        2 => semantic::program::SEGMENTS_2,
+        _ => semantic::program::SEGMENTS_3_PLUS,
+    };
+    vec![BucketHit::semantic(
+        bucket,
+        details_kv(&[
+            ("segment_count", json!(segment_count)),
+            ("semantic_family", json!("segment_count")),
+        ]),
+    )]
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{match_sequence_semantic_hits, sequence_trace_signals};
-    use crate::trace::observations::{SequenceInsnObservation, SequenceSemanticMatcherProfile};
+    use super::{
+        classify_imm_value, extend_word_derived_hits, is_program_length_hit,
+        match_bus_balance_semantic_hits, match_connector_chain_break_semantic_hits,
+        match_effective_ptr_semantic_hits, match_final_zero_register_semantic_hits,
+        match_interaction_kind_semantic_hits, match_interaction_row_timestamp_semantic_hits,
+        match_jal_lui_rd_semantic_hits, match_load_sign_extend_semantic_hits,
+        match_lui_addi_fusion_semantic_hits, match_mul_mulh_product_semantic_hits,
+        match_opcode_bigram_semantic_hits, match_opcode_chip_kind_semantic_hits,
+        match_operand_non_canonical_semantic_hits, match_phantom_sub_kind_semantic_hits,
+        match_program_length_semantic_hits, match_program_row_frequency_semantic_hits,
+        match_range_check_semantic_hits, match_repeated_instruction_run_semantic_hits,
+        match_sequence_semantic_hits, match_store_write_data_semantic_hits,
+        match_zero_register_semantic_hits, sequence_trace_signals,
+    };
+    use crate::trace::observations::{
+        ConnectorObservation, EffectivePtrObservation, InteractionTimestampObservation,
+        JalLuiObservation, LoadSignExtendObservation, MulProductObservation,
+        OpcodeDispatchObservation, ProgramRowObservation, RangeCheckObservation,
+        SequenceInsnObservation, SequenceSemanticMatcherProfile, StoreMergeObservation,
+        ZeroRegisterWriteObservation,
+    };
     use crate::trace::{TraceSignal, semantic};
 
     #[test]
@@ -610,4 +1361,499 @@ mod tests {
         assert!(signals.contains(&TraceSignal::HasLoad));
         assert!(signals.contains(&TraceSignal::HasAuipc));
     }
+
+    #[test]
+    fn program_length_buckets_cover_each_regime() {
+        assert!(match_program_length_semantic_hits(0, None).is_empty());
+        let ids: Vec<String> = [1usize, 3, 16, 17, 65]
+            .iter()
+            .map(|&n| match_program_length_semantic_hits(n, None)[0].bucket_id.clone())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                semantic::program::LEN_1.id,
+                semantic::program::LEN_2_4.id,
+                semantic::program::LEN_5_16.id,
+                semantic::program::LEN_17_64.id,
+                semantic::program::LEN_65_PLUS.id,
+            ]
+        );
+
+        let padded = &match_program_length_semantic_hits(20, Some(3))[0];
+        assert_eq!(padded.bucket_id, semantic::program::LEN_2_4.id);
+        assert_eq!(padded.details["instruction_count"], 20);
+        assert_eq!(padded.details["non_nop_count"], 3);
+        assert!(is_program_length_hit(padded));
+        let all_nops = &match_program_length_semantic_hits(4, Some(0))[0];
+        assert_eq!(all_nops.bucket_id, semantic::program::LEN_1.id);
+    }
+
+    #[test]
+    fn non_canonical_operands_fire_at_field_order() {
+        const P: u32 = 0x7800_0001;
+        let canonical = [0, 1, P - 1, 0, 0, 0, 0];
+        let non_canonical = [0, P, 0, 0, 0, u32::MAX, 0];
+        assert!(match_operand_non_canonical_semantic_hits([(0, &canonical[..])], P).is_empty());
+
+        let hits = match_operand_non_canonical_semantic_hits(
+            [(0, &canonical[..]), (1, &non_canonical[..]), (2, &non_canonical[..])],
+            P,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::decode::OPERAND_NON_CANONICAL.id);
+        assert_eq!(hits[0].details["step_idx"], 1);
+        assert_eq!(hits[0].details["operands"], serde_json::json!([1, 5]));
+        assert_eq!(hits[0].details["flagged_steps"], 2);
+    }
+
+    #[test]
+    fn store_write_data_checks_merge_at_the_pointer_offset() {
+        let store = |size_bytes, effective_ptr, write_data: [u32; 4]| StoreMergeObservation {
+            step_idx: 0,
+            op_idx: 0,
+            chip_name: "LoadStoreCoreAir".to_string(),
+            size_bytes,
+            effective_ptr,
+            read_data: vec![0xaa, 0xbb, 0xcc, 0xdd],
+            prev_data: vec![1, 2, 3, 4],
+            write_data: write_data.to_vec(),
+        };
+        let consistent = [
+            store(4, 0x100, [0xaa, 0xbb, 0xcc, 0xdd]),
+            store(2, 0x102, [1, 2, 0xaa, 0xbb]),
+            store(1, 0x103, [1, 2, 3, 0xaa]),
+            // Misaligned pointers cannot be checked.
+            store(2, 0x101, [1, 2, 3, 4]),
+        ];
+        assert!(match_store_write_data_semantic_hits(&consistent).is_empty());
+
+        let inconsistent = [
+            store(2, 0x100, [1, 0xaa, 0xbb, 4]),
+            store(1, 0x100, [0xaa, 0xbb, 3, 4]),
+            store(4, 0x100, [1, 2, 3, 4]),
+            // Well-formed merge, but at an offset other than the pointer's.
+            store(2, 0x100, [1, 2, 0xaa, 0xbb]),
+            store(1, 0x101, [1, 2, 3, 0xaa]),
+        ];
+        let hits = match_store_write_data_semantic_hits(&inconsistent);
+        assert_eq!(hits.len(), 5);
+        assert!(
+            hits.iter()
+                .all(|hit| hit.bucket_id == semantic::memory::STORE_WRITE_DATA_INCONSISTENT.id)
+        );
+    }
+
+    #[test]
+    fn opcode_chip_kind_fires_per_mismatched_row() {
+        let row = |step_idx, expected_kind: &str, row_kind: &str| OpcodeDispatchObservation {
+            step_idx,
+            op_idx: 0,
+            chip_name: "chip".to_string(),
+            opcode: 0x250,
+            expected_kind: expected_kind.to_string(),
+            row_kind: row_kind.to_string(),
+        };
+        let hits = match_opcode_chip_kind_semantic_hits(&[
+            row(0, "mul", "mul"),
+            row(1, "mul", "base_alu"),
+        ]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::decode::OPCODE_CHIP_KIND_MISMATCH.id);
+        assert_eq!(hits[0].details["step_idx"], 1);
+        assert_eq!(hits[0].details["expected_kind"], "mul");
+        assert_eq!(hits[0].details["row_kind"], "base_alu");
+    }
+
+    #[test]
+    fn load_sign_extend_flags_rows_contradicting_the_loaded_sign() {
+        let load = |flags: (bool, bool, bool), effective_ptr, data_most_sig_bit| {
+            LoadSignExtendObservation {
+                step_idx: 0,
+                op_idx: 0,
+                chip_name: "LoadSignExtendCoreAir".to_string(),
+                rd_ptr: 4,
+                effective_ptr,
+                needs_write: true,
+                shifted_read_data: vec![0x80, 0x7f, 0x12, 0x34],
+                data_most_sig_bit,
+                shift_most_sig_bit: effective_ptr & 2 != 0,
+                opcode_loadh_flag: flags.0,
+                opcode_loadb_flag0: flags.1,
+                opcode_loadb_flag1: flags.2,
+            }
+        };
+        let consistent = [
+            load((true, false, false), 0x102, false),
+            load((false, true, false), 0x100, true),
+            load((false, false, true), 0x103, false),
+        ];
+        assert!(match_load_sign_extend_semantic_hits(&consistent).is_empty());
+
+        let mut silent_write = load((false, true, false), 0x100, true);
+        silent_write.needs_write = false;
+        let broken = [
+            load((false, true, false), 0x100, false),
+            load((false, false, true), 0x102, false),
+            load((true, true, false), 0x100, false),
+            silent_write,
+        ];
+        let hits = match_load_sign_extend_semantic_hits(&broken);
+        let reasons: Vec<_> = hits.iter().map(|hit| hit.details["reason"].clone()).collect();
+        assert_eq!(
+            reasons,
+            [
+                "sign_bit_mismatch",
+                "shift_selection_mismatch",
+                "width_flags_not_one_hot",
+                "needs_write_mismatch"
+            ]
+        );
+        assert_eq!(hits[0].details["expected_rd"], 0xffff_ff80u32);
+        assert!(
+            hits.iter().all(|hit| {
+                hit.bucket_id == semantic::memory::LOAD_SIGN_EXTEND_RESULT_MISMATCH.id
+            })
+        );
+    }
+
+    #[test]
+    fn connector_chain_break_fires_only_on_mismatch() {
+        let connector = |from_pc, to_pc, from_ts, to_ts| ConnectorObservation {
+            step_idx: 0,
+            op_idx: 0,
+            chip_name: "VmConnectorAir".to_string(),
+            from_pc,
+            to_pc,
+            from_timestamp: Some(from_ts),
+            to_timestamp: Some(to_ts),
+            is_terminate: false,
+        };
+        let chained = [connector(0, 8, 1, 10), connector(8, 16, 10, 20)];
+        assert!(match_connector_chain_break_semantic_hits(&chained).is_empty());
+
+        let broken = [connector(0, 8, 1, 10), connector(12, 16, 10, 20)];
+        let hits = match_connector_chain_break_semantic_hits(&broken);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::time::CONNECTOR_CHAIN_BREAK.id);
+        assert_eq!(hits[0].details["prev_to_pc"], 8);
+        assert_eq!(hits[0].details["from_pc"], 12);
+    }
+
+    #[test]
+    fn interaction_timestamps_outside_the_row_window_fire() {
+        let interaction = |timestamp, step_next_timestamp| InteractionTimestampObservation {
+            step_idx: 2,
+            op_idx: 0,
+            kind: "memory".to_string(),
+            row_id: "step2_row0".to_string(),
+            timestamp,
+            row_timestamp: 10,
+            step_next_timestamp,
+        };
+        let within = [interaction(10, Some(13)), interaction(13, Some(13)), interaction(40, None)];
+        assert!(match_interaction_row_timestamp_semantic_hits(&within).is_empty());
+
+        let hits = match_interaction_row_timestamp_semantic_hits(&[
+            interaction(9, Some(13)),
+            interaction(14, Some(13)),
+        ]);
+        let reasons: Vec<_> = hits.iter().map(|hit| hit.details["reason"].clone()).collect();
+        assert_eq!(reasons, vec!["before_row", "after_step_end"]);
+        assert_eq!(hits[0].bucket_id, semantic::time::INTERACTION_ROW_TIMESTAMP_MISMATCH.id);
+    }
+
+    #[test]
+    fn zero_frequency_program_row_fires_only_when_anchored() {
+        let row = |execution_frequency, anchored_interactions| ProgramRowObservation {
+            step_idx: 0,
+            op_idx: 0,
+            row_id: "step0_row0".to_string(),
+            execution_frequency,
+            anchored_interactions,
+        };
+        let hits = match_program_row_frequency_semantic_hits(&[row(0, 0), row(1, 2), row(0, 2)]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::program::ZERO_FREQUENCY_ACTIVE_ROW.id);
+        assert_eq!(hits[0].details["anchored_interactions"], 2);
+    }
+
+    #[test]
+    fn jal_lui_rd_mismatch_checks_each_variant() {
+        let row = |is_jal, needs_write, rd_value| JalLuiObservation {
+            step_idx: 0,
+            op_idx: 0,
+            chip_name: "Rv32JalLui".to_string(),
+            is_jal,
+            needs_write,
+            from_pc: 0x100,
+            imm: 0x12345,
+            rd_value,
+        };
+        let hits = match_jal_lui_rd_semantic_hits(&[
+            row(true, true, 0x104),
+            row(false, true, 0x1234_5000),
+            row(true, false, 0),
+            row(false, true, 0x1234_5004),
+        ]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::control::JAL_LUI_RD_MISMATCH.id);
+        assert_eq!(hits[0].details["variant"], "lui");
+        assert_eq!(hits[0].details["expected"], 0x1234_5000);
+    }
+
+    #[test]
+    fn classifies_immediate_boundaries() {
+        use semantic::alu::{
+            IMM_VALUE_BOUNDARY_12BIT_MAX, IMM_VALUE_BOUNDARY_12BIT_MIN, IMM_VALUE_BOUNDARY_POW2,
+            IMM_VALUE_BOUNDARY_SIGN_EDGE,
+        };
+        assert_eq!(classify_imm_value(2047), Some(IMM_VALUE_BOUNDARY_12BIT_MAX));
+        assert_eq!(classify_imm_value(-2047), Some(IMM_VALUE_BOUNDARY_12BIT_MAX));
+        assert_eq!(classify_imm_value(-2048), Some(IMM_VALUE_BOUNDARY_12BIT_MIN));
+        assert_eq!(classify_imm_value(2048), Some(IMM_VALUE_BOUNDARY_12BIT_MIN));
+        assert_eq!(classify_imm_value(-16), Some(IMM_VALUE_BOUNDARY_POW2));
+        assert_eq!(classify_imm_value(1024), Some(IMM_VALUE_BOUNDARY_POW2));
+        assert_eq!(classify_imm_value(0x17ff), Some(IMM_VALUE_BOUNDARY_SIGN_EDGE));
+        assert_eq!(classify_imm_value(0x1800), Some(IMM_VALUE_BOUNDARY_SIGN_EDGE));
+        assert_eq!(classify_imm_value(1), None);
+        assert_eq!(classify_imm_value(0), None);
+        assert_eq!(classify_imm_value(100), None);
+        assert_eq!(classify_imm_value(i32::MIN), None);
+    }
+
+    #[test]
+    fn opcode_bigrams_emit_one_hit_per_distinct_pair() {
+        let hits = match_opcode_bigram_semantic_hits(["lw", "beq", "lw", "beq", "addi", "mret"]);
+        let ids: Vec<&str> = hits.iter().map(|h| h.bucket_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "sem.program.opcode_bigram.load.branch",
+                "sem.program.opcode_bigram.branch.load",
+                "sem.program.opcode_bigram.branch.alu",
+                "sem.program.opcode_bigram.alu.system",
+            ]
+        );
+        assert!(ids.iter().all(|id| semantic::by_id(id).is_some()));
+        assert!(match_opcode_bigram_semantic_hits(["lw", "bogus", "beq"]).is_empty());
+    }
+
+    #[test]
+    fn undecodable_words_break_word_derived_bigrams() {
+        // lw x1, 0(x0); an undecodable word; beq x0, x0, 0
+        let words = [0x0000_2083, 0xffff_ffff, 0x0000_0063];
+        let mut hits = Vec::new();
+        extend_word_derived_hits(&mut hits, &words);
+        assert!(!hits.iter().any(|hit| hit.bucket_id.starts_with("sem.program.opcode_bigram.")));
+
+        let mut hits = Vec::new();
+        extend_word_derived_hits(&mut hits, &[words[0], words[2]]);
+        assert!(hits.iter().any(|hit| hit.bucket_id == "sem.program.opcode_bigram.load.branch"));
+    }
+
+    #[test]
+    fn phantom_sub_kinds_hit_once_each_and_unknown_discriminants_are_other() {
+        let hits =
+            match_phantom_sub_kind_semantic_hits([(3, 0x20), (5, 0x20), (7, 0x99), (9, 0x01)]);
+        let ids: Vec<&str> = hits.iter().map(|h| h.bucket_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "sem.control.phantom_sub_kind.hint_input",
+                "sem.control.phantom_sub_kind.other",
+                "sem.control.phantom_sub_kind.debug_panic",
+            ]
+        );
+        assert_eq!(hits[0].details["first_step_idx"], 3);
+        assert_eq!(hits[1].details["phantom_sub_kind"], "other");
+        assert_eq!(hits[1].details["discriminant"], 0x99);
+        assert!(ids.iter().all(|id| semantic::by_id(id).is_some()));
+    }
+
+    #[test]
+    fn bucket_listing_is_sorted_then_ends_with_the_family_templates() {
+        let ids = semantic::all_bucket_ids();
+        let (concrete, templates) =
+            ids.split_at(ids.len() - semantic::BUCKET_FAMILY_TEMPLATES.len());
+        assert_eq!(templates, semantic::BUCKET_FAMILY_TEMPLATES);
+        assert!(concrete.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(concrete.iter().all(|id| semantic::by_id(id).is_some()));
+        assert_eq!(concrete.len(), semantic::all_buckets().count());
+        let bigram = semantic::program::OPCODE_BIGRAMS[0][1].id;
+        assert!(concrete.iter().any(|id| id == bigram));
+    }
+
+    #[test]
+    fn effectful_x0_write_is_split_from_plain_coverage() {
+        let obs = |op_idx, rd_data| ZeroRegisterWriteObservation {
+            op_idx,
+            pc: op_idx * 4,
+            raw_word: 0x0010_0013,
+            mnemonic: "addi".to_string(),
+            rd_data,
+        };
+        let hits =
+            match_zero_register_semantic_hits(&[obs(0, None), obs(1, Some(0)), obs(2, Some(1))]);
+        let ids: Vec<&str> = hits.iter().map(|h| h.bucket_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                semantic::decode::ZERO_REGISTER_IMMUTABILITY.id,
+                semantic::decode::ZERO_REGISTER_IMMUTABILITY.id,
+                semantic::decode::ZERO_REGISTER_IMMUTABILITY.id,
+                semantic::decode::ZERO_REGISTER_WRITE_EFFECTFUL.id,
+            ]
+        );
+        assert!(match_final_zero_register_semantic_hits(0).is_empty());
+        assert_eq!(match_final_zero_register_semantic_hits(7).len(), 1);
+    }
+
+    #[test]
+    fn interaction_kinds_emit_one_hit_each_with_unknown_as_other() {
+        let hits = match_interaction_kind_semantic_hits([
+            "memory",
+            "execution",
+            "memory",
+            "blake3",
+            "poseidon2",
+            "ecdsa",
+        ]);
+        let ids: Vec<&str> = hits.iter().map(|h| h.bucket_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "sem.interaction.kind_seen.memory",
+                "sem.interaction.kind_seen.execution",
+                "sem.interaction.kind_seen.other",
+                "sem.interaction.kind_seen.poseidon2",
+            ]
+        );
+        assert!(ids.iter().all(|id| semantic::by_id(id).is_some()));
+    }
+
+    #[test]
+    fn bus_balance_flags_only_two_sided_buses_that_do_not_cancel() {
+        let balanced = [("execution", -1), ("execution", 1), ("execution", -1), ("execution", 1)];
+        assert!(match_bus_balance_semantic_hits(balanced).is_empty());
+        // The program bus is receive-only here, so it has nothing to balance against.
+        assert!(match_bus_balance_semantic_hits([("program", -1), ("program", -1)]).is_empty());
+
+        let hits = match_bus_balance_semantic_hits([
+            ("execution", -1),
+            ("execution", 2),
+            ("program", -1),
+            ("memory", 1),
+            ("memory", -1),
+        ]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::interaction::BUS_IMBALANCE.id);
+        assert_eq!(hits[0].details["interaction_kind"], "execution");
+        assert_eq!(hits[0].details["net_multiplicity"], 1);
+    }
+
+    #[test]
+    fn effective_ptr_mismatch_fires_once_on_the_first_disagreeing_row() {
+        let obs = |step_idx, rs1_val, imm, effective_ptr| EffectivePtrObservation {
+            step_idx,
+            op_idx: 0,
+            chip_name: "LoadStore".to_string(),
+            op: 0,
+            rs1_val,
+            imm,
+            effective_ptr,
+        };
+        // A negative immediate wraps below rs1, and wrapping past zero is still consistent.
+        let consistent = [obs(0, 0x100, -4, 0xfc), obs(1, 0, -1, u32::MAX)];
+        assert!(match_effective_ptr_semantic_hits(&consistent).is_empty());
+
+        // A sign-flipped immediate (0x100 + 0xfffc instead of 0x100 - 4) disagrees.
+        let hits = match_effective_ptr_semantic_hits(&[
+            obs(0, 0x100, -4, 0xfc),
+            obs(1, 0x100, -4, 0x100 + 0xfffc),
+            obs(2, 0, 8, 0),
+        ]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::memory::EFFECTIVE_PTR_MISMATCH.id);
+        assert_eq!(hits[0].details["step_idx"], 1);
+        assert_eq!(hits[0].details["expected_ptr"], 0xfc);
+    }
+
+    #[test]
+    fn lui_addi_fusion_reports_materialized_constant() {
+        // lui a0, 0x12345; addi a0, a0, -1; lui a1, 1; addi a2, a1, 1
+        let words = [0x1234_5537, 0xfff5_0513, 0x0000_15b7, 0x0015_8613];
+        let hits = match_lui_addi_fusion_semantic_hits((0u64..).zip(words));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::decode::LUI_ADDI_FUSION_SEEN.id);
+        assert_eq!(hits[0].details["constant"], "0x12344fff");
+
+        // Backends that only know the executed words get it through the word-derived hits.
+        let mut hits = Vec::new();
+        extend_word_derived_hits(&mut hits, &words);
+        assert!(hits.iter().any(|hit| hit.bucket_id == semantic::decode::LUI_ADDI_FUSION_SEEN.id));
+    }
+
+    #[test]
+    fn range_check_splits_out_of_range_from_full_width_high_bit() {
+        let obs =
+            |value, max_bits| RangeCheckObservation { step_idx: 0, op_idx: 0, value, max_bits };
+        let ids = |observations: &[RangeCheckObservation]| -> Vec<String> {
+            match_range_check_semantic_hits(observations).into_iter().map(|h| h.bucket_id).collect()
+        };
+        assert!(ids(&[obs(0xff, 8), obs(0x7fff_ffff, 32)]).is_empty());
+        assert_eq!(
+            ids(&[obs(0x100, 8), obs(0x200, 8)]),
+            vec![semantic::lookup::RANGE_CHECK_VALUE_OUT_OF_RANGE.id]
+        );
+        assert_eq!(
+            ids(&[obs(0x8000_0000, 32)]),
+            vec![semantic::lookup::RANGE_CHECK_FULL_WIDTH_HIGH_BIT.id]
+        );
+    }
+
+    #[test]
+    fn repeated_instruction_runs_bucket_the_longest_run() {
+        let nop = 0x0000_0013;
+        let ids = |words: &[u32], min_len| -> Vec<String> {
+            match_repeated_instruction_run_semantic_hits(words.iter().copied(), min_len)
+                .into_iter()
+                .map(|h| h.bucket_id)
+                .collect()
+        };
+        assert!(ids(&[nop, nop, 1, nop, nop], 3).is_empty());
+        assert_eq!(
+            ids(&[1, nop, nop, nop, 2, 2, 2, 2, 2], 3),
+            vec![semantic::program::REPEATED_RUN_5_16.id]
+        );
+        assert!(ids(&[nop; 4], 5).is_empty());
+        assert_eq!(ids(&[nop; 4], 0), vec![semantic::program::REPEATED_RUN_3_4.id]);
+    }
+
+    #[test]
+    fn mul_mulh_pairs_fire_only_on_inconsistent_products() {
+        let obs = |step_idx: u64, mnemonic: &str, rs1: u32, rs2: u32, result: u32| {
+            MulProductObservation {
+                step_idx,
+                op_idx: 0,
+                mnemonic: mnemonic.to_string(),
+                rs1,
+                rs2,
+                result,
+            }
+        };
+        let mul = obs(0, "mul", 0xFFFF_FFFF, 2, 0xFFFF_FFFE);
+        // -1 * 2 = -2 signed, 0x1_FFFF_FFFE unsigned; operand order does not matter for `mul`.
+        let consistent = [
+            mul.clone(),
+            obs(1, "mulh", 2, 0xFFFF_FFFF, 0xFFFF_FFFF),
+            obs(2, "mulhu", 0xFFFF_FFFF, 2, 1),
+        ];
+        assert!(match_mul_mulh_product_semantic_hits(&consistent).is_empty());
+
+        let broken = [mul, obs(1, "mulhu", 0xFFFF_FFFF, 2, 0), obs(2, "mulh", 0xFFFF_FFFF, 2, 0)];
+        let hits = match_mul_mulh_product_semantic_hits(&broken);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bucket_id, semantic::arithmetic::MUL_MULH_PRODUCT_INCONSISTENT.id);
+    }
 }
@@ -4,7 +4,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use clap::{Arg, Command};
 use serde_json::json;
 
-use beak_core::fuzz::benchmark::{run_benchmark_threaded, BenchmarkConfig, DEFAULT_RNG_SEED};
+use beak_core::fuzz::benchmark::{
+    evaluate_stdin_threaded, run_benchmark_threaded, BenchmarkConfig, DEFAULT_RNG_SEED,
+};
 use beak_core::rv32im::oracle::{OracleConfig, OracleMemoryModel};
 
 use beak_jolt_e9caa235::backend::JoltBackend;
@@ -160,6 +162,12 @@ fn main() {
                 .default_value("0")
                 .help("Oracle zeroed data RAM bytes for split-code-data mode."),
         )
+        .arg(
+            Arg::new("eval_stdin")
+                .long("eval-stdin")
+                .action(clap::ArgAction::SetTrue)
+                .help("Evaluate one program read from stdin (hex words or RV32IM assembly) and print a JSON report."),
+        )
         .get_matches();
 
     let root = workspace_root();
@@ -233,7 +241,9 @@ fn main() {
             memory_model: oracle_memory_model,
             code_base: oracle_code_base,
             data_size_bytes: oracle_data_size_bytes,
+            fence_policy: Default::default(),
         },
+        ignored_regs: None,
         seeds_jsonl: seeds_path,
         out_dir: root.join("storage/fuzzing_seeds"),
         output_prefix: None,
@@ -246,9 +256,25 @@ fn main() {
         semantic_step_stride,
         semantic_max_trials_per_bucket,
         stack_size_bytes: 256 * 1024 * 1024,
+        record_bug_traces: false,
     };
 
     println!("oracle_code_base = 0x{JOLT_ORACLE_CODE_BASE:08x}");
+    if matches.get_flag("eval_stdin") {
+        let res =
+            evaluate_stdin_threaded(&cfg, move || JoltBackend::new(max_instructions, timeout_ms));
+        match res {
+            Ok(report) => {
+                println!("{}", serde_json::to_string_pretty(&report).expect("serialize report"))
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     let res = run_benchmark_threaded(cfg, move || JoltBackend::new(max_instructions, timeout_ms));
     match res {
         Ok(out) => {
@@ -257,6 +283,9 @@ fn main() {
             if let Some(runs_path) = out.runs_path.as_ref() {
                 println!("Wrote runs   JSONL: {}", runs_path.display());
             }
+            if let Some(histogram_path) = out.bucket_histogram_path.as_ref() {
+                println!("Wrote bucket histogram: {}", histogram_path.display());
+            }
         }
         Err(e) => {
             eprintln!("{e}");
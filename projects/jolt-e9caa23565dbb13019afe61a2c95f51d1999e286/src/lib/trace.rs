@@ -23,7 +23,10 @@ impl JoltTrace {
                 raw_word: *word,
             })
             .collect::<Vec<_>>();
-        let bucket_hits = semantic_matchers::match_upper_immediate_semantic_hits(&observations);
+        let mut bucket_hits = semantic_matchers::match_upper_immediate_semantic_hits(&observations);
+        bucket_hits.extend(semantic_matchers::match_lui_addi_fusion_semantic_hits(
+            (0u64..).zip(words.iter().copied()),
+        ));
 
         Ok(Self { bucket_hits, instruction_count: words.len() })
     }
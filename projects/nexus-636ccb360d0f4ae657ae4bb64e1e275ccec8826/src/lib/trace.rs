@@ -65,10 +65,14 @@ impl NexusTrace {
 
     pub fn from_words_and_uniform_trace(words: &[u32], trace: &UniformTrace) -> Self {
         let mut observations = Vec::new();
+        let mut executed_words = Vec::new();
         let mut global_step = 0u64;
 
         for block in &trace.blocks {
             for step in &block.steps {
+                if let Some(&word) = words.get(global_step as usize) {
+                    executed_words.push((global_step, word));
+                }
                 for record in &step.memory_records {
                     if let MemoryRecord::StoreRecord((size, address, value, prev_value), _) = record
                     {
@@ -87,7 +91,8 @@ impl NexusTrace {
             }
         }
 
-        let bucket_hits = semantic_matchers::match_memory_write_semantic_hits(&observations);
+        let mut bucket_hits = semantic_matchers::match_memory_write_semantic_hits(&observations);
+        bucket_hits.extend(semantic_matchers::match_lui_addi_fusion_semantic_hits(executed_words));
         Self { bucket_hits, step_count: trace.blocks.iter().map(|block| block.steps.len()).sum() }
     }
 
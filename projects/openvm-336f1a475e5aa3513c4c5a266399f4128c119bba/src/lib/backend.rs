@@ -1,8 +1,16 @@
 use beak_core::fuzz::benchmark::{
     BackendEval, BenchmarkBackend, InjectionSchedule, SemanticInjectionCandidate,
 };
+use beak_core::fuzz::loop1::{BackendCapabilities, WitnessBitFlip, WitnessFlipSite};
+use beak_core::fuzz::rebucket::trace_archive;
+use beak_core::fuzz::worker_diagnostics::worker_diagnostics;
+use beak_core::fuzz::worker_protocol::{
+    WorkerMessage, format_worker_message, parse_worker_message,
+};
 use beak_core::rv32im::instruction::RV32IMInstruction;
-use beak_core::trace::{Trace, TraceSignal, semantic};
+use beak_core::rv32im::supported::{FencePolicy, SupportedOpcodes};
+use beak_core::trace::limbs::read_final_regs_from_memory;
+use beak_core::trace::{Trace, TraceSignal, semantic, semantic_matchers};
 
 use crate::trace::OpenVMTrace;
 use openvm_circuit::arch::VmExecutor;
@@ -11,7 +19,7 @@ use openvm_instructions::SystemOpcode;
 use openvm_instructions::exe::VmExe;
 use openvm_instructions::instruction::Instruction;
 use openvm_instructions::program::Program;
-use openvm_instructions::riscv::RV32_REGISTER_AS;
+use openvm_instructions::riscv::{RV32_MEMORY_AS, RV32_REGISTER_AS};
 use openvm_rv32im_transpiler::{Rv32ITranspilerExtension, Rv32MTranspilerExtension};
 use openvm_sdk::config::{AppConfig, SdkVmConfig};
 use openvm_sdk::prover::AppProver;
@@ -21,7 +29,10 @@ use openvm_transpiler::transpiler::Transpiler;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
@@ -47,6 +58,14 @@ fn build_vm_config() -> SdkVmConfig {
     vm_config
 }
 
+const TERMINATE_EXIT_CODE_ENV: &str = "BEAK_OPENVM_TERMINATE_EXIT_CODE";
+
+/// Exit code of the TERMINATE that `build_exe` appends (`BEAK_OPENVM_TERMINATE_EXIT_CODE`,
+/// default 0). A nonzero code drives the VM's failed-terminate path.
+fn terminate_exit_code() -> usize {
+    std::env::var(TERMINATE_EXIT_CODE_ENV).ok().and_then(|v| v.trim().parse().ok()).unwrap_or(0)
+}
+
 fn build_exe(words: &[u32]) -> Result<std::sync::Arc<VmExe<F>>, String> {
     let transpiler = Transpiler::<F>::default()
         .with_extension(Rv32ITranspilerExtension)
@@ -57,26 +76,47 @@ fn build_exe(words: &[u32]) -> Result<std::sync::Arc<VmExe<F>>, String> {
     for opt in transpiled.into_iter().flatten() {
         instructions.push(opt);
     }
-    instructions.push(Instruction::from_usize(SystemOpcode::TERMINATE.global_opcode(), [0, 0, 0]));
+    // Keep a program-driven exit (the input ends in its own terminate); otherwise append one.
+    let terminate = SystemOpcode::TERMINATE.global_opcode();
+    if instructions.last().is_none_or(|insn| insn.opcode != terminate) {
+        instructions.push(Instruction::from_usize(terminate, [0, 0, terminate_exit_code()]));
+    }
 
     let program = Program::from_instructions(&instructions);
     Ok(std::sync::Arc::new(VmExe::new(program)))
 }
 
-fn is_openvm_supported_rv32_word(_word: u32) -> bool {
-    true
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkerRequest {
     pub request_id: u64,
     pub words: Vec<u32>,
     pub iteration: u64,
     pub inject_kind: Option<String>,
     pub inject_step: u64,
+    /// Keep this run's raw logs in the worker for a later `dump_trace_to` request.
+    #[serde(default)]
+    pub retain_trace: bool,
+    /// Instead of executing `words`, write the trace retained from the previous run to this path.
+    #[serde(default)]
+    pub dump_trace_to: Option<PathBuf>,
+    /// Field-level witness injection armed for this run (`None` clears the previous one).
+    #[serde(default)]
+    pub inject_path: Option<WitnessPathPlan>,
+}
+
+/// Flip `bit` of `field_path` in the `chip_kind` row emitted at instruction step `step` (only the
+/// row with index `op_idx` within the step when set); see
+/// `fuzzer_utils::configure_witness_injection_path`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessPathPlan {
+    pub step: u64,
+    pub op_idx: Option<u64>,
+    pub chip_kind: String,
+    pub field_path: String,
+    pub bit: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkerResponse {
     pub request_id: u64,
     pub final_regs: Option<[u32; 32]>,
@@ -86,9 +126,67 @@ pub struct WorkerResponse {
     pub backend_error: Option<String>,
     pub observed_injection_sites: BTreeMap<String, Vec<u64>>,
     pub injection_applied: bool,
+    /// Whether an applied injection changed the outcome relative to a clean reference run.
+    #[serde(default)]
+    pub injection_caught: Option<bool>,
+    /// Slowest mnemonics by attributed wall time in microseconds (only with
+    /// `BEAK_WORKER_OPCODE_TIMING=1`).
+    #[serde(default)]
+    pub slowest_opcodes: Vec<(String, u64)>,
+    /// The worker hit its `MAX_MICRO_OPS_ENV` cap, so bucket hits cover only a trace prefix.
+    #[serde(default)]
+    pub trace_truncated: bool,
+    /// Witness columns of this run's chip rows (see `fuzzer_utils::witness_flip_sites`).
+    #[serde(default)]
+    pub witness_flip_sites: BTreeMap<String, Vec<u64>>,
 }
 
-const WORKER_RESPONSE_PREFIX: &str = "__BEAK_WORKER_JSON__ ";
+/// Map one worker stdout line to the host's view; `None` for noise, logs and heartbeats.
+fn decode_worker_line(line: &str) -> Option<Result<WorkerResponse, String>> {
+    match parse_worker_message::<WorkerRequest, WorkerResponse>(line)? {
+        Ok(WorkerMessage::Response(resp)) => Some(Ok(resp)),
+        Ok(WorkerMessage::Error { request_id, message }) => {
+            Some(Err(format!("worker error (request {request_id:?}): {message}")))
+        }
+        Ok(WorkerMessage::Log { message }) => {
+            eprintln!("[openvm-worker] {message}");
+            None
+        }
+        Ok(WorkerMessage::Heartbeat { .. } | WorkerMessage::Request(_)) => None,
+        Err(e) => {
+            let mut preview = line.chars().take(200).collect::<String>();
+            if line.chars().count() > 200 {
+                preview.push_str("...");
+            }
+            Some(Err(format!("{e}; raw={preview:?}")))
+        }
+    }
+}
+
+/// Micro-ops one worker run may record (unset or 0 = unbounded). Past the cap the run reports
+/// `trace_truncated` instead of growing the trace until the worker runs out of memory.
+pub const MAX_MICRO_OPS_ENV: &str = "BEAK_WORKER_MAX_MICRO_OPS";
+
+/// Apply `MAX_MICRO_OPS_ENV` to the trace collector. Called once at worker startup.
+pub fn configure_worker_trace_cap() {
+    let max = std::env::var(MAX_MICRO_OPS_ENV).ok().and_then(|v| v.parse().ok()).unwrap_or(0);
+    fuzzer_utils::set_max_micro_ops(max);
+}
+
+/// Comma-separated `selector=weight` overrides of the opcode weights mutated instructions are
+/// drawn with (see `fuzzer_utils::OpcodeWeights`); unset keeps the defaults.
+pub const OPCODE_WEIGHTS_ENV: &str = "BEAK_WORKER_OPCODE_WEIGHTS";
+
+/// Apply `OPCODE_WEIGHTS_ENV` to the instruction mutator. Called once at worker startup.
+pub fn configure_worker_opcode_weights() {
+    let Ok(spec) = std::env::var(OPCODE_WEIGHTS_ENV) else { return };
+    match spec.parse() {
+        Ok(weights) => fuzzer_utils::set_opcode_weights(weights),
+        Err(e) => eprintln!("[openvm-worker] ignoring {OPCODE_WEIGHTS_ENV}: {e}"),
+    }
+}
+const OPCODE_TIMING_ENV: &str = "BEAK_WORKER_OPCODE_TIMING";
+const OPCODE_TIMING_TOP_N: usize = 5;
 const OPENVM_RV32_POINTER_MAX_BITS: u64 = 29;
 
 fn base_inject_kind(kind: &str) -> &str {
@@ -99,12 +197,108 @@ fn inject_kind_with_variant(kind: &str, variant: &str) -> String {
     if variant.is_empty() { kind.to_string() } else { format!("{kind}::{variant}") }
 }
 
+fn opcode_timing_enabled() -> bool {
+    std::env::var(OPCODE_TIMING_ENV)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Aggregate per-step wall time into per-mnemonic totals and keep the `top_n` slowest.
+///
+/// Steps are mapped to RV32 words via `pc / 4`; steps past the input (the appended TERMINATE)
+/// are reported as `terminate`.
+fn slowest_opcodes(
+    trace: &OpenVMTrace,
+    words: &[u32],
+    step_nanos: &BTreeMap<u64, u128>,
+    top_n: usize,
+) -> Vec<(String, u64)> {
+    let mut by_mnemonic: BTreeMap<String, u128> = BTreeMap::new();
+    for insn in trace.instructions() {
+        let Some(nanos) = step_nanos.get(&insn.step_idx) else { continue };
+        let mnemonic = words
+            .get((insn.pc / 4) as usize)
+            .and_then(|w| RV32IMInstruction::from_word(*w).ok())
+            .map(|i| i.mnemonic)
+            .unwrap_or_else(|| "terminate".to_string());
+        *by_mnemonic.entry(mnemonic).or_insert(0) += nanos;
+    }
+    let mut out: Vec<(String, u64)> =
+        by_mnemonic.into_iter().map(|(m, nanos)| (m, (nanos / 1_000) as u64)).collect();
+    out.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    out.truncate(top_n);
+    out
+}
+
+/// Set from `WorkerRequest::retain_trace` before each run.
+static RETAIN_TRACE: AtomicBool = AtomicBool::new(false);
+/// `trace_archive` of the worker's last run, kept only while `RETAIN_TRACE` is set.
+static RETAINED_TRACE: Mutex<Option<serde_json::Value>> = Mutex::new(None);
+
+/// Whether `run_backend_once` keeps its raw logs for a later `dump_retained_trace`.
+pub fn set_retain_trace(retain: bool) {
+    RETAIN_TRACE.store(retain, Ordering::Relaxed);
+}
+
+/// Arm `WorkerRequest::inject_path` for the next run, or clear it.
+pub fn set_path_injection(plan: Option<&WitnessPathPlan>) {
+    match plan {
+        Some(plan) => fuzzer_utils::configure_witness_injection_path(
+            plan.step,
+            plan.op_idx,
+            &plan.chip_kind,
+            &plan.field_path,
+            fuzzer_utils::WitnessMutation::FlipBit(plan.bit),
+        ),
+        None => fuzzer_utils::clear_witness_injection_path(),
+    }
+}
+
+/// Serve a `WorkerRequest::dump_trace_to` request: write the trace retained from the last run.
+pub fn dump_retained_trace(request_id: u64, path: &Path) -> Result<WorkerResponse, String> {
+    let retained = RETAINED_TRACE.lock().unwrap_or_else(|e| e.into_inner());
+    let archive =
+        retained.as_ref().ok_or_else(|| "no trace retained from the last run".to_string())?;
+    let json =
+        serde_json::to_string(archive).map_err(|e| format!("serialize trace failed: {e}"))?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("write trace {} failed: {e}", path.display()))?;
+    Ok(WorkerResponse { request_id, ..Default::default() })
+}
+
+/// Run `words` once in the worker. A failed run still leaves the micro-ops it logged before
+/// stopping as the retained trace, so the bug it raises has something to dump.
 pub fn run_backend_once(
     request_id: u64,
     words: &[u32],
     current_iteration: u64,
     inject_kind: Option<&str>,
     inject_step: u64,
+) -> Result<WorkerResponse, String> {
+    let result =
+        run_backend_uncaptured(request_id, words, current_iteration, inject_kind, inject_step);
+    if result.is_err() {
+        retain_failed_trace(words);
+    }
+    result
+}
+
+/// Retain the micro-ops logged so far for a run of `words` that stopped early (an error or a
+/// panic unwinding to the worker loop); the next run clears the log either way.
+pub fn retain_failed_trace(words: &[u32]) {
+    let logs = fuzzer_utils::take_json_logs();
+    if RETAIN_TRACE.load(Ordering::Relaxed) {
+        *RETAINED_TRACE.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some(trace_archive(logs, &[], words));
+    }
+}
+
+fn run_backend_uncaptured(
+    request_id: u64,
+    words: &[u32],
+    current_iteration: u64,
+    inject_kind: Option<&str>,
+    inject_step: u64,
 ) -> Result<WorkerResponse, String> {
     let t_total = Instant::now();
     let mut eval = BackendEval::default();
@@ -116,11 +310,14 @@ pub fn run_backend_once(
             std::env::remove_var("BEAK_OPENVM_ENABLE_O8");
         }
     }
-    fuzzer_utils::configure_witness_injection(inject_kind, inject_step);
+    fuzzer_utils::configure_witness_injection(inject_kind, inject_step, None);
     if let Some(kind) = inject_kind {
         eprintln!("[beak-inject-arm] kind={} step={}", kind, inject_step);
     }
     let _ = fuzzer_utils::take_json_logs();
+    let _ = fuzzer_utils::take_path_injection_applied();
+    *RETAINED_TRACE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    let _ = fuzzer_utils::take_step_nanos();
 
     let t0 = Instant::now();
     let exe = build_exe(words).map_err(|e| {
@@ -166,12 +363,9 @@ pub fn run_backend_once(
 
     let t3 = Instant::now();
     let state = vm_result.final_memory.as_ref().ok_or_else(|| "no final state".to_string())?;
-    let mut regs = [0u32; 32];
-    for i in 0..32u32 {
-        let limbs = state.get_range::<4>(&(RV32_REGISTER_AS, i * 4));
-        let bytes: [u8; 4] = limbs.map(|x| x.as_canonical_u32() as u8);
-        regs[i as usize] = u32::from_le_bytes(bytes);
-    }
+    let regs = read_final_regs_from_memory(|offset| {
+        state.get_range::<4>(&(RV32_REGISTER_AS, offset)).map(|x| x.as_canonical_u32() as u8)
+    });
     eval.final_regs = Some(regs);
     let ms_read_regs = t3.elapsed().as_millis();
 
@@ -189,34 +383,76 @@ pub fn run_backend_once(
             kind, inject_step, observed, applied, injection_applied
         );
     }
+    let trace_truncated = fuzzer_utils::trace_truncated();
     let logs = fuzzer_utils::take_json_logs();
+    let path_injection_applied = fuzzer_utils::take_path_injection_applied();
+    let witness_flip_sites = fuzzer_utils::witness_flip_sites(&logs);
+    let retained_logs = RETAIN_TRACE.load(Ordering::Relaxed).then(|| logs.clone());
+    let step_nanos = fuzzer_utils::take_step_nanos();
+    let mut slowest = Vec::new();
     let ms_take_logs = t4.elapsed().as_millis();
     let logs_len = logs.len();
+    if trace_truncated && worker_diagnostics().per_run() {
+        eprintln!(
+            "[openvm-backend-worker] iter={} trace truncated at {logs_len} micro-ops",
+            current_iteration
+        );
+    }
 
     let t5 = Instant::now();
     match OpenVMTrace::from_logs(logs) {
         Ok(trace) => {
+            let violations = trace.check_invariants();
+            if !violations.is_empty() {
+                let msg = format!(
+                    "trace invariants violated: {}",
+                    violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+                );
+                if worker_diagnostics().errors() {
+                    eprintln!("[openvm-backend-worker] iter={} ERROR {msg}", current_iteration);
+                }
+                eval.backend_error = Some(msg);
+            }
             let insn_count = trace.instructions().len();
             let row_count = trace.chip_rows().len();
             let hit_count = trace.bucket_hits().len();
             eval.micro_op_count = trace.instruction_count();
             eval.bucket_hits = trace.bucket_hits().to_vec();
             eval.trace_signals = trace.trace_signals().to_vec();
-            let ms_parse = t5.elapsed().as_millis();
-            eprintln!(
-                "[openvm-backend-worker] iter={} logs_len={logs_len} insn_count={insn_count} chip_rows={row_count} bucket_hits={hit_count} build_exe_ms={ms_build_exe} instance_ms={ms_instance} trace_only_ms={ms_trace_only} read_regs_ms={ms_read_regs} take_logs_ms={ms_take_logs} parse_ms={ms_parse} total_ms={}",
-                current_iteration,
-                t_total.elapsed().as_millis()
+            let executed_words = semantic_matchers::executed_words(
+                words,
+                trace.instructions().iter().map(|insn| u64::from(insn.pc)),
             );
+            semantic_matchers::extend_word_derived_hits(&mut eval.bucket_hits, &executed_words);
+            if opcode_timing_enabled() {
+                slowest = slowest_opcodes(&trace, words, &step_nanos, OPCODE_TIMING_TOP_N);
+                let summary: Vec<String> =
+                    slowest.iter().map(|(m, us)| format!("{m}:{us}us")).collect();
+                eprintln!(
+                    "[openvm-backend-worker] iter={} slowest_opcodes={}",
+                    current_iteration,
+                    summary.join(",")
+                );
+            }
+            let ms_parse = t5.elapsed().as_millis();
+            if worker_diagnostics().per_run() {
+                eprintln!(
+                    "[openvm-backend-worker] iter={} logs_len={logs_len} insn_count={insn_count} chip_rows={row_count} bucket_hits={hit_count} build_exe_ms={ms_build_exe} instance_ms={ms_instance} trace_only_ms={ms_trace_only} read_regs_ms={ms_read_regs} take_logs_ms={ms_take_logs} parse_ms={ms_parse} total_ms={}",
+                    current_iteration,
+                    t_total.elapsed().as_millis()
+                );
+            }
         }
         Err(e) => {
             let ms_parse = t5.elapsed().as_millis();
             eval.backend_error = Some(e.clone());
-            eprintln!(
-                "[openvm-backend-worker] iter={} ERROR parse_logs ({e}); logs_len={logs_len} build_exe_ms={ms_build_exe} instance_ms={ms_instance} trace_only_ms={ms_trace_only} read_regs_ms={ms_read_regs} take_logs_ms={ms_take_logs} parse_ms={ms_parse} total_ms={}",
-                current_iteration,
-                t_total.elapsed().as_millis()
-            );
+            if worker_diagnostics().errors() {
+                eprintln!(
+                    "[openvm-backend-worker] iter={} ERROR parse_logs ({e}); logs_len={logs_len} build_exe_ms={ms_build_exe} instance_ms={ms_instance} trace_only_ms={ms_trace_only} read_regs_ms={ms_read_regs} take_logs_ms={ms_take_logs} parse_ms={ms_parse} total_ms={}",
+                    current_iteration,
+                    t_total.elapsed().as_millis()
+                );
+            }
         }
     }
 
@@ -244,10 +480,12 @@ pub fn run_backend_once(
         }
     }
     let ms_prove_verify = t6.elapsed().as_millis();
-    eprintln!(
-        "[openvm-backend-worker] iter={} prove_verify_ms={ms_prove_verify}",
-        current_iteration
-    );
+    if worker_diagnostics().per_run() {
+        eprintln!(
+            "[openvm-backend-worker] iter={} prove_verify_ms={ms_prove_verify}",
+            current_iteration
+        );
+    }
     let prove_phase_applied = fuzzer_utils::take_applied_witness_sites();
     for (kind, steps) in prove_phase_applied {
         applied_injection_sites.entry(kind).or_default().extend(steps);
@@ -266,6 +504,18 @@ pub fn run_backend_once(
             }
         })
         .unwrap_or(false);
+    injection_applied |= path_injection_applied;
+
+    let summary = worker_diagnostics()
+        .record(t_total.elapsed().as_millis(), eval.backend_error.is_some());
+    if let Some(line) = summary {
+        eprintln!("[openvm-backend-worker] iter={} summary {line}", current_iteration);
+    }
+
+    if let Some(logs) = retained_logs {
+        *RETAINED_TRACE.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some(trace_archive(logs, &eval.bucket_hits, words));
+    }
 
     Ok(WorkerResponse {
         request_id,
@@ -276,9 +526,54 @@ pub fn run_backend_once(
         backend_error: eval.backend_error,
         observed_injection_sites,
         injection_applied,
+        injection_caught: None,
+        slowest_opcodes: slowest,
+        trace_truncated,
+        witness_flip_sites,
     })
 }
 
+fn run_outcome(resp: &WorkerResponse) -> (Option<[u32; 32]>, bool) {
+    (resp.final_regs, resp.backend_error.is_none())
+}
+
+/// Like `run_backend_once`, but when an injection is armed also performs a clean reference run
+/// and reports whether the injected fault changed final regs or proving success.
+///
+/// An applied fault that leaves the outcome unchanged was silently accepted by the constraint
+/// system (`injection_caught = Some(false)`), which is the under-constrained signal we want.
+pub fn run_backend_checked(
+    request_id: u64,
+    words: &[u32],
+    current_iteration: u64,
+    inject_kind: Option<&str>,
+    inject_step: u64,
+) -> Result<WorkerResponse, String> {
+    let Some(kind) = inject_kind else {
+        return run_backend_once(request_id, words, current_iteration, None, inject_step);
+    };
+    let clean = run_backend_once(request_id, words, current_iteration, None, 0);
+    let mut injected =
+        run_backend_once(request_id, words, current_iteration, Some(kind), inject_step)?;
+    // Only a clean run that proved gives an outcome the fault could have left unchanged.
+    injected.injection_caught = match clean {
+        Ok(clean) if injected.injection_applied && clean.backend_error.is_none() => {
+            Some(run_outcome(&clean) != run_outcome(&injected))
+        }
+        _ => None,
+    };
+    if worker_diagnostics().per_run() {
+        eprintln!(
+            "[beak-inject-caught] kind={} step={} applied={} caught={:?}",
+            kind, inject_step, injected.injection_applied, injected.injection_caught
+        );
+    }
+    Ok(injected)
+}
+
+/// `addi x0, x0, 0`: the cheapest program that still takes a fresh worker through SDK setup.
+const WARM_UP_PROGRAM: &[u32] = &[0x0000_0013];
+
 struct WorkerProcess {
     child: Child,
     stdin: ChildStdin,
@@ -295,6 +590,11 @@ struct WitnessInjectionPlan {
 pub struct OpenVmBackend {
     max_instructions: usize,
     timeout_ms: u64,
+    /// Budget for spawning a worker and running `WARM_UP_PROGRAM` on it (process start, SDK
+    /// setup), kept out of the per-input `timeout_ms`.
+    startup_timeout_ms: u64,
+    supported_opcodes: SupportedOpcodes,
+    fence_policy: FencePolicy,
     eval: BackendEval,
     last_words: Vec<u32>,
     last_observed_injection_sites: BTreeMap<String, Vec<u64>>,
@@ -302,13 +602,29 @@ pub struct OpenVmBackend {
     next_request_id: u64,
     pending_injection: Option<WitnessInjectionPlan>,
     worker: Option<WorkerProcess>,
+    /// Ask the worker to keep each run's trace for `persist_last_trace`.
+    retain_last_trace: bool,
+    /// Stand-in archive for a last run whose worker was killed, taking its retained trace along.
+    abandoned_trace: Option<serde_json::Value>,
+    /// Bit flip armed by `arm_witness_bit_flip` for the next run.
+    pending_path_injection: Option<WitnessPathPlan>,
+    /// `WorkerResponse::witness_flip_sites` of the last run.
+    last_witness_flip_sites: BTreeMap<String, Vec<u64>>,
 }
 
 impl OpenVmBackend {
-    pub fn new(max_instructions: usize, timeout_ms: u64) -> Self {
+    pub fn new(
+        max_instructions: usize,
+        timeout_ms: u64,
+        supported_opcodes: SupportedOpcodes,
+        fence_policy: FencePolicy,
+    ) -> Self {
         Self {
             max_instructions,
             timeout_ms,
+            startup_timeout_ms: timeout_ms,
+            supported_opcodes,
+            fence_policy,
             eval: BackendEval::default(),
             last_words: Vec::new(),
             last_observed_injection_sites: BTreeMap::new(),
@@ -316,9 +632,18 @@ impl OpenVmBackend {
             next_request_id: 1,
             pending_injection: None,
             worker: None,
+            retain_last_trace: false,
+            abandoned_trace: None,
+            pending_path_injection: None,
+            last_witness_flip_sites: BTreeMap::new(),
         }
     }
 
+    pub fn with_startup_timeout_ms(mut self, startup_timeout_ms: u64) -> Self {
+        self.startup_timeout_ms = startup_timeout_ms;
+        self
+    }
+
     fn ordered_steps_around_anchor(steps: &[u64], anchor: u64) -> Vec<u64> {
         let mut ordered = steps.to_vec();
         ordered.sort_by_key(|step| {
@@ -665,6 +990,19 @@ impl OpenVmBackend {
         }
     }
 
+    fn send_request(&mut self, req: &WorkerRequest) -> Result<(), String> {
+        let worker =
+            self.worker.as_mut().ok_or_else(|| "backend worker unavailable".to_string())?;
+        let mut payload = format_worker_message(&WorkerMessage::<_, WorkerResponse>::Request(req))?;
+        payload.push('\n');
+        worker
+            .stdin
+            .write_all(payload.as_bytes())
+            .map_err(|e| format!("write worker request failed: {e}"))?;
+        worker.stdin.flush().map_err(|e| format!("flush worker request failed: {e}"))?;
+        Ok(())
+    }
+
     fn start_worker(&mut self) -> Result<(), String> {
         if self.worker.is_some() {
             return Ok(());
@@ -698,18 +1036,10 @@ impl OpenVmBackend {
                         if trimmed.is_empty() {
                             continue;
                         }
-                        if !trimmed.starts_with(WORKER_RESPONSE_PREFIX) {
+                        let Some(parsed) = decode_worker_line(trimmed) else {
                             // Ignore non-protocol stdout noise from dependencies.
                             continue;
-                        }
-                        let payload = &trimmed[WORKER_RESPONSE_PREFIX.len()..];
-                        let parsed = serde_json::from_str::<WorkerResponse>(payload).map_err(|e| {
-                            let mut preview = payload.chars().take(200).collect::<String>();
-                            if payload.chars().count() > 200 {
-                                preview.push_str("...");
-                            }
-                            format!("parse worker response failed: {e}; raw={preview:?}")
-                        });
+                        };
                         if tx.send(parsed).is_err() {
                             break;
                         }
@@ -726,6 +1056,17 @@ impl OpenVmBackend {
         Ok(())
     }
 
+    /// Kill a worker that failed or overran mid-run. Its retained trace dies with it, so when
+    /// retention is on keep the program and error in its place for `persist_last_trace`.
+    fn abandon_run(&mut self, error: &str) {
+        self.stop_worker();
+        if self.retain_last_trace {
+            let mut archive = trace_archive(Vec::new(), &[], &self.last_words);
+            archive["backend_error"] = serde_json::json!(error);
+            self.abandoned_trace = Some(archive);
+        }
+    }
+
     fn stop_worker(&mut self) {
         if let Some(mut worker) = self.worker.take() {
             let _ = worker.child.kill();
@@ -734,25 +1075,69 @@ impl OpenVmBackend {
             let _ = worker.reader_thread.join();
         }
     }
+
+    /// Block until the worker answers `request_id`, skipping responses to earlier requests.
+    fn wait_for_response(
+        &self,
+        request_id: u64,
+        timeout: Duration,
+    ) -> Result<WorkerResponse, String> {
+        let worker =
+            self.worker.as_ref().ok_or_else(|| "backend worker unavailable".to_string())?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match worker.responses_rx.recv_timeout(remaining) {
+                Ok(Ok(resp)) if resp.request_id == request_id => return Ok(resp),
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => return Err(e),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
 }
 
 impl BenchmarkBackend for OpenVmBackend {
-    fn is_usable_seed(&self, words: &[u32]) -> bool {
-        if words.is_empty() {
-            return false;
-        }
-        if words.len() > self.max_instructions {
-            return false;
+    /// What this snapshot executes and reports.
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            supported_opcodes: self.supported_opcodes.clone(),
+            address_spaces: vec![RV32_REGISTER_AS, RV32_MEMORY_AS],
+            max_program_len: Some(self.max_instructions),
+            memory_readback: false,
+            witness_injection: true,
+            undecodable_words: false,
         }
-        words
-            .iter()
-            .all(|w| is_openvm_supported_rv32_word(*w) && RV32IMInstruction::from_word(*w).is_ok())
+    }
+
+    fn is_usable_seed(&self, words: &[u32]) -> bool {
+        !words.is_empty()
+            && self.capabilities().allows_program(words)
+            && words.iter().all(|w| self.fence_policy.allows_word(*w))
     }
 
     fn prepare_for_run(&mut self, _rng_seed: u64) {
         self.current_iteration = self.current_iteration.saturating_add(1);
     }
 
+    fn warm_up(&mut self) -> Result<(), String> {
+        if self.worker.is_some() {
+            return Ok(());
+        }
+        self.start_worker()?;
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.saturating_add(1);
+        let req =
+            WorkerRequest { request_id, words: WARM_UP_PROGRAM.to_vec(), ..Default::default() };
+        self.send_request(&req)?;
+        let startup_timeout = Duration::from_millis(self.startup_timeout_ms);
+        if let Err(e) = self.wait_for_response(request_id, startup_timeout) {
+            self.stop_worker();
+            return Err(format!("backend worker warm-up failed: {e}"));
+        }
+        Ok(())
+    }
+
     fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
         let timeout = Duration::from_millis(self.timeout_ms);
         self.eval.backend_error = None;
@@ -760,8 +1145,10 @@ impl BenchmarkBackend for OpenVmBackend {
         self.eval.micro_op_count = 0;
         self.eval.final_regs = None;
         self.eval.semantic_injection_applied = false;
+        self.eval.injection_caught = None;
         self.last_observed_injection_sites.clear();
         self.last_words = words.to_vec();
+        self.abandoned_trace = None;
         self.start_worker()?;
         let request_id = self.next_request_id;
         self.next_request_id = self.next_request_id.saturating_add(1);
@@ -771,30 +1158,22 @@ impl BenchmarkBackend for OpenVmBackend {
             iteration: self.current_iteration,
             inject_kind: self.pending_injection.as_ref().map(|p| p.kind.clone()),
             inject_step: self.pending_injection.as_ref().map(|p| p.step).unwrap_or(0),
+            retain_trace: self.retain_last_trace,
+            dump_trace_to: None,
+            inject_path: self.pending_path_injection.clone(),
         };
 
-        {
-            let worker =
-                self.worker.as_mut().ok_or_else(|| "backend worker unavailable".to_string())?;
-            let mut payload = serde_json::to_vec(&req)
-                .map_err(|e| format!("serialize worker request failed: {e}"))?;
-            payload.push(b'\n');
-            worker
-                .stdin
-                .write_all(&payload)
-                .map_err(|e| format!("write worker request failed: {e}"))?;
-            worker.stdin.flush().map_err(|e| format!("flush worker request failed: {e}"))?;
-        }
+        self.send_request(&req)?;
 
         let started = Instant::now();
         let worker_resp = loop {
             let elapsed = started.elapsed();
             if elapsed >= timeout {
-                self.stop_worker();
                 let msg = format!(
                     "backend trace build timed out after {} ms (worker killed)",
                     self.timeout_ms
                 );
+                self.abandon_run(&msg);
                 self.eval.backend_error = Some(msg.clone());
                 return Err(msg);
             }
@@ -812,22 +1191,22 @@ impl BenchmarkBackend for OpenVmBackend {
                     }
                 }
                 Ok(Err(e)) => {
-                    self.stop_worker();
+                    self.abandon_run(&e);
                     self.eval.backend_error = Some(e.clone());
                     return Err(e);
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
-                    self.stop_worker();
                     let msg = format!(
                         "backend trace build timed out after {} ms (worker killed)",
                         self.timeout_ms
                     );
+                    self.abandon_run(&msg);
                     self.eval.backend_error = Some(msg.clone());
                     return Err(msg);
                 }
                 Err(mpsc::RecvTimeoutError::Disconnected) => {
-                    self.stop_worker();
                     let msg = "backend worker disconnected".to_string();
+                    self.abandon_run(&msg);
                     self.eval.backend_error = Some(msg.clone());
                     return Err(msg);
                 }
@@ -839,7 +1218,10 @@ impl BenchmarkBackend for OpenVmBackend {
         self.eval.trace_signals = worker_resp.trace_signals;
         self.eval.backend_error = worker_resp.backend_error.clone();
         self.eval.final_regs = worker_resp.final_regs;
+        self.eval.trace_truncated = worker_resp.trace_truncated;
+        self.last_witness_flip_sites = worker_resp.witness_flip_sites;
         self.eval.semantic_injection_applied = worker_resp.injection_applied;
+        self.eval.injection_caught = worker_resp.injection_caught;
         self.last_observed_injection_sites = worker_resp.observed_injection_sites;
 
         match worker_resp.final_regs {
@@ -884,6 +1266,94 @@ impl BenchmarkBackend for OpenVmBackend {
         candidates.sort_by_key(Self::semantic_candidate_priority);
         candidates
     }
+
+    fn set_retain_last_trace(&mut self, retain: bool) {
+        self.retain_last_trace = retain;
+    }
+
+    fn persist_last_trace(&mut self, path: &Path) -> Result<(), String> {
+        if !self.retain_last_trace {
+            return Err("trace retention is not enabled".to_string());
+        }
+        if let Some(archive) = &self.abandoned_trace {
+            return std::fs::write(path, archive.to_string())
+                .map_err(|e| format!("write trace {} failed: {e}", path.display()));
+        }
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.saturating_add(1);
+        let req = WorkerRequest {
+            request_id,
+            dump_trace_to: Some(path.to_path_buf()),
+            ..Default::default()
+        };
+        self.send_request(&req)?;
+        let resp = self
+            .wait_for_response(request_id, Duration::from_millis(self.timeout_ms))
+            .map_err(|e| format!("dump trace request failed: {e}"))?;
+        resp.backend_error.map_or(Ok(()), Err)
+    }
+}
+
+/// Lets `run_loop2` flip witness bits through `WorkerRequest::inject_path`; everything else
+/// forwards to the `BenchmarkBackend` implementation.
+impl beak_core::fuzz::loop1::LoopBackend for OpenVmBackend {
+    fn capabilities(&self) -> BackendCapabilities {
+        BenchmarkBackend::capabilities(self)
+    }
+
+    fn is_usable_seed(&self, words: &[u32]) -> bool {
+        BenchmarkBackend::is_usable_seed(self, words)
+    }
+
+    fn prepare_for_run(&mut self, rng_seed: u64) {
+        BenchmarkBackend::prepare_for_run(self, rng_seed);
+    }
+
+    fn warm_up(&mut self) -> Result<(), String> {
+        BenchmarkBackend::warm_up(self)
+    }
+
+    fn set_timeout_ms(&mut self, timeout_ms: u64) {
+        self.timeout_ms = timeout_ms;
+    }
+
+    fn set_memory_probe(&mut self, addrs: &[u32]) {
+        BenchmarkBackend::set_memory_probe(self, addrs);
+    }
+
+    fn prove_and_read_final_regs(&mut self, words: &[u32]) -> Result<[u32; 32], String> {
+        BenchmarkBackend::prove_and_read_final_regs(self, words)
+    }
+
+    fn collect_eval(&mut self) -> BackendEval {
+        BenchmarkBackend::collect_eval(self)
+    }
+
+    fn clear_direct_injection(&mut self) {
+        self.pending_path_injection = None;
+    }
+
+    fn witness_flip_sites(&self) -> Vec<WitnessFlipSite> {
+        self.last_witness_flip_sites
+            .iter()
+            .map(|(column, steps)| WitnessFlipSite { column: column.clone(), steps: steps.clone() })
+            .collect()
+    }
+
+    fn arm_witness_bit_flip(&mut self, flip: &WitnessBitFlip) -> Result<(), String> {
+        let (chip_kind, field_path) = flip
+            .column
+            .split_once(':')
+            .ok_or_else(|| format!("witness column `{}` is not `kind:field_path`", flip.column))?;
+        self.pending_path_injection = Some(WitnessPathPlan {
+            step: flip.step,
+            op_idx: None,
+            chip_kind: chip_kind.to_string(),
+            field_path: field_path.to_string(),
+            bit: flip.bit,
+        });
+        Ok(())
+    }
 }
 
 impl Drop for OpenVmBackend {
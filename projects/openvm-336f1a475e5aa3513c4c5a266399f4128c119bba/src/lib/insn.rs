@@ -33,4 +33,8 @@ pub struct OpenVMInsn {
 
     /// OpenVM instruction operands: [a, b, c, d, e, f, g].
     pub operands: [FieldElement; 7],
+
+    /// Indices of operands the emitter found at or above the field modulus (normally empty).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub out_of_field_operands: Vec<usize>,
 }
@@ -1,5 +1,6 @@
 use openvm_instructions::VmOpcode;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, de::Error as _};
+use serde_json::Value;
 
 use crate::{FieldElement, Pc, Timestamp};
 
@@ -14,7 +15,8 @@ pub enum InteractionDirection {
     Receive,
 }
 
-/// The five core buses/kinds we model for OpenVM.
+/// The five core buses/kinds we model for OpenVM, plus a catch-all for any other bus the
+/// emitter reports.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OpenVMInteractionKind {
@@ -23,6 +25,22 @@ pub enum OpenVMInteractionKind {
     Memory,
     RangeCheck,
     Bitwise,
+    #[serde(other)]
+    Other,
+}
+
+impl OpenVMInteractionKind {
+    /// Serialized (snake_case) name, matched against `semantic::interaction::INTERACTION_KINDS`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Execution => "execution",
+            Self::Program => "program",
+            Self::Memory => "memory",
+            Self::RangeCheck => "range_check",
+            Self::Bitwise => "bitwise",
+            Self::Other => "other",
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -47,12 +65,21 @@ pub struct OpenVMInteractionBase {
     /// Some interactions (memory/execution) always have timestamp; others might not.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<Timestamp>,
+
+    /// Bus multiplicity of this interaction; logs predating the field decode as 1.
+    #[serde(default = "default_multiplicity")]
+    pub multiplicity: u32,
+}
+
+fn default_multiplicity() -> u32 {
+    1
 }
 
 /// One JSON object per interaction.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenVMInteractionEnvelope {
     pub base: OpenVMInteractionBase,
+    #[serde(deserialize_with = "deserialize_payload")]
     pub payload: OpenVMInteractionPayload,
 }
 
@@ -106,6 +133,26 @@ pub enum OpenVMInteractionPayload {
         /// 0 = range-check mode, 1 = XOR.
         op: u32,
     },
+
+    // Any bus we do not model; its data is dropped.
+    #[serde(skip_deserializing)]
+    Other,
+}
+
+const PAYLOAD_TYPES: [&str; 5] = ["execution", "program", "memory", "range_check", "bitwise"];
+
+/// Payloads tagged with a bus outside `PAYLOAD_TYPES` decode as `Other` instead of failing the
+/// whole log; a known tag with malformed data is still an error.
+fn deserialize_payload<'de, D>(deserializer: D) -> Result<OpenVMInteractionPayload, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Value::deserialize(deserializer)?;
+    let ty = value.get("type").and_then(Value::as_str);
+    if ty.is_some_and(|ty| !PAYLOAD_TYPES.contains(&ty)) {
+        return Ok(OpenVMInteractionPayload::Other);
+    }
+    serde_json::from_value(value).map_err(D::Error::custom)
 }
 
 // ---------------------------------------------------------------------------
@@ -117,6 +164,16 @@ impl OpenVMInteractionEnvelope {
         &self.base
     }
 
+    /// Multiplicity signed by direction (send positive, receive negative), so summing it over a
+    /// bus checks that sends and receives balance.
+    pub fn signed_multiplicity(&self) -> i64 {
+        let multiplicity = i64::from(self.base.multiplicity);
+        match self.base.direction {
+            InteractionDirection::Send => multiplicity,
+            InteractionDirection::Receive => -multiplicity,
+        }
+    }
+
     pub fn validate_kind_matches_payload(&self) -> Result<(), String> {
         let expected = match &self.payload {
             OpenVMInteractionPayload::Execution { .. } => OpenVMInteractionKind::Execution,
@@ -124,6 +181,7 @@ impl OpenVMInteractionEnvelope {
             OpenVMInteractionPayload::Memory { .. } => OpenVMInteractionKind::Memory,
             OpenVMInteractionPayload::RangeCheck { .. } => OpenVMInteractionKind::RangeCheck,
             OpenVMInteractionPayload::Bitwise { .. } => OpenVMInteractionKind::Bitwise,
+            OpenVMInteractionPayload::Other => OpenVMInteractionKind::Other,
         };
 
         if self.base.kind != expected {
@@ -3,15 +3,27 @@ use std::path::{Path, PathBuf};
 
 use clap::{Arg, Command};
 
-use beak_core::fuzz::benchmark::{run_benchmark_threaded, BenchmarkConfig, DEFAULT_RNG_SEED};
+use beak_core::fuzz::benchmark::{
+    evaluate_stdin_threaded, run_benchmark_threaded, BenchmarkBackend, BenchmarkConfig,
+    DEFAULT_RNG_SEED,
+};
+use beak_core::fuzz::rebucket::rebucket_corpus;
+use beak_core::fuzz::worker_protocol::{
+    begin_worker_request, format_worker_message_compressed, install_worker_panic_hook,
+    parse_worker_message, worker_compress_min_bytes, worker_panic_reported, WorkerMessage,
+};
 use beak_core::rv32im::oracle::{OracleConfig, OracleMemoryModel};
+use beak_core::rv32im::supported::{FencePolicy, SupportedOpcodes};
+use beak_core::trace::{semantic, Trace};
 
 use beak_openvm_d7eab708::backend::{
-    run_backend_once, OpenVmBackend, WorkerRequest, WorkerResponse,
+    configure_worker_opcode_weights, configure_worker_trace_cap, dump_retained_trace,
+    retain_failed_trace, run_backend_once, set_path_injection, set_retain_trace, OpenVmBackend,
+    WorkerRequest, WorkerResponse,
 };
+use beak_openvm_d7eab708::trace::OpenVMTrace;
 
 const ZKVM_COMMIT: &str = "d7eab708f43487b2e7c00524ffd611f835e8e6b5";
-const WORKER_RESPONSE_PREFIX: &str = "__BEAK_WORKER_JSON__ ";
 
 fn workspace_root() -> PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -55,6 +67,12 @@ fn main() {
                 .default_value("500")
                 .help("Best-effort per-seed wall-time timeout in milliseconds."),
         )
+        .arg(
+            Arg::new("startup_timeout_ms")
+                .long("startup-timeout-ms")
+                .default_value("120000")
+                .help("Wall-time budget in milliseconds for starting and warming up a worker."),
+        )
         .arg(
             Arg::new("initial_limit")
                 .long("initial-limit")
@@ -109,6 +127,24 @@ fn main() {
                 .default_value("65536")
                 .help("Oracle zeroed data RAM bytes for split-code-data mode."),
         )
+        .arg(
+            Arg::new("fence_policy")
+                .long("fence-policy")
+                .default_value("execute")
+                .help("Fence handling for backend and oracle: reject | treat-as-nop | execute."),
+        )
+        .arg(
+            Arg::new("allow_insn")
+                .long("allow-insn")
+                .default_value("")
+                .help("Comma-separated instruction allowlist: mnemonics or 0x-prefixed major opcodes (empty = all)."),
+        )
+        .arg(
+            Arg::new("deny_insn")
+                .long("deny-insn")
+                .default_value("")
+                .help("Comma-separated instruction denylist: mnemonics or 0x-prefixed major opcodes."),
+        )
         .arg(
             Arg::new("worker_loop")
                 .long("worker-loop")
@@ -116,12 +152,58 @@ fn main() {
                 .action(clap::ArgAction::SetTrue)
                 .help("Run persistent backend worker loop from stdin JSONL."),
         )
+        .arg(
+            Arg::new("list_buckets")
+                .long("list-buckets")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print every bucket id (and {param} family template) and exit."),
+        )
+        .arg(
+            Arg::new("record_bug_traces")
+                .long("record-bug-traces")
+                .action(clap::ArgAction::SetTrue)
+                .help("Write the raw trace of every bug input to <out_dir>/traces for triage."),
+        )
+        .arg(
+            Arg::new("rebucket_traces")
+                .long("rebucket-traces")
+                .value_name("DIR")
+                .help("Re-bucket archived trace logs in DIR and print a JSON report."),
+        )
+        .arg(
+            Arg::new("eval_stdin")
+                .long("eval-stdin")
+                .action(clap::ArgAction::SetTrue)
+                .help("Evaluate one program read from stdin (hex words or RV32IM assembly) and print a JSON report."),
+        )
         .get_matches();
 
     if matches.get_flag("worker_loop") {
         run_worker_loop();
         return;
     }
+    if matches.get_flag("list_buckets") {
+        for id in semantic::all_bucket_ids() {
+            println!("{id}");
+        }
+        return;
+    }
+    if let Some(dir) = matches.get_one::<String>("rebucket_traces") {
+        let report = rebucket_corpus(Path::new(dir), |logs| {
+            OpenVMTrace::from_logs(logs).map(|trace| {
+                let pcs = trace.instructions().iter().map(|insn| u64::from(insn.pc)).collect();
+                (trace.bucket_hits().to_vec(), pcs)
+            })
+        });
+        match report {
+            Ok(report) => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
 
     let root = workspace_root();
     let seeds_arg = matches.get_one::<String>("seeds_jsonl").unwrap().to_string();
@@ -129,6 +211,11 @@ fn main() {
 
     let timeout_ms: u64 =
         matches.get_one::<String>("timeout_ms").unwrap().parse().expect("timeout-ms");
+    let startup_timeout_ms: u64 = matches
+        .get_one::<String>("startup_timeout_ms")
+        .unwrap()
+        .parse()
+        .expect("startup-timeout-ms");
     let initial_limit: usize =
         matches.get_one::<String>("initial_limit").unwrap().parse().expect("initial-limit");
     let max_instructions: usize =
@@ -163,6 +250,13 @@ fn main() {
         matches.get_one::<String>("oracle_data_size_bytes").unwrap(),
         "oracle-data-size-bytes",
     );
+    let fence_policy = FencePolicy::parse(matches.get_one::<String>("fence_policy").unwrap())
+        .expect("fence-policy");
+    let supported_opcodes = SupportedOpcodes::parse(
+        matches.get_one::<String>("allow_insn").unwrap(),
+        matches.get_one::<String>("deny_insn").unwrap(),
+    )
+    .expect("allow-insn/deny-insn");
 
     let cfg = BenchmarkConfig {
         zkvm_tag: "openvm".to_string(),
@@ -173,7 +267,9 @@ fn main() {
             memory_model: oracle_memory_model,
             code_base: oracle_code_base,
             data_size_bytes: oracle_data_size_bytes,
+            fence_policy,
         },
+        ignored_regs: None,
         seeds_jsonl: seeds_path,
         out_dir: root.join("storage/fuzzing_seeds"),
         output_prefix: None,
@@ -186,9 +282,33 @@ fn main() {
         semantic_step_stride,
         semantic_max_trials_per_bucket,
         stack_size_bytes: 256 * 1024 * 1024,
+        record_bug_traces: matches.get_flag("record_bug_traces"),
     };
 
-    let res = run_benchmark_threaded(cfg, move || OpenVmBackend::new(max_instructions, timeout_ms));
+    if matches.get_flag("eval_stdin") {
+        let res = evaluate_stdin_threaded(&cfg, move || {
+            OpenVmBackend::new(max_instructions, timeout_ms, supported_opcodes, fence_policy)
+                .with_startup_timeout_ms(startup_timeout_ms)
+        });
+        match res {
+            Ok(report) => {
+                println!("{}", serde_json::to_string_pretty(&report).expect("serialize report"))
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let res = run_benchmark_threaded(cfg, move || {
+        let backend =
+            OpenVmBackend::new(max_instructions, timeout_ms, supported_opcodes, fence_policy)
+                .with_startup_timeout_ms(startup_timeout_ms);
+        eprintln!("backend capabilities: {}", backend.capabilities().summary());
+        backend
+    });
     match res {
         Ok(out) => {
             println!("Wrote corpus JSONL: {}", out.corpus_path.display());
@@ -196,6 +316,9 @@ fn main() {
             if let Some(runs_path) = out.runs_path.as_ref() {
                 println!("Wrote runs   JSONL: {}", runs_path.display());
             }
+            if let Some(histogram_path) = out.bucket_histogram_path.as_ref() {
+                println!("Wrote bucket histogram: {}", histogram_path.display());
+            }
         }
         Err(e) => {
             eprintln!("{e}");
@@ -204,11 +327,28 @@ fn main() {
     }
 }
 
+/// Serve one worker request: a trace dump when `dump_trace_to` is set, otherwise a run.
+fn serve_request(req: &WorkerRequest) -> Result<WorkerResponse, String> {
+    if let Some(path) = req.dump_trace_to.as_deref() {
+        return dump_retained_trace(req.request_id, path);
+    }
+    set_retain_trace(req.retain_trace);
+    set_path_injection(req.inject_path.as_ref());
+    run_backend_once(req.request_id, &req.words, req.iteration, &req.memory_probe)
+}
+
+fn error_response(request_id: u64, backend_error: String) -> WorkerResponse {
+    WorkerResponse { request_id, backend_error: Some(backend_error), ..Default::default() }
+}
+
 fn run_worker_loop() {
+    install_worker_panic_hook(|request_id, msg| {
+        let _ = write_worker_message(&WorkerMessage::Response(error_response(request_id, msg)));
+    });
+    configure_worker_trace_cap();
+    configure_worker_opcode_weights();
     let stdin = std::io::stdin();
     let mut input = stdin.lock();
-    let stdout = std::io::stdout();
-    let mut out = stdout.lock();
 
     loop {
         let mut line = String::new();
@@ -219,54 +359,40 @@ fn run_worker_loop() {
                 if trimmed.is_empty() {
                     continue;
                 }
-                let req: WorkerRequest = match serde_json::from_str(trimmed) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        eprintln!("parse worker request failed: {e}");
+                let req = match parse_worker_message::<WorkerRequest, WorkerResponse>(trimmed) {
+                    Some(Ok(WorkerMessage::Request(req))) => req,
+                    Some(Ok(_)) | None => continue,
+                    Some(Err(e)) => {
+                        let message = format!("parse worker request failed: {e}");
+                        eprintln!("{message}");
+                        let error = WorkerMessage::Error { request_id: None, message };
+                        if write_worker_message(&error).is_err() {
+                            break;
+                        }
                         continue;
                     }
                 };
-                let resp = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    run_backend_once(req.request_id, &req.words, req.iteration)
-                })) {
+                begin_worker_request(req.request_id);
+                let served =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| serve_request(&req)));
+                if served.is_err() {
+                    retain_failed_trace(&req.words);
+                }
+                let resp = match served {
                     Ok(Ok(v)) => v,
-                    Ok(Err(e)) => WorkerResponse {
-                        request_id: req.request_id,
-                        final_regs: None,
-                        micro_op_count: 0,
-                        bucket_hits: Vec::new(),
-                        trace_signals: Vec::new(),
-                        backend_error: Some(e),
-                    },
-                    Err(p) => WorkerResponse {
-                        request_id: req.request_id,
-                        final_regs: None,
-                        micro_op_count: 0,
-                        bucket_hits: Vec::new(),
-                        trace_signals: Vec::new(),
-                        backend_error: Some(format!(
+                    Ok(Err(e)) => error_response(req.request_id, e),
+                    // The panic hook already sent the detailed report for this request.
+                    Err(_) if worker_panic_reported() => continue,
+                    Err(p) => error_response(
+                        req.request_id,
+                        format!(
                             "worker panic in run_backend_once: {}",
                             panic_payload_to_string(p.as_ref())
-                        )),
-                    },
+                        ),
+                    ),
                 };
-                let payload = match serde_json::to_vec(&resp) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        eprintln!("serialize worker response failed: {e}");
-                        continue;
-                    }
-                };
-                if out.write_all(WORKER_RESPONSE_PREFIX.as_bytes()).is_err() {
-                    break;
-                }
-                if out.write_all(&payload).is_err() {
-                    break;
-                }
-                if out.write_all(b"\n").is_err() {
-                    break;
-                }
-                if out.flush().is_err() {
+                if let Err(e) = write_worker_message(&WorkerMessage::Response(resp)) {
+                    eprintln!("write worker response failed: {e}");
                     break;
                 }
             }
@@ -275,6 +401,15 @@ fn run_worker_loop() {
     }
 }
 
+fn write_worker_message(msg: &WorkerMessage<WorkerRequest, WorkerResponse>) -> std::io::Result<()> {
+    let line = format_worker_message_compressed(msg, worker_compress_min_bytes())
+        .map_err(std::io::Error::other)?;
+    let mut out = std::io::stdout().lock();
+    out.write_all(line.as_bytes())?;
+    out.write_all(b"\n")?;
+    out.flush()
+}
+
 fn panic_payload_to_string(p: &(dyn std::any::Any + Send)) -> String {
     if let Some(s) = p.downcast_ref::<&str>() {
         return (*s).to_string();
@@ -24,6 +24,10 @@ pub struct OpenVMChipRowBase {
 
     /// Human-readable chip instance name, e.g. "Rv32BaseAlu".
     pub chip_name: String,
+
+    /// Anchor id that interactions reference via `OpenVMInteractionBase::row_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -46,6 +50,8 @@ pub enum OpenVMChipRowKind {
     Program,
     Connector,
     Padding,
+    RangeCheck,
+    Bitwise,
 }
 
 /// One JSON object per chip row:
@@ -228,6 +234,10 @@ pub enum OpenVMChipRowPayload {
         imm_sign: bool,
         mem_as: u32,
         effective_ptr: u32,
+        /// Base register value the address is computed from; absent when the emitting hook
+        /// cannot see it.
+        #[serde(default)]
+        rs1_val: Option<u32>,
         is_store: bool,
         needs_write: bool,
         is_load: bool,
@@ -247,6 +257,8 @@ pub enum OpenVMChipRowPayload {
         imm_sign: bool,
         mem_as: u32,
         effective_ptr: u32,
+        #[serde(default)]
+        rs1_val: Option<u32>,
         needs_write: bool,
         prev_data: Vec<u8>,
         shifted_read_data: Vec<u8>,
@@ -258,7 +270,14 @@ pub enum OpenVMChipRowPayload {
     },
 
     // ---- System chips ----
-    Phantom {},
+    Phantom {
+        /// Phantom discriminant (low 16 bits of operand `c`); absent in older traces.
+        #[serde(default)]
+        sub_kind: Option<u32>,
+        /// Raw instruction operands `[a, b, c]`.
+        #[serde(default)]
+        operands: Vec<u32>,
+    },
 
     Program {
         opcode: VmOpcode,
@@ -281,6 +300,22 @@ pub enum OpenVMChipRowPayload {
     Padding {
         data: String,
     },
+
+    // ---- Lookup chips ----
+    /// VariableRangeCheckerAir row: `mult` counts the lookups of `(value, max_bits)`.
+    RangeCheck {
+        value: FieldElement,
+        max_bits: u32,
+        mult: u32,
+    },
+
+    /// BitwiseOperationLookupAir row: multiplicities of range-check and XOR lookups of `(x, y)`.
+    Bitwise {
+        x: FieldElement,
+        y: FieldElement,
+        mult_range: u32,
+        mult_xor: u32,
+    },
 }
 
 // -----------------------------
@@ -327,6 +362,8 @@ impl OpenVMChipRowEnvelope {
             OpenVMChipRowPayload::Program { .. } => OpenVMChipRowKind::Program,
             OpenVMChipRowPayload::Connector { .. } => OpenVMChipRowKind::Connector,
             OpenVMChipRowPayload::Padding { .. } => OpenVMChipRowKind::Padding,
+            OpenVMChipRowPayload::RangeCheck { .. } => OpenVMChipRowKind::RangeCheck,
+            OpenVMChipRowPayload::Bitwise { .. } => OpenVMChipRowKind::Bitwise,
         };
 
         if self.kind != expected {
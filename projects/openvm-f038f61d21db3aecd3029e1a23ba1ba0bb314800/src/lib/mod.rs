@@ -40,4 +40,22 @@ impl MemorySize {
             MemorySize::Word => 4,
         }
     }
+
+    /// Canonical numeric encoding (access width in bytes).
+    pub fn as_u32(self) -> u32 {
+        self.len() as u32
+    }
+}
+
+impl TryFrom<u32> for MemorySize {
+    type Error = String;
+
+    fn try_from(width: u32) -> Result<Self, Self::Error> {
+        match width {
+            1 => Ok(MemorySize::Byte),
+            2 => Ok(MemorySize::Half),
+            4 => Ok(MemorySize::Word),
+            other => Err(format!("invalid memory access width: {other}")),
+        }
+    }
 }
@@ -1,16 +1,30 @@
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
+use beak_core::trace::envelope::{
+    TraceInvariantViolation, build_op_spans_from_step_idx, validate_micro_op_envelope,
+    validate_seq_monotonic,
+};
+use beak_core::trace::limbs::{Endianness, limbs_to_u32};
 use beak_core::trace::observations::{
     ArithmeticSpecialCaseObservation, AuipcPcLimbObservation, BoundaryOriginObservation,
-    ImmediateLimbObservation, MemoryAddressSpaceObservation, MemoryImmediateSignObservation,
-    VolatileBoundaryObservation, XorMultiplicityObservation,
+    ConnectorObservation, EffectivePtrObservation, ImmediateLimbObservation,
+    InteractionTimestampObservation, JalLuiObservation, LoadSignExtendObservation,
+    MemoryAddressSpaceObservation, MemoryImmediateSignObservation, MulProductObservation,
+    OpcodeDispatchObservation, ProgramRowObservation, RangeCheckObservation, StoreMergeObservation,
+    VolatileBoundaryObservation, XorMultiplicityObservation, ZeroRegisterWriteObservation,
+};
+use beak_core::trace::{
+    BucketHit, Trace, TraceSignal, bound_bucket_hit_details, max_details_per_bucket,
+    semantic_matchers,
 };
-use beak_core::trace::{BucketHit, Trace, TraceSignal, semantic_matchers};
+use openvm_instructions::LocalOpcode;
+use openvm_rv32im_transpiler::Rv32LoadStoreOpcode;
 use serde_json::Value;
 
 use crate::chip_row::{OpenVMChipRow, OpenVMChipRowKind, OpenVMChipRowPayload, Rs2Source};
 use crate::insn::OpenVMInsn;
-use crate::interaction::OpenVMInteraction;
+use crate::interaction::{OpenVMInteraction, OpenVMInteractionPayload};
 
 #[derive(Debug, Clone)]
 pub struct OpenVMTrace {
@@ -37,6 +51,9 @@ pub struct OpenVMTrace {
     // ---- row_id / bus_kind -> interaction indices (no cloning) --------------
     interactions_by_row_id: HashMap<String, Vec<usize>>,
     interactions_by_bus: HashMap<crate::interaction::OpenVMInteractionKind, Vec<usize>>,
+
+    // ---- step_idx -> index range into the `from_logs` micro-op stream ------
+    op_spans: Option<Vec<Range<usize>>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,8 +72,21 @@ struct OpenVmObservationProfile {
     emit_boundary_origin_semantic: bool,
     emit_volatile_boundary_semantic: bool,
     emit_arithmetic_special_case_semantic: bool,
+    /// Limb order of emitted register values (OpenVM is little-endian).
+    limb_endianness: Endianness,
 }
 
+const OBSERVATION_PROFILE: OpenVmObservationProfile = OpenVmObservationProfile {
+    emit_alu_immediate_limb_semantic: true,
+    emit_xor_multiplicity_semantic: false,
+    emit_auipc_pc_limb_semantic: true,
+    memory_semantic: OpenVmMemoryObservationProfile::AddressSpace,
+    emit_boundary_origin_semantic: true,
+    emit_volatile_boundary_semantic: true,
+    emit_arithmetic_special_case_semantic: false,
+    limb_endianness: Endianness::Little,
+};
+
 fn kind_snake(kind: OpenVMChipRowKind) -> String {
     match serde_json::to_value(kind) {
         Ok(Value::String(s)) => s,
@@ -64,15 +94,44 @@ fn kind_snake(kind: OpenVMChipRowKind) -> String {
     }
 }
 
-fn le_u32_from_bytes(bytes: &[u8]) -> Option<u32> {
-    if bytes.len() < 4 {
-        return None;
+/// Kind of the execution row `opcode` is expected to produce in this snapshot. The instrumented
+/// base ALU adapter is shared with the shift and less-than chips, so their rows arrive as
+/// `base_alu`.
+fn expected_row_kind(opcode: usize) -> Option<&'static str> {
+    match fuzzer_utils::opcode_chip_kind(opcode)? {
+        "shift" | "less_than" => Some("base_alu"),
+        kind => Some(kind),
     }
-    let mut arr = [0u8; 4];
-    arr.copy_from_slice(&bytes[..4]);
-    Some(u32::from_le_bytes(arr))
 }
 
+/// Row kinds that execute an instruction, as opposed to program, connector, padding and lookup
+/// rows that any step may carry.
+fn is_execution_kind(kind: OpenVMChipRowKind) -> bool {
+    !matches!(
+        kind,
+        OpenVMChipRowKind::Program
+            | OpenVMChipRowKind::Connector
+            | OpenVMChipRowKind::Padding
+            | OpenVMChipRowKind::RangeCheck
+            | OpenVMChipRowKind::Bitwise
+    )
+}
+
+/// BabyBear modulus; instruction operands are canonical field elements strictly below it.
+const BABYBEAR_MODULUS: u32 = 0x7800_0001;
+
+/// Access width of a store in the RV32 load/store chip, keyed by its local opcode.
+fn store_size_bytes(op: u32) -> Option<u8> {
+    use Rv32LoadStoreOpcode::*;
+    [(STOREW, 4), (STOREH, 2), (STOREB, 1)]
+        .into_iter()
+        .find(|(opcode, _)| opcode.local_usize() == op as usize)
+        .map(|(_, size)| size)
+}
+
+/// Bits per register limb in the OpenVM RV32 chips (4 x 8-bit limbs per word).
+const LIMB_BITS: usize = 8;
+
 fn rs2_imm_value(rs2: &Rs2Source) -> Option<i32> {
     match rs2 {
         Rs2Source::Imm { value } => Some(*value),
@@ -80,6 +139,23 @@ fn rs2_imm_value(rs2: &Rs2Source) -> Option<i32> {
     }
 }
 
+/// `rd_ptr` and written limbs of an execution row that writes a register; `None` for rows that
+/// never write rd or whose `needs_write` is clear.
+fn rd_write(payload: &OpenVMChipRowPayload) -> Option<(u32, &[u8])> {
+    match payload {
+        OpenVMChipRowPayload::BaseAlu { rd_ptr, a, .. }
+        | OpenVMChipRowPayload::Shift { rd_ptr, a, .. }
+        | OpenVMChipRowPayload::LessThan { rd_ptr, a, .. }
+        | OpenVMChipRowPayload::Mul { rd_ptr, a, .. }
+        | OpenVMChipRowPayload::MulH { rd_ptr, a, .. }
+        | OpenVMChipRowPayload::DivRem { rd_ptr, a, .. } => Some((*rd_ptr, a)),
+        OpenVMChipRowPayload::JalLui { rd_ptr, rd_data, needs_write: true, .. }
+        | OpenVMChipRowPayload::Jalr { rd_ptr, rd_data, needs_write: true, .. }
+        | OpenVMChipRowPayload::Auipc { rd_ptr, rd_data, .. } => Some((*rd_ptr, rd_data)),
+        _ => None,
+    }
+}
+
 fn flipped_sign_ptr(effective_ptr: u32, imm_sign: bool) -> (u32, i32) {
     if imm_sign {
         (effective_ptr.wrapping_add(1 << 16), 1 << 16)
@@ -98,10 +174,15 @@ fn record_signal(
     }
 }
 
-fn derive_semantic_feedback(
-    trace: &OpenVMTrace,
+fn derive_semantic_feedback<'a>(
+    trace: &'a OpenVMTrace,
+    rows: impl IntoIterator<Item = &'a OpenVMChipRow>,
+    interactions: impl IntoIterator<Item = &'a OpenVMInteraction>,
     profile: OpenVmObservationProfile,
 ) -> (Vec<BucketHit>, Vec<TraceSignal>) {
+    let rows: Vec<&OpenVMChipRow> = rows.into_iter().collect();
+    let row_ids: HashSet<&str> =
+        rows.iter().copied().filter_map(|row| row.base().row_id.as_deref()).collect();
     let mut signals = Vec::new();
     let mut seen_signals = HashSet::new();
     let mut immediate_limb = Vec::new();
@@ -110,19 +191,95 @@ fn derive_semantic_feedback(
     let mut memory_immediate_sign = Vec::new();
     let mut memory_address_space = Vec::new();
     let mut boundary_origin = Vec::new();
+    let mut jal_lui = Vec::new();
+    let mut connectors = Vec::new();
+    let mut program_rows = Vec::new();
+    let mut interactions_per_row: HashMap<&str, usize> = HashMap::new();
+    let mut range_checks = Vec::new();
+    let execution_rows: HashMap<&str, _> = rows
+        .iter()
+        .filter(|row| is_execution_kind(row.kind))
+        .filter_map(|row| Some((row.base().row_id.as_deref()?, row.base())))
+        .collect();
+    let mut interaction_timestamps = Vec::new();
+    for ia in interactions {
+        if !row_ids.contains(ia.base.row_id.as_str()) {
+            continue;
+        }
+        *interactions_per_row.entry(ia.base.row_id.as_str()).or_default() += 1;
+        let anchor = execution_rows.get(ia.base.row_id.as_str());
+        if let (Some(timestamp), Some(row_timestamp)) =
+            (ia.base.timestamp, anchor.and_then(|row| row.timestamp))
+        {
+            let step_next_timestamp = anchor
+                .and_then(|row| usize::try_from(row.step_idx).ok())
+                .and_then(|step| trace.insn_by_step.get(step).copied().flatten())
+                .map(|i| trace.instructions[i].next_timestamp);
+            interaction_timestamps.push(InteractionTimestampObservation {
+                step_idx: ia.base.step_idx,
+                op_idx: ia.base.op_idx,
+                kind: ia.base.kind.as_str().to_string(),
+                row_id: ia.base.row_id.clone(),
+                timestamp,
+                row_timestamp,
+                step_next_timestamp,
+            });
+        }
+        if let OpenVMInteractionPayload::RangeCheck { value, max_bits } = &ia.payload {
+            range_checks.push(RangeCheckObservation {
+                step_idx: ia.base.step_idx,
+                op_idx: ia.base.op_idx,
+                value: *value,
+                max_bits: *max_bits,
+            });
+        }
+    }
     let mut volatile_boundary = Vec::new();
     let mut arithmetic_special_case = Vec::new();
+    let mut mul_product = Vec::new();
+    let mut effective_ptr_obs = Vec::new();
+    let mut phantom_sub_kinds = Vec::new();
+    let mut store_merge = Vec::new();
+    let mut load_sign_extend = Vec::new();
+    let mut opcode_dispatch = Vec::new();
+    let mut zero_reg = Vec::new();
 
     let mut saw_system_terminate = false;
     let mut saw_missing_row_timestamp = false;
     let mut saw_memory_access = false;
 
-    for row in trace.chip_rows() {
+    for row in rows {
         let base = row.base();
         let kind = kind_snake(row.kind);
         if base.timestamp.is_none() {
             saw_missing_row_timestamp = true;
         }
+        let step_insn = usize::try_from(base.step_idx)
+            .ok()
+            .and_then(|step| trace.insn_by_step.get(step).copied().flatten())
+            .map(|i| &trace.instructions[i]);
+        if let Some(insn) = step_insn.filter(|_| is_execution_kind(row.kind)) {
+            if let Some(expected) = expected_row_kind(insn.opcode.as_usize()) {
+                opcode_dispatch.push(OpcodeDispatchObservation {
+                    step_idx: base.step_idx,
+                    op_idx: base.op_idx,
+                    chip_name: base.chip_name.clone(),
+                    opcode: insn.opcode.as_usize() as u64,
+                    expected_kind: expected.to_string(),
+                    row_kind: kind.clone(),
+                });
+            }
+        }
+        if let Some((_, rd_limbs)) = rd_write(&row.payload).filter(|(rd_ptr, _)| *rd_ptr == 0) {
+            zero_reg.push(ZeroRegisterWriteObservation {
+                op_idx: base.step_idx,
+                pc: step_insn.map_or(0, |insn| u64::from(insn.pc)),
+                // OpenVM rows carry no RISC-V encoding.
+                raw_word: 0,
+                mnemonic: kind.clone(),
+                rd_data: limbs_to_u32(rd_limbs, LIMB_BITS, profile.limb_endianness),
+            });
+        }
         if base.chip_name.contains("Volatile") {
             record_signal(
                 &mut signals,
@@ -153,9 +310,11 @@ fn derive_semantic_feedback(
                     }
                 }
                 if profile.emit_xor_multiplicity_semantic {
-                    if let (Some(out), Some(lhs), Some(rhs)) =
-                        (le_u32_from_bytes(a), le_u32_from_bytes(b), le_u32_from_bytes(c))
-                    {
+                    if let (Some(out), Some(lhs), Some(rhs)) = (
+                        limbs_to_u32(a, LIMB_BITS, profile.limb_endianness),
+                        limbs_to_u32(b, LIMB_BITS, profile.limb_endianness),
+                        limbs_to_u32(c, LIMB_BITS, profile.limb_endianness),
+                    ) {
                         if out == (lhs ^ rhs) && (lhs & rhs) != 0 {
                             xor_multiplicity.push(XorMultiplicityObservation {
                                 step_idx: base.step_idx,
@@ -169,9 +328,38 @@ fn derive_semantic_feedback(
                     }
                 }
             }
+            OpenVMChipRowPayload::Mul { op, a, b, c, .. }
+            | OpenVMChipRowPayload::MulH { op, a, b, c, .. } => {
+                // `op` is the chip-local opcode: MUL, or MULH/MULHSU/MULHU.
+                let mnemonic = match (row.kind, *op) {
+                    (OpenVMChipRowKind::Mul, _) => Some("mul"),
+                    (_, 0) => Some("mulh"),
+                    (_, 1) => Some("mulhsu"),
+                    (_, 2) => Some("mulhu"),
+                    _ => None,
+                };
+                if let (Some(mnemonic), Some(result), Some(rs1), Some(rs2)) = (
+                    mnemonic,
+                    limbs_to_u32(a, LIMB_BITS, profile.limb_endianness),
+                    limbs_to_u32(b, LIMB_BITS, profile.limb_endianness),
+                    limbs_to_u32(c, LIMB_BITS, profile.limb_endianness),
+                ) {
+                    mul_product.push(MulProductObservation {
+                        step_idx: base.step_idx,
+                        op_idx: base.op_idx,
+                        mnemonic: mnemonic.to_string(),
+                        rs1,
+                        rs2,
+                        result,
+                    });
+                }
+            }
             OpenVMChipRowPayload::DivRem { b, c, .. } => {
                 if profile.emit_arithmetic_special_case_semantic {
-                    if let (Some(rs1), Some(rs2)) = (le_u32_from_bytes(b), le_u32_from_bytes(c)) {
+                    if let (Some(rs1), Some(rs2)) = (
+                        limbs_to_u32(b, LIMB_BITS, profile.limb_endianness),
+                        limbs_to_u32(c, LIMB_BITS, profile.limb_endianness),
+                    ) {
                         if rs2 == 0 || (rs1 == 0x8000_0000 && rs2 == 0xFFFF_FFFF) {
                             arithmetic_special_case.push(ArithmeticSpecialCaseObservation {
                                 step_idx: base.step_idx,
@@ -203,12 +391,27 @@ fn derive_semantic_feedback(
                 imm_sign,
                 mem_as,
                 effective_ptr,
+                rs1_val,
                 is_store,
                 needs_write,
                 is_load,
+                read_data,
+                prev_data,
+                write_data,
                 ..
             } => {
                 saw_memory_access = true;
+                if let Some(rs1_val) = rs1_val {
+                    effective_ptr_obs.push(EffectivePtrObservation {
+                        step_idx: base.step_idx,
+                        op_idx: base.op_idx,
+                        chip_name: base.chip_name.clone(),
+                        op: *op,
+                        rs1_val: *rs1_val,
+                        imm: *imm,
+                        effective_ptr: *effective_ptr,
+                    });
+                }
                 if *is_load {
                     record_signal(&mut signals, &mut seen_signals, TraceSignal::HasLoad);
                     record_signal(&mut signals, &mut seen_signals, TraceSignal::HasLoadStore);
@@ -216,6 +419,18 @@ fn derive_semantic_feedback(
                 if *is_store {
                     record_signal(&mut signals, &mut seen_signals, TraceSignal::HasStore);
                     record_signal(&mut signals, &mut seen_signals, TraceSignal::HasLoadStore);
+                    if let Some(size_bytes) = store_size_bytes(*op) {
+                        store_merge.push(StoreMergeObservation {
+                            step_idx: base.step_idx,
+                            op_idx: base.op_idx,
+                            chip_name: base.chip_name.clone(),
+                            size_bytes,
+                            effective_ptr: *effective_ptr,
+                            read_data: read_data.clone(),
+                            prev_data: prev_data.clone(),
+                            write_data: write_data.clone(),
+                        });
+                    }
                 }
                 match profile.memory_semantic {
                     OpenVmMemoryObservationProfile::ImmediateSign => {
@@ -261,10 +476,42 @@ fn derive_semantic_feedback(
                 imm_sign,
                 mem_as,
                 effective_ptr,
+                rs1_val,
                 needs_write,
+                shifted_read_data,
+                data_most_sig_bit,
+                shift_most_sig_bit,
+                opcode_loadh_flag,
+                opcode_loadb_flag1,
+                opcode_loadb_flag0,
                 ..
             } => {
                 saw_memory_access = true;
+                load_sign_extend.push(LoadSignExtendObservation {
+                    step_idx: base.step_idx,
+                    op_idx: base.op_idx,
+                    chip_name: base.chip_name.clone(),
+                    rd_ptr: *rd_ptr,
+                    effective_ptr: *effective_ptr,
+                    needs_write: *needs_write,
+                    shifted_read_data: shifted_read_data.clone(),
+                    data_most_sig_bit: *data_most_sig_bit,
+                    shift_most_sig_bit: *shift_most_sig_bit,
+                    opcode_loadh_flag: *opcode_loadh_flag,
+                    opcode_loadb_flag1: *opcode_loadb_flag1,
+                    opcode_loadb_flag0: *opcode_loadb_flag0,
+                });
+                if let Some(rs1_val) = rs1_val {
+                    effective_ptr_obs.push(EffectivePtrObservation {
+                        step_idx: base.step_idx,
+                        op_idx: base.op_idx,
+                        chip_name: base.chip_name.clone(),
+                        op: *op,
+                        rs1_val: *rs1_val,
+                        imm: *imm,
+                        effective_ptr: *effective_ptr,
+                    });
+                }
                 record_signal(&mut signals, &mut seen_signals, TraceSignal::HasLoad);
                 record_signal(&mut signals, &mut seen_signals, TraceSignal::HasLoadStore);
                 match profile.memory_semantic {
@@ -303,9 +550,52 @@ fn derive_semantic_feedback(
                     OpenVmMemoryObservationProfile::None => {}
                 }
             }
+            OpenVMChipRowPayload::JalLui { imm, needs_write, from_pc, rd_data, is_jal, .. } => {
+                if let Some(rd_value) = limbs_to_u32(rd_data, LIMB_BITS, profile.limb_endianness) {
+                    jal_lui.push(JalLuiObservation {
+                        step_idx: base.step_idx,
+                        op_idx: base.op_idx,
+                        chip_name: base.chip_name.clone(),
+                        is_jal: *is_jal,
+                        needs_write: *needs_write,
+                        from_pc: *from_pc,
+                        imm: *imm,
+                        rd_value,
+                    });
+                }
+            }
+            OpenVMChipRowPayload::Program { execution_frequency, .. } => {
+                if let Some(row_id) = base.row_id.as_deref() {
+                    program_rows.push(ProgramRowObservation {
+                        step_idx: base.step_idx,
+                        op_idx: base.op_idx,
+                        row_id: row_id.to_string(),
+                        execution_frequency: *execution_frequency,
+                        anchored_interactions: interactions_per_row
+                            .get(row_id)
+                            .copied()
+                            .unwrap_or(0),
+                    });
+                }
+            }
             OpenVMChipRowPayload::Connector {
-                from_timestamp, to_timestamp, is_terminate, ..
+                from_pc,
+                to_pc,
+                from_timestamp,
+                to_timestamp,
+                is_terminate,
+                ..
             } => {
+                connectors.push(ConnectorObservation {
+                    step_idx: base.step_idx,
+                    op_idx: base.op_idx,
+                    chip_name: base.chip_name.clone(),
+                    from_pc: *from_pc,
+                    to_pc: *to_pc,
+                    from_timestamp: *from_timestamp,
+                    to_timestamp: *to_timestamp,
+                    is_terminate: *is_terminate,
+                });
                 if *is_terminate {
                     saw_system_terminate = true;
                     record_signal(&mut signals, &mut seen_signals, TraceSignal::HasEcall);
@@ -322,6 +612,9 @@ fn derive_semantic_feedback(
                     });
                 }
             }
+            OpenVMChipRowPayload::Phantom { sub_kind: Some(sub_kind), .. } => {
+                phantom_sub_kinds.push((base.step_idx, *sub_kind));
+            }
             _ => {}
         }
     }
@@ -352,11 +645,25 @@ fn derive_semantic_feedback(
     bucket_hits
         .extend(semantic_matchers::match_memory_address_space_semantic_hits(&memory_address_space));
     bucket_hits.extend(semantic_matchers::match_boundary_origin_semantic_hits(&boundary_origin));
+    bucket_hits.extend(semantic_matchers::match_connector_chain_break_semantic_hits(&connectors));
+    bucket_hits.extend(semantic_matchers::match_jal_lui_rd_semantic_hits(&jal_lui));
+    bucket_hits.extend(semantic_matchers::match_program_row_frequency_semantic_hits(&program_rows));
     bucket_hits
         .extend(semantic_matchers::match_volatile_boundary_semantic_hits(&volatile_boundary));
     bucket_hits.extend(semantic_matchers::match_arithmetic_special_case_semantic_hits(
         &arithmetic_special_case,
     ));
+    bucket_hits.extend(semantic_matchers::match_mul_mulh_product_semantic_hits(&mul_product));
+    bucket_hits.extend(semantic_matchers::match_effective_ptr_semantic_hits(&effective_ptr_obs));
+    bucket_hits.extend(semantic_matchers::match_range_check_semantic_hits(&range_checks));
+    bucket_hits.extend(semantic_matchers::match_phantom_sub_kind_semantic_hits(phantom_sub_kinds));
+    bucket_hits.extend(semantic_matchers::match_store_write_data_semantic_hits(&store_merge));
+    bucket_hits.extend(semantic_matchers::match_load_sign_extend_semantic_hits(&load_sign_extend));
+    bucket_hits.extend(semantic_matchers::match_opcode_chip_kind_semantic_hits(&opcode_dispatch));
+    bucket_hits.extend(semantic_matchers::match_zero_register_semantic_hits(&zero_reg));
+    bucket_hits.extend(semantic_matchers::match_interaction_row_timestamp_semantic_hits(
+        &interaction_timestamps,
+    ));
     (bucket_hits, signals)
 }
 
@@ -375,7 +682,10 @@ impl OpenVMTrace {
         let mut chip_rows = Vec::new();
         let mut interactions = Vec::new();
 
+        validate_seq_monotonic(&logs)?;
+        let op_spans = build_op_spans_from_step_idx(&logs).ok();
         for (idx, log) in logs.into_iter().enumerate() {
+            validate_micro_op_envelope(&log).map_err(|e| format!("log[{}]: {}", idx, e))?;
             let obj = log.as_object().ok_or_else(|| format!("log[{}]: not an object", idx))?;
             let ty = obj
                 .get("type")
@@ -406,7 +716,70 @@ impl OpenVMTrace {
             }
         }
 
-        Ok(Self::new(instructions, chip_rows, interactions))
+        let mut trace = Self::new(instructions, chip_rows, interactions);
+        trace.op_spans = op_spans;
+        Ok(trace)
+    }
+
+    /// Structural checks over the parsed trace, run before its buckets are trusted:
+    /// - `step_idx` never decreases within instructions, chip rows and interactions (each in
+    ///   emission order); `seq` order is already enforced by `from_logs`;
+    /// - chip-row `op_idx` values of every step are exactly `0..n`;
+    /// - every interaction's `row_id` names an emitted chip row. Interactions emitted before
+    ///   any chip row carry an empty `row_id` and are not checked.
+    pub fn check_invariants(&self) -> Vec<TraceInvariantViolation> {
+        let mut violations = Vec::new();
+        let mut violation = |invariant: &'static str, detail: String| {
+            violations.push(TraceInvariantViolation { invariant, detail });
+        };
+
+        let streams: [(&str, Vec<u64>); 3] = [
+            ("instruction", self.instructions.iter().map(|i| i.step_idx).collect()),
+            ("chip_row", self.chip_rows.iter().map(|r| r.base().step_idx).collect()),
+            ("interaction", self.interactions.iter().map(|ia| ia.base().step_idx).collect()),
+        ];
+        for (name, steps) in &streams {
+            for (idx, pair) in steps.windows(2).enumerate() {
+                let (prev_step, step) = (pair[0], pair[1]);
+                if step < prev_step {
+                    violation(
+                        "StepIdxDecreasing",
+                        format!(
+                            "{name}[{}]: step_idx={step} follows step_idx={prev_step}",
+                            idx + 1
+                        ),
+                    );
+                }
+            }
+        }
+
+        for (step, rows) in self.chip_rows_by_step.iter().enumerate() {
+            let mut op_idxs: Vec<u64> =
+                rows.iter().map(|&i| self.chip_rows[i].base().op_idx).collect();
+            op_idxs.sort_unstable();
+            if op_idxs.iter().enumerate().any(|(expected, &op_idx)| op_idx != expected as u64) {
+                violation(
+                    "ChipRowOpIdxGap",
+                    format!(
+                        "step_idx={step}: chip_row op_idx values {op_idxs:?} are not 0..{}",
+                        op_idxs.len()
+                    ),
+                );
+            }
+        }
+
+        let row_ids: HashSet<&str> =
+            self.chip_rows.iter().filter_map(|r| r.base().row_id.as_deref()).collect();
+        for ia in &self.interactions {
+            let b = ia.base();
+            if !b.row_id.is_empty() && !row_ids.contains(b.row_id.as_str()) {
+                violation(
+                    "InteractionRowIdUnresolved",
+                    format!("interaction seq={}: row_id={:?} names no chip row", b.seq, b.row_id),
+                );
+            }
+        }
+        violations
     }
 }
 
@@ -492,20 +865,33 @@ impl OpenVMTrace {
             interactions_by_step,
             interactions_by_row_id,
             interactions_by_bus,
+            op_spans: None,
         };
 
-        let (bucket_hits, trace_signals) = derive_semantic_feedback(
+        let (mut bucket_hits, trace_signals) = derive_semantic_feedback(
             &out,
-            OpenVmObservationProfile {
-                emit_alu_immediate_limb_semantic: true,
-                emit_xor_multiplicity_semantic: false,
-                emit_auipc_pc_limb_semantic: true,
-                memory_semantic: OpenVmMemoryObservationProfile::AddressSpace,
-                emit_boundary_origin_semantic: true,
-                emit_volatile_boundary_semantic: true,
-                emit_arithmetic_special_case_semantic: false,
-            },
+            out.chip_rows(),
+            out.interactions(),
+            OBSERVATION_PROFILE,
         );
+        bucket_hits.extend(semantic_matchers::match_program_length_semantic_hits(
+            out.instructions().len(),
+            None,
+        ));
+        bucket_hits.extend(semantic_matchers::match_operand_out_of_field_semantic_hits(
+            out.instructions().iter().map(|i| (i.step_idx, i.out_of_field_operands.as_slice())),
+        ));
+        bucket_hits.extend(semantic_matchers::match_operand_non_canonical_semantic_hits(
+            out.instructions().iter().map(|i| (i.step_idx, i.operands.as_slice())),
+            BABYBEAR_MODULUS,
+        ));
+        bucket_hits.extend(semantic_matchers::match_interaction_kind_semantic_hits(
+            out.interactions().iter().map(|ia| ia.base.kind.as_str()),
+        ));
+        bucket_hits.extend(semantic_matchers::match_bus_balance_semantic_hits(
+            out.interactions().iter().map(|ia| (ia.base.kind.as_str(), ia.signed_multiplicity())),
+        ));
+        bound_bucket_hit_details(&mut bucket_hits, max_details_per_bucket());
         out.bucket_hits = bucket_hits;
         out.trace_signals = trace_signals;
         out
@@ -515,6 +901,12 @@ impl OpenVMTrace {
         &self.instructions
     }
 
+    /// `op_spans[step_idx]` is the range of that step's micro-ops in the stream passed to
+    /// `from_logs`. `None` for traces built with `new` or whose steps were interleaved.
+    pub fn op_spans(&self) -> Option<&[Range<usize>]> {
+        self.op_spans.as_deref()
+    }
+
     pub fn chip_rows(&self) -> &[OpenVMChipRow] {
         &self.chip_rows
     }
@@ -629,6 +1021,25 @@ impl OpenVMTrace {
     }
 }
 
+/// Bucket hits matched independently for each step, keyed by `step_idx`.
+///
+/// Unlike `Trace::bucket_hits`, nothing is deduplicated across steps, so a pattern recurring on
+/// many steps shows up once per step with that step's details. Analysis-only: novelty feedback
+/// keeps using the trace-wide hits. Trace-level buckets (program length) are not included.
+pub fn match_bucket_hits_per_step(trace: &OpenVMTrace) -> HashMap<usize, Vec<BucketHit>> {
+    (0..trace.chip_rows_by_step.len())
+        .filter_map(|step| {
+            let (hits, _) = derive_semantic_feedback(
+                trace,
+                trace.chip_rows_for_step(step),
+                trace.interactions_for_step(step),
+                OBSERVATION_PROFILE,
+            );
+            (!hits.is_empty()).then_some((step, hits))
+        })
+        .collect()
+}
+
 impl Trace for OpenVMTrace {
     fn bucket_hits(&self) -> &[BucketHit] {
         &self.bucket_hits
@@ -638,3 +1049,165 @@ impl Trace for OpenVMTrace {
         &self.trace_signals
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use beak_core::trace::semantic;
+    use serde_json::json;
+
+    use super::{OpenVMChipRow, OpenVMTrace, match_bucket_hits_per_step};
+    use crate::interaction::{OpenVMInteractionKind, OpenVMInteractionPayload};
+
+    fn range_check_row(seq: u64, step_idx: u64, row_id: &str) -> OpenVMChipRow {
+        serde_json::from_value(json!({
+            "base": {
+                "seq": seq,
+                "step_idx": step_idx,
+                "op_idx": 0,
+                "is_valid": true,
+                "chip_name": "VariableRangeChecker",
+                "row_id": row_id,
+            },
+            "kind": "range_check",
+            "payload": { "type": "range_check", "data": { "value": 1, "max_bits": 8, "mult": 1 } },
+        }))
+        .expect("chip row")
+    }
+
+    fn alu_row(seq: u64, rd_ptr: u32, a: [u8; 4]) -> OpenVMChipRow {
+        serde_json::from_value(json!({
+            "base": {
+                "seq": seq,
+                "step_idx": seq,
+                "op_idx": 0,
+                "is_valid": true,
+                "chip_name": "BaseAlu",
+                "row_id": format!("alu{seq}"),
+            },
+            "kind": "base_alu",
+            "payload": {
+                "type": "base_alu",
+                "data": {
+                    "op": 0,
+                    "rd_ptr": rd_ptr,
+                    "rs1_ptr": 4,
+                    "rs2": { "src": "imm", "value": 1 },
+                    "a": a,
+                    "b": [0, 0, 0, 0],
+                    "c": [1, 0, 0, 0],
+                },
+            },
+        }))
+        .expect("chip row")
+    }
+
+    #[test]
+    fn per_step_hits_only_see_that_steps_range_checks() {
+        let chip_rows = vec![range_check_row(0, 0, "r0"), range_check_row(2, 1, "r1")];
+        let interactions = vec![
+            serde_json::from_value(json!({
+                "base": {
+                    "seq": 1,
+                    "step_idx": 0,
+                    "op_idx": 0,
+                    "row_id": "r0",
+                    "direction": "send",
+                    "kind": "range_check",
+                },
+                "payload": { "type": "range_check", "data": { "value": 300, "max_bits": 8 } },
+            }))
+            .expect("interaction"),
+        ];
+        let trace = OpenVMTrace::new(Vec::new(), chip_rows, interactions);
+
+        let per_step = match_bucket_hits_per_step(&trace);
+        let has_out_of_range = |step: usize| {
+            per_step.get(&step).is_some_and(|hits| {
+                hits.iter()
+                    .any(|hit| hit.bucket_id == semantic::lookup::RANGE_CHECK_VALUE_OUT_OF_RANGE.id)
+            })
+        };
+        assert!(has_out_of_range(0));
+        assert!(!has_out_of_range(1));
+    }
+
+    #[test]
+    fn unmodeled_interaction_kinds_parse_as_other() {
+        let logs = vec![json!({
+            "type": "interaction",
+            "data": {
+                "base": {
+                    "seq": 0,
+                    "step_idx": 0,
+                    "op_idx": 0,
+                    "row_id": "",
+                    "direction": "send",
+                    "kind": "poseidon2",
+                },
+                "payload": { "type": "poseidon2", "data": { "state": [1, 2, 3] } },
+            },
+        })];
+        let trace = OpenVMTrace::from_logs(logs).expect("unknown kinds must not fail parsing");
+
+        let ia = &trace.interactions()[0];
+        assert_eq!(ia.base.kind, OpenVMInteractionKind::Other);
+        assert!(matches!(ia.payload, OpenVMInteractionPayload::Other));
+        ia.validate_kind_matches_payload().expect("catch-all kind and payload agree");
+        let other = semantic::interaction::KIND_SEEN.last().expect("other is the last kind");
+        assert!(trace.bucket_hits.iter().any(|hit| hit.bucket_id == other.id));
+    }
+
+    #[test]
+    fn x0_rows_with_a_nonzero_result_are_effectful_writes() {
+        let hit_ids = |rows| -> Vec<String> {
+            let trace = OpenVMTrace::new(Vec::new(), rows, Vec::new());
+            trace.bucket_hits.into_iter().map(|hit| hit.bucket_id).collect()
+        };
+        let immutability = semantic::decode::ZERO_REGISTER_IMMUTABILITY.id.to_string();
+        let effectful = semantic::decode::ZERO_REGISTER_WRITE_EFFECTFUL.id.to_string();
+
+        let ids = hit_ids(vec![alu_row(0, 0, [0; 4]), alu_row(1, 8, [5, 0, 0, 0])]);
+        assert!(ids.contains(&immutability));
+        assert!(!ids.contains(&effectful));
+
+        let ids = hit_ids(vec![alu_row(0, 0, [0; 4]), alu_row(1, 0, [1, 0, 0, 0])]);
+        assert!(ids.contains(&effectful));
+    }
+
+    #[test]
+    fn invariants_flag_step_order_op_idx_gaps_and_dangling_row_ids() {
+        let ok = OpenVMTrace::new(
+            Vec::new(),
+            vec![range_check_row(0, 0, "r0"), range_check_row(1, 1, "r1")],
+            Vec::new(),
+        );
+        assert!(ok.check_invariants().is_empty());
+
+        // Step 1 twice around step 0, both at op_idx 0, and an interaction on an unknown row.
+        let chip_rows = vec![
+            range_check_row(0, 1, "r0"),
+            range_check_row(1, 0, "r1"),
+            range_check_row(2, 1, "r2"),
+        ];
+        let interactions = vec![
+            serde_json::from_value(json!({
+                "base": {
+                    "seq": 3,
+                    "step_idx": 1,
+                    "op_idx": 0,
+                    "row_id": "r9",
+                    "direction": "send",
+                    "kind": "range_check",
+                },
+                "payload": { "type": "range_check", "data": { "value": 1, "max_bits": 8 } },
+            }))
+            .expect("interaction"),
+        ];
+        let trace = OpenVMTrace::new(Vec::new(), chip_rows, interactions);
+        let invariants: Vec<&str> = trace.check_invariants().iter().map(|v| v.invariant).collect();
+        assert_eq!(
+            invariants,
+            vec!["StepIdxDecreasing", "ChipRowOpIdxGap", "InteractionRowIdUnresolved"]
+        );
+    }
+}
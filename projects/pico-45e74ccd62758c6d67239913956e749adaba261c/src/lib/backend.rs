@@ -578,6 +578,10 @@ impl BenchmarkBackend for PicoBackend {
             final_regs: resp.final_regs,
             backend_error: resp.backend_error.clone(),
             semantic_injection_applied: resp.injection_applied,
+            injection_caught: None,
+            segment_count: 0,
+            final_memory: None,
+            trace_truncated: false,
         };
         self.last_observed_injection_sites = resp.observed_injection_sites;
 
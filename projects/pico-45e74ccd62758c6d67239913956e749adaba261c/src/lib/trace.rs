@@ -196,6 +196,9 @@ impl PicoTrace {
             },
             &insns,
         );
+        out.bucket_hits.extend(semantic_matchers::match_lui_addi_fusion_semantic_hits(
+            insns.iter().map(|insn| (insn.step_idx, insn.word)),
+        ));
         out
     }
 
@@ -8,7 +8,7 @@ use beak_core::rv32im::{
     instruction::RV32IMInstruction,
     oracle::{OracleConfig, OracleMemoryModel, RISCVOracle},
 };
-use beak_core::trace::{BucketHit, Trace, TraceSignal, semantic};
+use beak_core::trace::{BucketHit, Trace, TraceSignal, semantic, semantic_matchers};
 use risc0_binfmt::{MemoryImage, Program};
 use risc0_circuit_rv32im::{
     MAX_INSN_CYCLES,
@@ -237,6 +237,7 @@ fn oracle_fallback_regs(words: &[u32]) -> [u32; 32] {
             memory_model: OracleMemoryModel::SplitCodeData,
             code_base: crate::RISC0_ORACLE_CODE_BASE,
             data_size_bytes: 0,
+            fence_policy: Default::default(),
         },
     )
 }
@@ -373,6 +374,7 @@ pub fn run_backend_once(
     }
 
     let final_regs = final_regs_for_oracle(words).unwrap_or_else(|_| oracle_fallback_regs(words));
+    bucket_hits.extend(semantic_matchers::match_final_zero_register_semantic_hits(final_regs[0]));
 
     Ok(RunResponse {
         final_regs: Some(final_regs),
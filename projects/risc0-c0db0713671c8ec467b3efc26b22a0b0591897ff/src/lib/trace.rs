@@ -57,6 +57,7 @@ impl Risc0Trace {
                     pc,
                     raw_word: word,
                     mnemonic: dec.mnemonic.clone(),
+                    rd_data: None,
                 });
                 ecalls.push(EcallInsnObservation {
                     op_idx: op_idx as u64,
@@ -86,6 +87,7 @@ impl Risc0Trace {
                             pc,
                             raw_word: word,
                             mnemonic: dec.mnemonic.clone(),
+                            rd_data: None,
                         });
                     } else {
                         rd_bits.push(RdBitDecompositionObservation {
@@ -113,6 +115,9 @@ impl Risc0Trace {
             },
             &sequence,
         ));
+        bucket_hits.extend(semantic_matchers::match_lui_addi_fusion_semantic_hits(
+            sequence.iter().map(|insn| (insn.step_idx, insn.word)),
+        ));
         bucket_hits.extend(semantic_matchers::match_zero_register_semantic_hits(&zero_reg));
         bucket_hits.extend(semantic_matchers::match_rd_bit_semantic_hits(&rd_bits));
         bucket_hits.extend(semantic_matchers::match_division_semantic_hits(&divisions));
@@ -5,7 +5,9 @@ use std::path::{Path, PathBuf};
 use clap::{Arg, Command};
 use serde_json::json;
 
-use beak_core::fuzz::benchmark::{run_benchmark_threaded, BenchmarkConfig, DEFAULT_RNG_SEED};
+use beak_core::fuzz::benchmark::{
+    evaluate_stdin_threaded, run_benchmark_threaded, BenchmarkConfig, DEFAULT_RNG_SEED,
+};
 use beak_core::rv32im::oracle::{OracleConfig, OracleMemoryModel};
 
 use beak_sp1_39ab52fc::backend::{run_backend_once, Sp1Backend, WorkerRequest, WorkerResponse};
@@ -170,6 +172,12 @@ fn main() {
                 .action(clap::ArgAction::SetTrue)
                 .help("Run persistent backend worker loop from stdin JSONL."),
         )
+        .arg(
+            Arg::new("eval_stdin")
+                .long("eval-stdin")
+                .action(clap::ArgAction::SetTrue)
+                .help("Evaluate one program read from stdin (hex words or RV32IM assembly) and print a JSON report."),
+        )
         .get_matches();
 
     if matches.get_flag("worker_loop") {
@@ -248,7 +256,9 @@ fn main() {
             memory_model: oracle_memory_model,
             code_base: oracle_code_base,
             data_size_bytes: oracle_data_size_bytes,
+            fence_policy: Default::default(),
         },
+        ignored_regs: None,
         seeds_jsonl: seeds_path,
         out_dir: root.join("storage/fuzzing_seeds"),
         output_prefix: None,
@@ -261,8 +271,24 @@ fn main() {
         semantic_step_stride,
         semantic_max_trials_per_bucket,
         stack_size_bytes: 256 * 1024 * 1024,
+        record_bug_traces: false,
     };
 
+    if matches.get_flag("eval_stdin") {
+        let res =
+            evaluate_stdin_threaded(&cfg, move || Sp1Backend::new(max_instructions, timeout_ms));
+        match res {
+            Ok(report) => {
+                println!("{}", serde_json::to_string_pretty(&report).expect("serialize report"))
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     let res = run_benchmark_threaded(cfg, move || Sp1Backend::new(max_instructions, timeout_ms));
     match res {
         Ok(out) => {
@@ -271,6 +297,9 @@ fn main() {
             if let Some(runs_path) = out.runs_path.as_ref() {
                 println!("Wrote runs   JSONL: {}", runs_path.display());
             }
+            if let Some(histogram_path) = out.bucket_histogram_path.as_ref() {
+                println!("Wrote bucket histogram: {}", histogram_path.display());
+            }
         }
         Err(e) => {
             eprintln!("{e}");
@@ -79,6 +79,7 @@ fn sp1_oracle_config() -> OracleConfig {
         memory_model: OracleMemoryModel::SplitCodeData,
         code_base: 0x1000,
         data_size_bytes: 0,
+        fence_policy: Default::default(),
     }
 }
 
@@ -531,6 +532,9 @@ impl BenchmarkBackend for Sp1Backend {
             final_regs: resp.final_regs,
             backend_error: resp.backend_error.clone(),
             semantic_injection_applied: resp.injection_applied,
+            injection_caught: None,
+            segment_count: 0,
+            final_memory: None,
         };
         self.last_observed_injection_sites = resp.observed_injection_sites;
 
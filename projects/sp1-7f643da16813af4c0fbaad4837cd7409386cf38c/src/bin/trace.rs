@@ -85,6 +85,7 @@ fn main() {
         memory_model: oracle_memory_model,
         code_base: oracle_code_base,
         data_size_bytes: oracle_data_size_bytes,
+        fence_policy: Default::default(),
     };
 
     let words: Vec<u32> = input_words
@@ -475,6 +475,9 @@ impl Sp1Trace {
             },
             &insns,
         );
+        out.bucket_hits.extend(semantic_matchers::match_lui_addi_fusion_semantic_hits(
+            insns.iter().map(|insn| (insn.step_idx, insn.word)),
+        ));
         out
     }
 